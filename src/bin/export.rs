@@ -0,0 +1,70 @@
+//! Export a saved log, or the raid runs saved alongside logs, as CSV or JSON.
+//!
+//! Usage: `export <log> [<table>] [csv|json]`, where the table is one of summary, skills, hits,
+//! damage_taken, counters, stagger, stagger_contributions, shields and uptime, or
+//! `export <log dir> [runs|battle_items] [csv|json]`, writing to stdout.
+
+use std::{io, path::Path};
+
+use anyhow::Context;
+use larps::{
+    export::{self, Format},
+    persist,
+};
+
+fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let Some(path) = args.first() else {
+        anyhow::bail!(
+            "usage: export <log> [summary|skills|hits|damage_taken|counters|stagger|\
+             stagger_contributions|shields|uptime] [csv|json]\n       \
+             export <log dir> [runs|battle_items] [csv|json]"
+        );
+    };
+    let table = args.get(1).map(String::as_str).unwrap_or("summary");
+    let format = match args.get(2).map(String::as_str).unwrap_or("csv") {
+        "csv" => Format::Csv,
+        "json" => Format::Json,
+        other => anyhow::bail!("unknown format {}, expected csv or json", other),
+    };
+
+    if let "runs" | "battle_items" = table {
+        let runs = persist::list_runs(Path::new(path))
+            .with_context(|| format!("listing raid runs in {}", path))?
+            .iter()
+            .map(|path| {
+                persist::read_run(path).with_context(|| format!("reading {}", path.display()))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let out = io::BufWriter::new(io::stdout().lock());
+        return match table {
+            "runs" => export::write(&export::run_records(&runs), format, out),
+            _ => export::write(&export::battle_item_records(&runs), format, out),
+        };
+    }
+
+    let log = persist::read_log(Path::new(path)).with_context(|| format!("reading {}", path))?;
+    let (env, enc) = log.into_encounter();
+
+    let out = io::BufWriter::new(io::stdout().lock());
+    match table {
+        "summary" => export::write(&export::player_summaries(&enc, &env), format, out),
+        "skills" => export::write(&export::skill_summaries(&enc, &env), format, out),
+        "hits" => export::write(&export::hit_records(&enc, &env), format, out),
+        "damage_taken" => export::write(&export::damage_taken_records(&enc, &env), format, out),
+        "counters" => export::write(&export::counter_records(&enc, &env), format, out),
+        "stagger" => export::write(&export::stagger_check_records(&enc, &env), format, out),
+        "stagger_contributions" => export::write(
+            &export::stagger_contribution_records(&enc, &env),
+            format,
+            out,
+        ),
+        "shields" => export::write(&export::shield_records(&enc, &env), format, out),
+        "uptime" => export::write(&export::uptime_records(&enc, &env), format, out),
+        other => anyhow::bail!(
+            "unknown table {}, expected summary, skills, hits, damage_taken, counters, stagger, \
+             stagger_contributions, shields, uptime, runs or battle_items",
+            other
+        ),
+    }
+}
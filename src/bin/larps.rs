@@ -9,22 +9,28 @@ use std::{
 use larps::{
     capture,
     meter::{Data, Meter},
+    persist::{LogConfig, LogWriter},
     ui,
 };
 
 fn main() -> anyhow::Result<()> {
     let (ctx_oneshot_tx, ctx_oneshot_rx) = mpsc::channel();
     let data = Data::new();
+    data.lock().log_writer = Some(LogWriter::spawn(LogConfig::from_env()));
     start_capture(ctx_oneshot_rx, Arc::clone(&data));
-    ui::run(ctx_oneshot_tx, data, 8)
+    let result = ui::run(ctx_oneshot_tx, Arc::clone(&data), 8);
+    data.lock().close_log_writer();
+    result
 }
 
 fn start_capture(ctx_rx: mpsc::Receiver<egui::Context>, data: Arc<parking_lot::Mutex<Data>>) {
     thread::spawn(move || {
         let ctx = ctx_rx.recv().expect("egui context channel closed");
-        let meter = Meter::new(ctx, data).expect("meter init failed -- missing resources?");
+        let meter =
+            Meter::new(ctx, Arc::clone(&data)).expect("meter init failed -- missing resources?");
         if let Err(e) = capture::run(meter) {
             println!("backend: {:?}\nclosing", e);
+            data.lock().close_log_writer();
             process::exit(1);
         }
     });
@@ -1,4 +1,5 @@
 use std::{
+    collections::BTreeMap,
     sync::{mpsc, Arc},
     time::{Duration, Instant},
 };
@@ -70,10 +71,25 @@ pub fn main() -> anyhow::Result<()> {
             last_damage: None,
             environment: 0,
             players,
+            npcs: BTreeMap::new(),
+            damage_taken: BTreeMap::new(),
+            deaths: Vec::new(),
+            counters: BTreeMap::new(),
+            stagger: BTreeMap::new(),
+            identity: BTreeMap::new(),
+            status_effects: BTreeMap::new(),
+            shields: BTreeMap::new(),
+            zone_status_effects: Vec::new(),
             tracked: Vec::new(),
             wipe: false,
             clear: false,
+            zone: None,
+            raid_id: None,
+            buff_categories: Arc::new([]),
         }],
+        raids: Vec::new(),
+        log_writer: None,
+        saved: Vec::new(),
     };
 
     let data = Arc::new(parking_lot::Mutex::new(data));
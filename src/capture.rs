@@ -2,11 +2,13 @@
 
 use anyhow::Context;
 
+pub use crate::generated::handler::PacketHandler;
+
 use crate::{
     definitions::Opcode,
+    generated::handler,
     oodle::OodleDecompressor,
-    packet,
-    parser::{Event, Packet, Parser},
+    parser::{Event, Parser},
     socket::{SelectError, Sockets},
     util,
 };
@@ -113,7 +115,7 @@ pub fn run<P: PacketHandler>(mut handler: P) -> anyhow::Result<!> {
 }
 
 // Parse, but append additional context in case of failure
-fn parse_with_context<'bump, T>(
+pub(crate) fn parse_with_context<'bump, T>(
     parser: &mut Parser,
     bump: &'bump mut bumpalo::Bump,
 ) -> anyhow::Result<T::Out>
@@ -168,367 +170,5 @@ fn parse_loa_packet<P: PacketHandler>(
     };
 
     let mut parser = Parser::new(packet);
-    match opcode {
-        Opcode::RaidBossKillNotify => {
-            let pkt = parse_with_context::<packet::PktRaidBossKillNotify>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_raid_boss_kill_notify(pkt)?;
-        }
-        Opcode::NewPc => {
-            let pkt = parse_with_context::<packet::PktNewPc>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_new_pc(pkt)?;
-        }
-        Opcode::SkillDamageAbnormalMoveNotify => {
-            let pkt =
-                parse_with_context::<packet::PktSkillDamageAbnormalMoveNotify>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_skill_damage_abnormal_move_notify(pkt)?;
-        }
-        Opcode::AddonSkillFeatureChangeNotify => {
-            let pkt =
-                parse_with_context::<packet::PktAddonSkillFeatureChangeNotify>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_addon_skill_feature_change_notify(pkt)?;
-        }
-        Opcode::StatusEffectDurationNotify => {
-            let pkt =
-                parse_with_context::<packet::PktStatusEffectDurationNotify>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_status_effect_duration_notify(pkt)?;
-        }
-        Opcode::PassiveStatusEffectRemoveNotify => {
-            let pkt = parse_with_context::<packet::PktPassiveStatusEffectRemoveNotify>(
-                &mut parser,
-                bump,
-            )?;
-            handler.on_packet(&pkt);
-            handler.on_passive_status_effect_remove_notify(pkt)?;
-        }
-        Opcode::StatusEffectRemoveNotify => {
-            let pkt = parse_with_context::<packet::PktStatusEffectRemoveNotify>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_status_effect_remove_notify(pkt)?;
-        }
-        Opcode::StatusEffectSyncDataNotify => {
-            let pkt =
-                parse_with_context::<packet::PktStatusEffectSyncDataNotify>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_status_effect_sync_data_notify(pkt)?;
-        }
-        Opcode::TroopMemberUpdateMinNotify => {
-            let pkt =
-                parse_with_context::<packet::PktTroopMemberUpdateMinNotify>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_troop_member_update_min_notify(pkt)?;
-        }
-        Opcode::InitItem => {
-            let pkt = parse_with_context::<packet::PktInitItem>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_init_item(pkt)?;
-        }
-        Opcode::ActiveAbilityNotify => {
-            let pkt = parse_with_context::<packet::PktActiveAbilityNotify>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_active_ability_notify(pkt)?;
-        }
-        Opcode::SkillStageNotify => {
-            let pkt = parse_with_context::<packet::PktSkillStageNotify>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_skill_stage_notify(pkt)?;
-        }
-        Opcode::StatusEffectAddNotify => {
-            let pkt = parse_with_context::<packet::PktStatusEffectAddNotify>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_status_effect_add_notify(pkt)?;
-        }
-        Opcode::NewNpc => {
-            let pkt = parse_with_context::<packet::PktNewNpc>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_new_npc(pkt)?;
-        }
-        Opcode::DeathNotify => {
-            let pkt = parse_with_context::<packet::PktDeathNotify>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_death_notify(pkt)?;
-        }
-        Opcode::InitPc => {
-            let pkt = parse_with_context::<packet::PktInitPc>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_init_pc(pkt)?;
-        }
-        Opcode::IdentityStanceChangeNotify => {
-            let pkt =
-                parse_with_context::<packet::PktIdentityStanceChangeNotify>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_identity_stance_change_notify(pkt)?;
-        }
-        Opcode::SkillDamageNotify => {
-            let pkt = parse_with_context::<packet::PktSkillDamageNotify>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_skill_damage_notify(pkt)?;
-        }
-        Opcode::ParalyzationStateNotify => {
-            let pkt = parse_with_context::<packet::PktParalyzationStateNotify>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_paralyzation_state_notify(pkt)?;
-        }
-        Opcode::EquipLifeToolChangeNotify => {
-            let pkt =
-                parse_with_context::<packet::PktEquipLifeToolChangeNotify>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_equip_life_tool_change_notify(pkt)?;
-        }
-        Opcode::AuthTokenResult => {
-            let pkt = parse_with_context::<packet::PktAuthTokenResult>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_auth_token_result(pkt)?;
-        }
-        Opcode::CounterAttackNotify => {
-            let pkt = parse_with_context::<packet::PktCounterAttackNotify>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_counter_attack_notify(pkt)?;
-        }
-        Opcode::TriggerBossBattleStatus => {
-            let pkt = parse_with_context::<packet::PktTriggerBossBattleStatus>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_trigger_boss_battle_status(pkt)?;
-        }
-        Opcode::PartyStatusEffectAddNotify => {
-            let pkt =
-                parse_with_context::<packet::PktPartyStatusEffectAddNotify>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_party_status_effect_add_notify(pkt)?;
-        }
-        Opcode::InitAbility => {
-            let pkt = parse_with_context::<packet::PktInitAbility>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_init_ability(pkt)?;
-        }
-        Opcode::SkillCastNotify => {
-            let pkt = parse_with_context::<packet::PktSkillCastNotify>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_skill_cast_notify(pkt)?;
-        }
-        Opcode::NewTrap => {
-            let pkt = parse_with_context::<packet::PktNewTrap>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_new_trap(pkt)?;
-        }
-        Opcode::BlockSkillStateNotify => {
-            let pkt = parse_with_context::<packet::PktBlockSkillStateNotify>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_block_skill_state_notify(pkt)?;
-        }
-        Opcode::NewNpcSummon => {
-            let pkt = parse_with_context::<packet::PktNewNpcSummon>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_new_npc_summon(pkt)?;
-        }
-        Opcode::PartyStatusEffectResultNotify => {
-            let pkt =
-                parse_with_context::<packet::PktPartyStatusEffectResultNotify>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_party_status_effect_result_notify(pkt)?;
-        }
-        Opcode::ZoneStatusEffectAddNotify => {
-            let pkt =
-                parse_with_context::<packet::PktZoneStatusEffectAddNotify>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_zone_status_effect_add_notify(pkt)?;
-        }
-        Opcode::ZoneObjectUnpublishNotify => {
-            let pkt =
-                parse_with_context::<packet::PktZoneObjectUnpublishNotify>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_zone_object_unpublish_notify(pkt)?;
-        }
-        Opcode::InitEnv => {
-            let pkt = parse_with_context::<packet::PktInitEnv>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_init_env(pkt)?;
-        }
-        Opcode::IdentityGaugeChangeNotify => {
-            let pkt =
-                parse_with_context::<packet::PktIdentityGaugeChangeNotify>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_identity_gauge_change_notify(pkt)?;
-        }
-        Opcode::SkillStartNotify => {
-            let pkt = parse_with_context::<packet::PktSkillStartNotify>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_skill_start_notify(pkt)?;
-        }
-        Opcode::InitLocal => {
-            let pkt = parse_with_context::<packet::PktInitLocal>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_init_local(pkt)?;
-        }
-        Opcode::PartyLeaveResult => {
-            let pkt = parse_with_context::<packet::PktPartyLeaveResult>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_party_leave_result(pkt)?;
-        }
-        Opcode::PassiveStatusEffectAddNotify => {
-            let pkt =
-                parse_with_context::<packet::PktPassiveStatusEffectAddNotify>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_passive_status_effect_add_notify(pkt)?;
-        }
-        Opcode::PartyPassiveStatusEffectAddNotify => {
-            let pkt = parse_with_context::<packet::PktPartyPassiveStatusEffectAddNotify>(
-                &mut parser,
-                bump,
-            )?;
-            handler.on_packet(&pkt);
-            handler.on_party_passive_status_effect_add_notify(pkt)?;
-        }
-        Opcode::PartyInfo => {
-            let pkt = parse_with_context::<packet::PktPartyInfo>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_party_info(pkt)?;
-        }
-        Opcode::TriggerFinishNotify => {
-            let pkt = parse_with_context::<packet::PktTriggerFinishNotify>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_trigger_finish_notify(pkt)?;
-        }
-        Opcode::PartyStatusEffectRemoveNotify => {
-            let pkt =
-                parse_with_context::<packet::PktPartyStatusEffectRemoveNotify>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_party_status_effect_remove_notify(pkt)?;
-        }
-        Opcode::TriggerStartNotify => {
-            let pkt = parse_with_context::<packet::PktTriggerStartNotify>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_trigger_start_notify(pkt)?;
-        }
-        Opcode::ZoneMemberLoadStatusNotify => {
-            let pkt =
-                parse_with_context::<packet::PktZoneMemberLoadStatusNotify>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_zone_member_load_status_notify(pkt)?;
-        }
-        Opcode::NewProjectile => {
-            let pkt = parse_with_context::<packet::PktNewProjectile>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_new_projectile(pkt)?;
-        }
-        Opcode::ZoneStatusEffectRemoveNotify => {
-            let pkt =
-                parse_with_context::<packet::PktZoneStatusEffectRemoveNotify>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_zone_status_effect_remove_notify(pkt)?;
-        }
-        Opcode::RemoveObject => {
-            let pkt = parse_with_context::<packet::PktRemoveObject>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_remove_object(pkt)?;
-        }
-        Opcode::StatChangeOriginNotify => {
-            let pkt = parse_with_context::<packet::PktStatChangeOriginNotify>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_stat_change_origin_notify(pkt)?;
-        }
-        Opcode::PartyPassiveStatusEffectRemoveNotify => {
-            let pkt = parse_with_context::<packet::PktPartyPassiveStatusEffectRemoveNotify>(
-                &mut parser,
-                bump,
-            )?;
-            handler.on_packet(&pkt);
-            handler.on_party_passive_status_effect_remove_notify(pkt)?;
-        }
-        Opcode::RaidResult => {
-            let pkt = parse_with_context::<packet::PktRaidResult>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_raid_result(pkt)?;
-        }
-        Opcode::AbilityChangeNotify => {
-            let pkt = parse_with_context::<packet::PktAbilityChangeNotify>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_ability_change_notify(pkt)?;
-        }
-        Opcode::MigrationExecute => {
-            let pkt = parse_with_context::<packet::PktMigrationExecute>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_migration_execute(pkt)?;
-        }
-        Opcode::EquipChangeNotify => {
-            let pkt = parse_with_context::<packet::PktEquipChangeNotify>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_equip_change_notify(pkt)?;
-        }
-        Opcode::RaidBegin => {
-            let pkt = parse_with_context::<packet::PktRaidBegin>(&mut parser, bump)?;
-            handler.on_packet(&pkt);
-            handler.on_raid_begin(pkt)?;
-        }
-    }
-    Ok(())
-}
-
-#[rustfmt::skip]
-pub trait PacketHandler {
-    fn on_raid_boss_kill_notify(&mut self, _: packet::PktRaidBossKillNotify) -> anyhow::Result<()> { Ok(()) }
-    fn on_new_pc(&mut self, _: packet::PktNewPc) -> anyhow::Result<()> { Ok(()) }
-    fn on_skill_damage_abnormal_move_notify(&mut self, _: packet::PktSkillDamageAbnormalMoveNotify) -> anyhow::Result<()> { Ok(()) }
-    fn on_addon_skill_feature_change_notify(&mut self, _: packet::PktAddonSkillFeatureChangeNotify) -> anyhow::Result<()> { Ok(()) }
-    fn on_status_effect_duration_notify(&mut self, _: packet::PktStatusEffectDurationNotify) -> anyhow::Result<()> { Ok(()) }
-    fn on_passive_status_effect_remove_notify(&mut self, _: packet::PktPassiveStatusEffectRemoveNotify) -> anyhow::Result<()> { Ok(()) }
-    fn on_status_effect_remove_notify(&mut self, _: packet::PktStatusEffectRemoveNotify) -> anyhow::Result<()> { Ok(()) }
-    fn on_status_effect_sync_data_notify(&mut self, _: packet::PktStatusEffectSyncDataNotify) -> anyhow::Result<()> { Ok(()) }
-    fn on_troop_member_update_min_notify(&mut self, _: packet::PktTroopMemberUpdateMinNotify) -> anyhow::Result<()> { Ok(()) }
-    fn on_init_item(&mut self, _: packet::PktInitItem) -> anyhow::Result<()> { Ok(()) }
-    fn on_active_ability_notify(&mut self, _: packet::PktActiveAbilityNotify) -> anyhow::Result<()> { Ok(()) }
-    fn on_skill_stage_notify(&mut self, _: packet::PktSkillStageNotify) -> anyhow::Result<()> { Ok(()) }
-    fn on_status_effect_add_notify(&mut self, _: packet::PktStatusEffectAddNotify) -> anyhow::Result<()> { Ok(()) }
-    fn on_new_npc(&mut self, _: packet::PktNewNpc) -> anyhow::Result<()> { Ok(()) }
-    fn on_death_notify(&mut self, _: packet::PktDeathNotify) -> anyhow::Result<()> { Ok(()) }
-    fn on_init_pc(&mut self, _: packet::PktInitPc) -> anyhow::Result<()> { Ok(()) }
-    fn on_identity_stance_change_notify(&mut self, _: packet::PktIdentityStanceChangeNotify) -> anyhow::Result<()> { Ok(()) }
-    fn on_skill_damage_notify(&mut self, _: packet::PktSkillDamageNotify) -> anyhow::Result<()> { Ok(()) }
-    fn on_paralyzation_state_notify(&mut self, _: packet::PktParalyzationStateNotify) -> anyhow::Result<()> { Ok(()) }
-    fn on_equip_life_tool_change_notify(&mut self, _: packet::PktEquipLifeToolChangeNotify) -> anyhow::Result<()> { Ok(()) }
-    fn on_auth_token_result(&mut self, _: packet::PktAuthTokenResult) -> anyhow::Result<()> { Ok(()) }
-    fn on_counter_attack_notify(&mut self, _: packet::PktCounterAttackNotify) -> anyhow::Result<()> { Ok(()) }
-    fn on_trigger_boss_battle_status(&mut self, _: packet::PktTriggerBossBattleStatus) -> anyhow::Result<()> { Ok(()) }
-    fn on_party_status_effect_add_notify(&mut self, _: packet::PktPartyStatusEffectAddNotify) -> anyhow::Result<()> { Ok(()) }
-    fn on_init_ability(&mut self, _: packet::PktInitAbility) -> anyhow::Result<()> { Ok(()) }
-    fn on_skill_cast_notify(&mut self, _: packet::PktSkillCastNotify) -> anyhow::Result<()> { Ok(()) }
-    fn on_new_trap(&mut self, _: packet::PktNewTrap) -> anyhow::Result<()> { Ok(()) }
-    fn on_block_skill_state_notify(&mut self, _: packet::PktBlockSkillStateNotify) -> anyhow::Result<()> { Ok(()) }
-    fn on_new_npc_summon(&mut self, _: packet::PktNewNpcSummon) -> anyhow::Result<()> { Ok(()) }
-    fn on_party_status_effect_result_notify(&mut self, _: packet::PktPartyStatusEffectResultNotify) -> anyhow::Result<()> { Ok(()) }
-    fn on_zone_status_effect_add_notify(&mut self, _: packet::PktZoneStatusEffectAddNotify) -> anyhow::Result<()> { Ok(()) }
-    fn on_zone_object_unpublish_notify(&mut self, _: packet::PktZoneObjectUnpublishNotify) -> anyhow::Result<()> { Ok(()) }
-    fn on_init_env(&mut self, _: packet::PktInitEnv) -> anyhow::Result<()> { Ok(()) }
-    fn on_identity_gauge_change_notify(&mut self, _: packet::PktIdentityGaugeChangeNotify) -> anyhow::Result<()> { Ok(()) }
-    fn on_skill_start_notify(&mut self, _: packet::PktSkillStartNotify) -> anyhow::Result<()> { Ok(()) }
-    fn on_init_local(&mut self, _: packet::PktInitLocal) -> anyhow::Result<()> { Ok(()) }
-    fn on_party_leave_result(&mut self, _: packet::PktPartyLeaveResult) -> anyhow::Result<()> { Ok(()) }
-    fn on_passive_status_effect_add_notify(&mut self, _: packet::PktPassiveStatusEffectAddNotify) -> anyhow::Result<()> { Ok(()) }
-    fn on_party_passive_status_effect_add_notify(&mut self, _: packet::PktPartyPassiveStatusEffectAddNotify) -> anyhow::Result<()> { Ok(()) }
-    fn on_party_info(&mut self, _: packet::PktPartyInfo) -> anyhow::Result<()> { Ok(()) }
-    fn on_trigger_finish_notify(&mut self, _: packet::PktTriggerFinishNotify) -> anyhow::Result<()> { Ok(()) }
-    fn on_party_status_effect_remove_notify(&mut self, _: packet::PktPartyStatusEffectRemoveNotify) -> anyhow::Result<()> { Ok(()) }
-    fn on_trigger_start_notify(&mut self, _: packet::PktTriggerStartNotify) -> anyhow::Result<()> { Ok(()) }
-    fn on_zone_member_load_status_notify(&mut self, _: packet::PktZoneMemberLoadStatusNotify) -> anyhow::Result<()> { Ok(()) }
-    fn on_new_projectile(&mut self, _: packet::PktNewProjectile) -> anyhow::Result<()> { Ok(()) }
-    fn on_zone_status_effect_remove_notify(&mut self, _: packet::PktZoneStatusEffectRemoveNotify) -> anyhow::Result<()> { Ok(()) }
-    fn on_remove_object(&mut self, _: packet::PktRemoveObject) -> anyhow::Result<()> { Ok(()) }
-    fn on_stat_change_origin_notify(&mut self, _: packet::PktStatChangeOriginNotify) -> anyhow::Result<()> { Ok(()) }
-    fn on_party_passive_status_effect_remove_notify(&mut self, _: packet::PktPartyPassiveStatusEffectRemoveNotify) -> anyhow::Result<()> { Ok(()) }
-    fn on_raid_result(&mut self, _: packet::PktRaidResult) -> anyhow::Result<()> { Ok(()) }
-    fn on_ability_change_notify(&mut self, _: packet::PktAbilityChangeNotify) -> anyhow::Result<()> { Ok(()) }
-    fn on_migration_execute(&mut self, _: packet::PktMigrationExecute) -> anyhow::Result<()> { Ok(()) }
-    fn on_equip_change_notify(&mut self, _: packet::PktEquipChangeNotify) -> anyhow::Result<()> { Ok(()) }
-    fn on_raid_begin(&mut self, _: packet::PktRaidBegin) -> anyhow::Result<()> { Ok(()) }
-
-    fn on_packet<P>(&mut self, _: &P) where P: Packet + serde::Serialize {}
-
-    /// Used to filter out unnecessary opcodes before parsing.
-    fn filter(_: &Opcode) -> bool {
-        true
-    }
+    handler::dispatch(handler, opcode, &mut parser, bump)
 }
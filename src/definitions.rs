@@ -3,48 +3,6 @@
 
 pub use crate::generated::opcode::Opcode;
 
-#[derive(Default, serde::Serialize)]
-pub struct SkillOptionData {
-    pub layer_index: Option<u8>,
-    pub start_stage_index: Option<u8>,
-    pub transit_index: Option<u32>,
-    pub stage_start_time: Option<u32>,
-    pub farmost_dist: Option<u32>,
-    pub tripod_index: Option<TripodIndex>,
-    pub tripod_level: Option<TripodLevel>,
-}
-
-#[derive(Default, serde::Serialize)]
-pub struct SkillMoveOptionData {
-    pub move_time: Option<u32>,
-    pub stand_up_time: Option<u32>,
-    pub down_time: Option<u32>,
-    pub freeze_time: Option<u32>,
-    pub move_height: Option<u32>,
-    pub farmost_dist: Option<u32>,
-}
-
-#[derive(Default, serde::Serialize)]
-pub struct MoveOptionData {
-    pub modifier: Option<u8>,
-    pub speed: Option<u32>,
-    pub next_pos: Option<u64>,
-}
-
-#[derive(serde::Serialize)]
-pub struct TripodIndex {
-    pub first: u8,
-    pub second: u8,
-    pub third: u8,
-}
-
-#[derive(serde::Serialize)]
-pub struct TripodLevel {
-    pub first: u16,
-    pub second: u16,
-    pub third: u16,
-}
-
 // TODO: generate these definitions
 
 #[derive(Debug, Copy, Clone, serde::Serialize)]
@@ -136,6 +94,51 @@ impl Class {
         }
     }
 
+    /// Inverse of [`Class::from_id`], with 0 for [`Class::Unknown`].
+    pub fn id(&self) -> u16 {
+        match self {
+            Self::Warrior => 101,
+            Self::Mage => 201,
+            Self::MartialArtist => 301,
+            Self::Assassin => 401,
+            Self::Gunner => 501,
+            Self::Specialist => 601,
+            Self::Berserker => 102,
+            Self::Destroyer => 103,
+            Self::Gunlancer => 104,
+            Self::Paladin => 105,
+            Self::Arcanist => 202,
+            Self::Summoner => 203,
+            Self::Bard => 204,
+            Self::Sorceress => 205,
+            Self::Wardancer => 302,
+            Self::Scrapper => 303,
+            Self::Soulfist => 304,
+            Self::Glaivier => 305,
+            Self::Deathblade => 402,
+            Self::Shadowhunter => 403,
+            Self::Reaper => 404,
+            Self::Souleater => 405,
+            Self::Sharpshooter => 502,
+            Self::Deadeye => 503,
+            Self::Artillerist => 504,
+            Self::Scouter => 505,
+            Self::FemaleGunner => 511,
+            Self::Gunslinger => 512,
+            Self::MaleMartialArtist => 311,
+            Self::Striker => 312,
+            Self::Artist => 602,
+            Self::Aeromancer => 603,
+            Self::FemaleWarrior => 111,
+            Self::Slayer => 112,
+            Self::Unknown => 0,
+        }
+    }
+
+    pub fn is_support(&self) -> bool {
+        matches!(self, Self::Bard | Self::Paladin | Self::Artist)
+    }
+
     pub fn color(&self) -> (u8, u8, u8) {
         match self {
             Self::Warrior => (0xff, 0xff, 0x00),
@@ -834,184 +837,173 @@ pub mod stat_type {
     pub const MAX: u8 = 152;
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
-pub enum Boss {
-    ValtanG1,
-    ValtanG2,
-    ValtanG2Ghost,
-
-    VykasG1,
-    VykasG2,
-    VykasG3,
-
-    KakulSaydonG1,
-    KakulSaydonG2,
-    KakulSaydonG3,
-    KakulSaydonG3Bingo,
-
-    BrelshazaG1Dogs,
-    BrelshazaG1Pre,
-    BrelshazaG1,
-    BrelshazaG2Prokel,
-    BrelshazaG2,
-    BrelshazaG3,
-    BrelshazaG4,
-    BrelshazaG5Cube,
-    BrelshazaG5,
-    BrelshazaG6,
-
-    AkkanG1,
-    AkkanG2,
-    AkkanG3,
-    AkkanG3Bonus,
-
-    Bird,
-    Tienis,
-    Prunya,
-    Lauriel,
-
-    Deskaluda,
-    Kungelanium,
-    Caliligos,
-    Hanumatan,
-    Sonavel,
-
-    Golem,
+/// Group of stats that increase damage dealt, for attributing damage to the status effects
+/// granting them. Bonuses within a group add up, while groups multiply.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DamageStat {
+    AttackPower,
+    Damage,
 }
 
-impl Boss {
-    pub fn name(&self) -> &'static str {
-        // TODO: temp system
-        match self {
-            Boss::ValtanG1 => "valtan-g1",
-            Boss::ValtanG2 => "valtan-g2",
-            Boss::ValtanG2Ghost => "valtan-g2-ghost",
-            Boss::VykasG1 => "vykas-g1",
-            Boss::VykasG2 => "vykas-g2",
-            Boss::VykasG3 => "vykas-g3",
-            Boss::KakulSaydonG1 => "clown-g1",
-            Boss::KakulSaydonG2 => "clown-g2",
-            Boss::KakulSaydonG3 => "clown-g3",
-            Boss::KakulSaydonG3Bingo => "clown-g3-bingo",
-            Boss::BrelshazaG1Dogs => "brel-g1-dogs",
-            Boss::BrelshazaG1Pre => "brel-g1-pre",
-            Boss::BrelshazaG1 => "brel-g1",
-            Boss::BrelshazaG2Prokel => "brel-g2-prokel",
-            Boss::BrelshazaG2 => "brel-g2",
-            Boss::BrelshazaG3 => "brel-g3",
-            Boss::BrelshazaG4 => "brel-g4",
-            Boss::BrelshazaG5Cube => "brel-g5-cube",
-            Boss::BrelshazaG5 => "brel-g5",
-            Boss::BrelshazaG6 => "brel-g6",
-            Boss::AkkanG1 => "akkan-g1",
-            Boss::AkkanG2 => "akkan-g2",
-            Boss::AkkanG3 => "akkan-g3",
-            Boss::AkkanG3Bonus => "akkan-g3-bonus",
-            Boss::Bird => "kayangel-bird",
-            Boss::Tienis => "kayangel-g1",
-            Boss::Prunya => "kayangel-g2",
-            Boss::Lauriel => "kayangel-g3",
-            Boss::Deskaluda => "deskaluda",
-            Boss::Kungelanium => "kungelanium",
-            Boss::Caliligos => "caliligos",
-            Boss::Hanumatan => "hanumatan",
-            Boss::Sonavel => "sonavel",
-            Boss::Golem => "golem",
-        }
+impl DamageStat {
+    /// Group of a stat as named in the status effect database, if it increases damage.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "attack_power_rate"
+            | "attack_power_rate_x"
+            | "attack_power_sub_rate_2"
+            | "attack_power_sub_rate_3" => Self::AttackPower,
+            "skill_damage_rate"
+            | "skill_damage_rate_x"
+            | "skill_damage_sub_rate_2"
+            | "skill_damage_sub_rate_3"
+            | "physical_inc_rate"
+            | "physical_inc_sub_rate_2"
+            | "physical_inc_sub_rate_3"
+            | "magical_inc_rate"
+            | "magical_inc_sub_rate_2"
+            | "magical_inc_sub_rate_3" => Self::Damage,
+            _ => return None,
+        })
     }
+}
 
-    pub fn from_id(id: u32) -> Option<Self> {
-        match id {
-            720011 => Some(Self::Golem),
-
-            480005 | 480026 | // Leader Lugaru 
-            480006 | 480031 | // Destroyer Lucas
-            480009 | 480010 // Dark Mountain Predator
-            => Some(Self::ValtanG1),
-            42063041 | 42063042 | 42063043 | 42063044 => Some(Self::ValtanG2),
-            480007 => Some(Self::ValtanG2Ghost),
-
-            480208 | // Incubus Morphe
-            480209 // Nightmarish Morphe
-            => Some(Self::VykasG1),
-            480210 => Some(Self::VykasG2),
-            480211 => Some(Self::VykasG3),
-
-            480691 | 480601
-            // | 480779 | 480780 | 480781 | 480782
-            // | 480603 | 480604 | 480605
-            // | 480606 | 480607 | 480621
-            => Some(Self::KakulSaydonG1), // Saydon
-            480696 | 480611 | 480612 => Some(Self::KakulSaydonG2), // Kakul
-            480631 => Some(Self::KakulSaydonG3), // Kakul-Saydon
-            480635 => Some(Self::KakulSaydonG3Bingo), // Encore-Desiring Kakul-Saydon
-
-            480805 | // Crushing Phantom Wardog
-            480874 | // Molting Phantom Wardog
-            480875 | // Echoing Phantom Wardog
-            480876 // Raging Phantom Wardog
-            => Some(Self::BrelshazaG1Dogs),
-            480803 => Some(Self::BrelshazaG1Pre), // Nightmare Gehenna
-            480802 => Some(Self::BrelshazaG1), // Gehenna Helkasirs
-            480808 => Some(Self::BrelshazaG2Prokel), // Prokel
-            480809 => Some(Self::BrelshazaG2), // Prokel's Spiritual Echo
-            480810 => Some(Self::BrelshazaG3), // Ashtarot
-            480811 => Some(Self::BrelshazaG4), // Primordial Nightmare
-            4221463 | // Pseudospace Primordial Nightmare
-            4221464 // Imagined Primordial Nightmare
-            => Some(Self::BrelshazaG5Cube),
-            480813 | 480815 => Some(Self::BrelshazaG5), // Brelshaza, Monarch of Nightmares
-            480814 => Some(Self::BrelshazaG6), // Phantom Legion Commander Brelshaza
-
-            480920 | 480934 | 480935 | 480954 | 480955 => Some(Self::AkkanG1), // Griefbringer Maurug
-            // Lord of Degradation Akkan
-            481085 | 480902 | 480930 | 480931 | 480932 | 480936 | 480996 | 480997 | 480998 |
-            481050 | 481051 | 481053 | 481057 | 480059 | 481060 | 481061 | 481066 | 481067 |
-            481068 | 481069 | 481070 => Some(Self::AkkanG2), // Lord of Degradation Akkan
-            481076 | 480903 | 480905 |
-            886045 | 131770 | 820109 => Some(Self::AkkanG3), // Plague Legion Commander Akkan
-            481078 | 481079 | 481080 | 481081 | 480904 | 480964 | 480965 | 480966 | 480967 |
-            480968 | 480969 => Some(Self::AkkanG3Bonus),
-
-            620260 => Some(Self::Deskaluda),
-            620250 => Some(Self::Caliligos),
-            620280 => Some(Self::Hanumatan),
-            620400 => Some(Self::Sonavel),
-            _ => None,
+/// Difficulty of a zone, as sent in `PktZoneMemberLoadStatusNotify`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Difficulty {
+    Normal,
+    Hard,
+    Inferno,
+    Challenge,
+    Special,
+    Trial,
+    Unknown(u8),
+}
+
+impl Difficulty {
+    pub fn from_level(level: u8) -> Self {
+        match level {
+            0 => Self::Normal,
+            1 => Self::Hard,
+            2 => Self::Inferno,
+            3 => Self::Challenge,
+            4 => Self::Special,
+            5 => Self::Trial,
+            _ => Self::Unknown(level),
         }
     }
 
-    pub fn max_bars(&self) -> Option<u16> {
+    pub fn name(&self) -> &'static str {
         match self {
-            Self::ValtanG1 => Some(50),
-            Self::ValtanG2 => Some(160),
-            Self::ValtanG2Ghost => Some(40),
-            Self::VykasG1 => Some(60),
-            Self::VykasG2 => Some(160),
-            Self::VykasG3 => Some(180),
-            Self::KakulSaydonG1 => Some(160),
-            Self::KakulSaydonG2 => Some(140),
-            Self::KakulSaydonG3 => Some(180),
-            Self::KakulSaydonG3Bingo => Some(77),
-            Self::BrelshazaG1Dogs => Some(20),
-            Self::BrelshazaG1Pre => Some(40),
-            Self::BrelshazaG1 => Some(120),
-            Self::BrelshazaG2Prokel => Some(160),
-            Self::BrelshazaG2 => Some(80),
-            Self::BrelshazaG3 => Some(170),
-            Self::BrelshazaG4 => Some(190),
-            Self::BrelshazaG5Cube => Some(20),
-            Self::BrelshazaG5 => Some(200),
-            Self::BrelshazaG6 => Some(250),
-            _ => None,
+            Self::Normal => "Normal",
+            Self::Hard => "Hard",
+            Self::Inferno => "Inferno",
+            Self::Challenge => "Challenge",
+            Self::Special => "Special",
+            Self::Trial => "Trial",
+            Self::Unknown(_) => "Unknown",
         }
     }
+}
 
-    pub fn id(&self) -> u32 {
-        match self {
-            Self::Golem => 720011,
+/// A raid gate or other boss fight, as defined in `resources/bosses`.
+#[derive(Debug, serde::Deserialize)]
+pub struct BossEncounter {
+    pub raid: String,
+    pub gate: Option<u8>,
+    pub difficulty: Option<String>,
+    pub npcs: Vec<BossNpc>,
+}
 
-            _ => 0, // TODO
+impl BossEncounter {
+    pub fn name(&self) -> String {
+        let mut name = self.raid.clone();
+        if let Some(gate) = self.gate {
+            name.push_str(&format!(" G{}", gate));
+        }
+        if let Some(difficulty) = &self.difficulty {
+            name.push_str(&format!(" ({})", difficulty));
         }
+        name
+    }
+}
+
+#[derive(Debug, Copy, Clone, serde::Deserialize)]
+pub struct BossNpc {
+    pub id: u32,
+    /// Number of HP bars shown in game, if any.
+    pub bars: Option<u16>,
+    pub role: BossRole,
+}
+
+/// How the appearance of a boss NPC affects encounters.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, serde::Deserialize)]
+pub enum BossRole {
+    /// Begins a new encounter if the current one isn't tracking a boss yet.
+    Start,
+    /// Only tracked if the current encounter is already tracking a boss.
+    Extend,
+}
+
+/// A boss NPC along with the encounter it belongs to.
+#[derive(Debug, Clone)]
+pub struct Boss {
+    pub npc: BossNpc,
+    pub encounter: std::sync::Arc<BossEncounter>,
+}
+
+impl Boss {
+    pub fn name(&self) -> String {
+        self.encounter.name()
     }
+
+    pub fn max_bars(&self) -> Option<u16> {
+        self.npc.bars
+    }
+}
+
+/// Buff tracking definitions, as stored in `resources/buff_categories`.
+#[derive(Debug, serde::Deserialize)]
+pub struct BuffDefinitions {
+    /// Categories that damage dealt under is tracked for, in display order.
+    pub categories: Vec<BuffCategory>,
+    /// Skill IDs of support identity ultimates, such as Serenade of Courage or Moonfall.
+    pub support_identity_skills: Vec<u32>,
+}
+
+/// A kind of buff that damage dealt under is tracked for, as defined in
+/// `resources/buff_categories`.
+///
+/// A status effect is in the category if its ID is listed in `ids`, or if it's in one of
+/// `buff_categories` in the status effect database and passes the other filters.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BuffCategory {
+    /// Short name shown in the meter.
+    pub name: String,
+    pub holder: BuffHolder,
+    pub ids: Vec<u32>,
+    pub buff_categories: Vec<String>,
+    /// Whether status effects have to be buffs or debuffs, if either.
+    pub kind: Option<StatusEffectCategory>,
+    /// Stats that status effects have to grant one of, as named in the status effect database.
+    /// Any status effect passes if none are listed.
+    pub stats: Vec<String>,
+    /// Whether status effects have to come from skills of support classes.
+    pub supports_only: bool,
+}
+
+/// Whether a status effect is beneficial to what it's applied to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum StatusEffectCategory {
+    Buff,
+    Debuff,
+}
+
+/// Who has to be under a status effect for a hit to count towards its [`BuffCategory`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum BuffHolder {
+    /// The player dealing the damage.
+    Attacker,
+    /// The entity being hit, with the status effect applied by the attacker's party.
+    Target,
 }
@@ -0,0 +1,967 @@
+//! Tabular exports of encounters, as CSV or JSON.
+
+use std::{collections::BTreeMap, io::Write, time::Instant};
+
+use serde::Serialize;
+
+use crate::meter::{log, Encounter, Environment, PlayerData};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    Json,
+}
+
+/// A row of a table that can be written as CSV.
+pub trait CsvRow: Sized {
+    const HEADER: &'static [&'static str];
+    fn fields(&self) -> Vec<String>;
+
+    /// Column names of a table of `rows`, for tables with columns beyond [`Self::HEADER`].
+    fn header(_rows: &[Self]) -> Vec<String> {
+        Self::HEADER.iter().map(|&name| name.to_owned()).collect()
+    }
+}
+
+/// Damage dealt by a player over the whole encounter.
+#[derive(Debug, Serialize)]
+pub struct PlayerSummary {
+    pub id: u64,
+    pub name: Option<String>,
+    pub class: String,
+    pub damage: i64,
+    pub dps: f64,
+    pub crit_rate: f64,
+    pub back_rate: f64,
+    pub front_rate: f64,
+    /// Damage that other players' buffs added to this player's hits.
+    pub damage_from_buffs: i64,
+    /// Damage that this player's buffs added to other players' hits.
+    pub damage_enabled: i64,
+    /// Share of damage dealt under each buff category, by category key.
+    pub buff_rates: BTreeMap<String, f64>,
+    /// Counterattacks that interrupted their target.
+    pub counters: usize,
+    /// Counterattacks attempted, including those that succeeded.
+    pub counter_attempts: usize,
+    pub deaths: usize,
+    /// Share of the encounter spent in an identity stance.
+    pub identity_rate: f64,
+    /// Seconds the identity gauge spent full.
+    pub gauge_capped: f64,
+    /// Support identity ultimate casts.
+    pub ultimates: usize,
+}
+
+/// Damage dealt by one skill of a player.
+#[derive(Debug, Serialize)]
+pub struct SkillSummary {
+    pub player_id: u64,
+    pub player: Option<String>,
+    pub skill_id: u32,
+    pub skill: Option<String>,
+    pub hits: usize,
+    pub damage: i64,
+    /// Share of the player's damage dealt by this skill.
+    pub damage_share: f64,
+    pub crit_rate: f64,
+    pub back_rate: f64,
+    pub front_rate: f64,
+}
+
+/// A single hit by a player or NPC.
+#[derive(Debug, Serialize)]
+pub struct HitRecord {
+    /// Milliseconds since the encounter began.
+    pub timestamp: u64,
+    pub source_id: u64,
+    pub source: Option<String>,
+    pub skill_id: u32,
+    pub skill: Option<String>,
+    pub target_id: u64,
+    pub target: Option<String>,
+    pub damage: i64,
+    pub crit: bool,
+    pub back_attack: bool,
+    pub front_attack: bool,
+}
+
+/// A counterattack attempted by a player.
+#[derive(Debug, Serialize)]
+pub struct CounterRecord {
+    /// Milliseconds since the encounter began.
+    pub timestamp: u64,
+    pub player_id: u64,
+    pub player: Option<String>,
+    pub target_id: u64,
+    pub target: Option<String>,
+    /// Raw counter type sent by the server.
+    pub kind: u32,
+    /// Whether the counterattack interrupted the target.
+    pub success: bool,
+}
+
+/// Uptime of a status effect on an entity.
+#[derive(Debug, Serialize)]
+pub struct UptimeRecord {
+    pub target_id: u64,
+    pub target: Option<String>,
+    pub status_effect_id: u32,
+    pub status_effect: Option<String>,
+    /// Number of times the status effect was applied.
+    pub applications: usize,
+    /// Share of the encounter the status effect was applied.
+    pub uptime: f64,
+}
+
+/// Damage a player took from one NPC skill.
+#[derive(Debug, Serialize)]
+pub struct DamageTakenRecord {
+    pub player_id: u64,
+    pub player: Option<String>,
+    pub skill_id: u32,
+    pub skill: Option<String>,
+    pub hits: usize,
+    pub damage: i64,
+    /// Share of the player's damage taken that came from this skill.
+    pub damage_share: f64,
+    pub largest: i64,
+}
+
+/// A window in which an NPC's stagger bar had to be filled.
+#[derive(Debug, Serialize)]
+pub struct StaggerCheckRecord {
+    pub target_id: u64,
+    pub target: Option<String>,
+    /// Milliseconds since the encounter began.
+    pub start: u64,
+    /// Milliseconds since the encounter began, if the check is over.
+    pub end: Option<u64>,
+    pub max_point: u32,
+    pub success: bool,
+}
+
+/// Stagger a player dealt to an NPC.
+#[derive(Debug, Serialize)]
+pub struct StaggerContributionRecord {
+    pub target_id: u64,
+    pub target: Option<String>,
+    pub player_id: u64,
+    pub player: Option<String>,
+    pub stagger: u64,
+    /// Share of all stagger dealt to the NPC.
+    pub share: f64,
+}
+
+/// Shields one entity gave another.
+#[derive(Debug, Serialize)]
+pub struct ShieldRecord {
+    pub source_id: u64,
+    pub source: Option<String>,
+    pub target_id: u64,
+    pub target: Option<String>,
+    pub given: u64,
+    pub absorbed: u64,
+    pub expired: u64,
+}
+
+/// A saved raid run.
+#[derive(Debug, Serialize)]
+pub struct RunRecord {
+    pub raid_id: u32,
+    /// Unix time the run was first seen at, in seconds.
+    pub started_at: u64,
+    /// Official clear time in milliseconds, if the raid was cleared.
+    pub clear_time: Option<u64>,
+    pub result: Option<u8>,
+    pub revives_used: u8,
+    pub bosses_killed: usize,
+    pub encounters: usize,
+    pub battle_items_used: u32,
+}
+
+/// Uses of a battle item by a player during a raid run.
+#[derive(Debug, Serialize)]
+pub struct BattleItemRecord {
+    pub raid_id: u32,
+    pub started_at: u64,
+    pub player: Option<String>,
+    pub status_effect_id: u32,
+    pub battle_item: Option<String>,
+    pub uses: u32,
+}
+
+/// Per-player summaries, highest damage first.
+pub fn player_summaries(enc: &Encounter, env: &Environment) -> Vec<PlayerSummary> {
+    let secs = enc.duration().as_secs_f64();
+    let end = enc.end.unwrap_or_else(Instant::now);
+    let mut rows: Vec<_> = enc
+        .players
+        .iter()
+        .map(|(&id, data)| {
+            let player = env.players.get(&id);
+            let skills = data.skills.values();
+            let hits: usize = skills.clone().map(|s| s.count).sum();
+            let crits: usize = skills.clone().map(|s| s.crits).sum();
+            let back: usize = skills.clone().map(|s| s.back).sum();
+            let front: usize = skills.map(|s| s.front).sum();
+            let identity = enc.identity.get(&id);
+            let counters = enc.counter_count(id);
+            PlayerSummary {
+                id,
+                name: player.and_then(|p| p.name.clone()),
+                class: player.map(|p| p.class.to_string()).unwrap_or_default(),
+                damage: data.dmg_dealt,
+                dps: ratio(data.dmg_dealt as f64, secs),
+                crit_rate: ratio(crits as f64, hits as f64),
+                back_rate: ratio(back as f64, hits as f64),
+                front_rate: ratio(front as f64, hits as f64),
+                damage_from_buffs: data.damage_from_buffs(),
+                damage_enabled: enc.damage_enabled_by(id),
+                buff_rates: enc
+                    .buff_categories
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, category)| (category_key(&category.name), data.buffed_rate(idx)))
+                    .collect(),
+                counters: counters.successes,
+                counter_attempts: counters.attempts,
+                deaths: enc.deaths_of(id).count(),
+                identity_rate: identity.map_or(0.0, |identity| {
+                    ratio(identity.time_in_identity(end).as_secs_f64(), secs)
+                }),
+                gauge_capped: identity
+                    .map_or(0.0, |identity| identity.time_at_cap(end).as_secs_f64()),
+                ultimates: identity.map_or(0, |identity| identity.ultimates.len()),
+            }
+        })
+        .collect();
+    rows.sort_by_key(|row| -row.damage);
+    rows
+}
+
+/// Per-skill summaries of every player, highest damage first within each player.
+pub fn skill_summaries(enc: &Encounter, env: &Environment) -> Vec<SkillSummary> {
+    let mut rows = Vec::new();
+    for (&id, data) in &enc.players {
+        let player = env.players.get(&id).and_then(|p| p.name.clone());
+        let start = rows.len();
+        rows.extend(data.skills.iter().map(|(&skill_id, skill)| SkillSummary {
+            player_id: id,
+            player: player.clone(),
+            skill_id,
+            skill: skill.name.clone(),
+            hits: skill.count,
+            damage: skill.damage,
+            damage_share: ratio(skill.damage as f64, data.dmg_dealt as f64),
+            crit_rate: ratio(skill.crits as f64, skill.count as f64),
+            back_rate: ratio(skill.back as f64, skill.count as f64),
+            front_rate: ratio(skill.front as f64, skill.count as f64),
+        }));
+        rows[start..].sort_by_key(|row| -row.damage);
+    }
+    rows
+}
+
+/// Every hit dealt by players and NPCs, in order.
+pub fn hit_records(enc: &Encounter, env: &Environment) -> Vec<HitRecord> {
+    let name = |id: u64| entity_name(env, id);
+
+    let mut rows = Vec::new();
+    let sources: [&BTreeMap<u64, PlayerData>; 2] = [&enc.players, &enc.npcs];
+    for (&id, data) in sources.into_iter().flatten() {
+        let source = name(id);
+        for (&skill_id, skill) in &data.skills {
+            rows.extend(skill.hits.iter().map(|(instant, hit)| HitRecord {
+                timestamp: to_ms(enc, *instant),
+                source_id: id,
+                source: source.clone(),
+                skill_id,
+                skill: skill.name.clone(),
+                target_id: hit.target_id,
+                target: name(hit.target_id),
+                damage: hit.damage,
+                crit: hit.is_crit,
+                back_attack: hit.is_back_attack,
+                front_attack: hit.is_front_attack,
+            }));
+        }
+    }
+    rows.sort_by_key(|row| row.timestamp);
+    rows
+}
+
+/// Damage taken by every player from each NPC skill, highest damage first within each player.
+pub fn damage_taken_records(enc: &Encounter, env: &Environment) -> Vec<DamageTakenRecord> {
+    let mut rows = Vec::new();
+    for (&id, taken) in &enc.damage_taken {
+        let player = entity_name(env, id);
+        let start = rows.len();
+        rows.extend(
+            taken
+                .skills
+                .iter()
+                .map(|(&skill_id, skill)| DamageTakenRecord {
+                    player_id: id,
+                    player: player.clone(),
+                    skill_id,
+                    skill: skill.name.clone(),
+                    hits: skill.hits,
+                    damage: skill.damage,
+                    damage_share: ratio(skill.damage as f64, taken.total as f64),
+                    largest: skill.largest,
+                }),
+        );
+        rows[start..].sort_by_key(|row| -row.damage);
+    }
+    rows
+}
+
+/// Every stagger check, in order.
+pub fn stagger_check_records(enc: &Encounter, env: &Environment) -> Vec<StaggerCheckRecord> {
+    let mut rows: Vec<_> = enc
+        .stagger
+        .iter()
+        .flat_map(|(&id, stagger)| stagger.checks.iter().map(move |check| (id, check)))
+        .map(|(id, check)| StaggerCheckRecord {
+            target_id: id,
+            target: entity_name(env, id),
+            start: to_ms(enc, check.start),
+            end: check.end.map(|end| to_ms(enc, end)),
+            max_point: check.max_point,
+            success: check.success,
+        })
+        .collect();
+    rows.sort_by_key(|row| row.start);
+    rows
+}
+
+/// Stagger dealt by every player to each NPC, highest first within each NPC.
+pub fn stagger_contribution_records(
+    enc: &Encounter,
+    env: &Environment,
+) -> Vec<StaggerContributionRecord> {
+    let mut rows = Vec::new();
+    for (&id, stagger) in &enc.stagger {
+        let target = entity_name(env, id);
+        let total: u64 = stagger.contribution.values().sum();
+        let start = rows.len();
+        rows.extend(stagger.contribution.iter().map(|(&player_id, &amount)| {
+            StaggerContributionRecord {
+                target_id: id,
+                target: target.clone(),
+                player_id,
+                player: entity_name(env, player_id),
+                stagger: amount,
+                share: ratio(amount as f64, total as f64),
+            }
+        }));
+        rows[start..].sort_by(|a, b| b.stagger.cmp(&a.stagger));
+    }
+    rows
+}
+
+/// Shields given between every pair of entities, largest first.
+pub fn shield_records(enc: &Encounter, env: &Environment) -> Vec<ShieldRecord> {
+    let mut rows: Vec<_> = enc
+        .shields
+        .iter()
+        .map(|(&(source_id, target_id), shielding)| ShieldRecord {
+            source_id,
+            source: entity_name(env, source_id),
+            target_id,
+            target: entity_name(env, target_id),
+            given: shielding.given,
+            absorbed: shielding.absorbed,
+            expired: shielding.expired,
+        })
+        .collect();
+    rows.sort_by(|a, b| b.given.cmp(&a.given));
+    rows
+}
+
+/// Every counterattack attempted by players, in order.
+pub fn counter_records(enc: &Encounter, env: &Environment) -> Vec<CounterRecord> {
+    let mut rows: Vec<_> = enc
+        .counters
+        .iter()
+        .flat_map(|(&id, counters)| counters.iter().map(move |counter| (id, counter)))
+        .map(|(id, counter)| CounterRecord {
+            timestamp: to_ms(enc, counter.time),
+            player_id: id,
+            player: entity_name(env, id),
+            target_id: counter.target_id,
+            target: entity_name(env, counter.target_id),
+            kind: counter.kind,
+            success: counter.success(),
+        })
+        .collect();
+    rows.sort_by_key(|row| row.timestamp);
+    rows
+}
+
+/// Uptime of every status effect on every entity, highest uptime first within each entity.
+/// Zone-wide status effects are listed under target ID 0.
+pub fn uptime_records(enc: &Encounter, env: &Environment) -> Vec<UptimeRecord> {
+    let mut rows = Vec::new();
+    let targets = enc
+        .status_effects
+        .iter()
+        .map(|(&id, effects)| (id, effects))
+        .chain(std::iter::once((0, &enc.zone_status_effects)));
+    for (target_id, effects) in targets {
+        let mut applications: BTreeMap<u32, (usize, Option<String>)> = BTreeMap::new();
+        for effect in effects {
+            let entry = applications
+                .entry(effect.status_effect_id)
+                .or_insert_with(|| (0, effect.name.clone()));
+            entry.0 += 1;
+        }
+        let target = entity_name(env, target_id);
+        let start = rows.len();
+        rows.extend(
+            applications
+                .into_iter()
+                .map(|(id, (applications, name))| UptimeRecord {
+                    target_id,
+                    target: target.clone(),
+                    status_effect_id: id,
+                    status_effect: name,
+                    applications,
+                    uptime: match target_id {
+                        0 => enc.zone_status_effect_uptime(id),
+                        _ => enc.status_effect_uptime(target_id, id),
+                    },
+                }),
+        );
+        rows[start..].sort_by(|a, b| b.uptime.total_cmp(&a.uptime));
+    }
+    rows
+}
+
+/// One row per raid run, in the given order.
+pub fn run_records(runs: &[log::Run]) -> Vec<RunRecord> {
+    runs.iter()
+        .map(|run| RunRecord {
+            raid_id: run.raid_id,
+            started_at: run.started_at,
+            clear_time: run.clear_time,
+            result: run.result,
+            revives_used: run.init_brave_hearts.saturating_sub(run.brave_hearts),
+            bosses_killed: run.kills.len(),
+            encounters: run.encounters.len(),
+            battle_items_used: run.battle_items.iter().map(|item| item.uses).sum(),
+        })
+        .collect()
+}
+
+/// Battle items used during each raid run, most used first within each run.
+pub fn battle_item_records(runs: &[log::Run]) -> Vec<BattleItemRecord> {
+    let mut rows = Vec::new();
+    for run in runs {
+        let start = rows.len();
+        rows.extend(run.battle_items.iter().map(|item| BattleItemRecord {
+            raid_id: run.raid_id,
+            started_at: run.started_at,
+            player: item.player.clone(),
+            status_effect_id: item.status_effect_id,
+            battle_item: item.name.clone(),
+            uses: item.uses,
+        }));
+        rows[start..].sort_by(|a, b| b.uses.cmp(&a.uses));
+    }
+    rows
+}
+
+/// Write a table of rows in the given format.
+pub fn write<T, W>(rows: &[T], format: Format, mut w: W) -> anyhow::Result<()>
+where
+    T: CsvRow + Serialize,
+    W: Write,
+{
+    match format {
+        Format::Json => serde_json::to_writer_pretty(&mut w, rows)?,
+        Format::Csv => {
+            writeln!(w, "{}", T::header(rows).join(","))?;
+            for row in rows {
+                let fields: Vec<_> = row.fields().into_iter().map(csv_escape).collect();
+                writeln!(w, "{}", fields.join(","))?;
+            }
+        }
+    }
+    w.flush()?;
+    Ok(())
+}
+
+fn entity_name(env: &Environment, id: u64) -> Option<String> {
+    match env.players.get(&id) {
+        Some(player) => player.name.clone(),
+        None => env.npcs.get(&id)?.name.clone(),
+    }
+}
+
+/// Milliseconds since the encounter began.
+fn to_ms(enc: &Encounter, instant: Instant) -> u64 {
+    instant.saturating_duration_since(enc.start).as_millis() as u64
+}
+
+/// Name of a buff category as used in column names, e.g. `attack_power` for "Attack Power".
+fn category_key(name: &str) -> String {
+    name.to_lowercase().replace(' ', "_")
+}
+
+fn csv_escape(field: String) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field
+    }
+}
+
+fn ratio(num: f64, den: f64) -> f64 {
+    if den == 0.0 {
+        0.0
+    } else {
+        num / den
+    }
+}
+
+fn opt(s: &Option<String>) -> String {
+    s.clone().unwrap_or_default()
+}
+
+impl CsvRow for PlayerSummary {
+    const HEADER: &'static [&'static str] = &[
+        "id",
+        "name",
+        "class",
+        "damage",
+        "dps",
+        "crit_rate",
+        "back_rate",
+        "front_rate",
+        "damage_from_buffs",
+        "damage_enabled",
+        "counters",
+        "counter_attempts",
+        "deaths",
+        "identity_rate",
+        "gauge_capped",
+        "ultimates",
+    ];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            opt(&self.name),
+            self.class.clone(),
+            self.damage.to_string(),
+            format!("{:.0}", self.dps),
+            format!("{:.4}", self.crit_rate),
+            format!("{:.4}", self.back_rate),
+            format!("{:.4}", self.front_rate),
+            self.damage_from_buffs.to_string(),
+            self.damage_enabled.to_string(),
+            self.counters.to_string(),
+            self.counter_attempts.to_string(),
+            self.deaths.to_string(),
+            format!("{:.4}", self.identity_rate),
+            format!("{:.1}", self.gauge_capped),
+            self.ultimates.to_string(),
+        ]
+        .into_iter()
+        .chain(self.buff_rates.values().map(|rate| format!("{:.4}", rate)))
+        .collect()
+    }
+
+    // rows of an encounter all have the same buff categories
+    fn header(rows: &[Self]) -> Vec<String> {
+        let extra = rows
+            .first()
+            .into_iter()
+            .flat_map(|row| row.buff_rates.keys());
+        Self::HEADER
+            .iter()
+            .map(|&name| name.to_owned())
+            .chain(extra.map(|key| format!("{}_rate", key)))
+            .collect()
+    }
+}
+
+impl CsvRow for SkillSummary {
+    const HEADER: &'static [&'static str] = &[
+        "player_id",
+        "player",
+        "skill_id",
+        "skill",
+        "hits",
+        "damage",
+        "damage_share",
+        "crit_rate",
+        "back_rate",
+        "front_rate",
+    ];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.player_id.to_string(),
+            opt(&self.player),
+            self.skill_id.to_string(),
+            opt(&self.skill),
+            self.hits.to_string(),
+            self.damage.to_string(),
+            format!("{:.4}", self.damage_share),
+            format!("{:.4}", self.crit_rate),
+            format!("{:.4}", self.back_rate),
+            format!("{:.4}", self.front_rate),
+        ]
+    }
+}
+
+impl CsvRow for HitRecord {
+    const HEADER: &'static [&'static str] = &[
+        "timestamp",
+        "source_id",
+        "source",
+        "skill_id",
+        "skill",
+        "target_id",
+        "target",
+        "damage",
+        "crit",
+        "back_attack",
+        "front_attack",
+    ];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.timestamp.to_string(),
+            self.source_id.to_string(),
+            opt(&self.source),
+            self.skill_id.to_string(),
+            opt(&self.skill),
+            self.target_id.to_string(),
+            opt(&self.target),
+            self.damage.to_string(),
+            self.crit.to_string(),
+            self.back_attack.to_string(),
+            self.front_attack.to_string(),
+        ]
+    }
+}
+
+impl CsvRow for CounterRecord {
+    const HEADER: &'static [&'static str] = &[
+        "timestamp",
+        "player_id",
+        "player",
+        "target_id",
+        "target",
+        "kind",
+        "success",
+    ];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.timestamp.to_string(),
+            self.player_id.to_string(),
+            opt(&self.player),
+            self.target_id.to_string(),
+            opt(&self.target),
+            self.kind.to_string(),
+            self.success.to_string(),
+        ]
+    }
+}
+
+impl CsvRow for UptimeRecord {
+    const HEADER: &'static [&'static str] = &[
+        "target_id",
+        "target",
+        "status_effect_id",
+        "status_effect",
+        "applications",
+        "uptime",
+    ];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.target_id.to_string(),
+            opt(&self.target),
+            self.status_effect_id.to_string(),
+            opt(&self.status_effect),
+            self.applications.to_string(),
+            format!("{:.4}", self.uptime),
+        ]
+    }
+}
+
+impl CsvRow for DamageTakenRecord {
+    const HEADER: &'static [&'static str] = &[
+        "player_id",
+        "player",
+        "skill_id",
+        "skill",
+        "hits",
+        "damage",
+        "damage_share",
+        "largest",
+    ];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.player_id.to_string(),
+            opt(&self.player),
+            self.skill_id.to_string(),
+            opt(&self.skill),
+            self.hits.to_string(),
+            self.damage.to_string(),
+            format!("{:.4}", self.damage_share),
+            self.largest.to_string(),
+        ]
+    }
+}
+
+impl CsvRow for StaggerCheckRecord {
+    const HEADER: &'static [&'static str] = &[
+        "target_id",
+        "target",
+        "start",
+        "end",
+        "max_point",
+        "success",
+    ];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.target_id.to_string(),
+            opt(&self.target),
+            self.start.to_string(),
+            self.end.map(|ms| ms.to_string()).unwrap_or_default(),
+            self.max_point.to_string(),
+            self.success.to_string(),
+        ]
+    }
+}
+
+impl CsvRow for StaggerContributionRecord {
+    const HEADER: &'static [&'static str] = &[
+        "target_id",
+        "target",
+        "player_id",
+        "player",
+        "stagger",
+        "share",
+    ];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.target_id.to_string(),
+            opt(&self.target),
+            self.player_id.to_string(),
+            opt(&self.player),
+            self.stagger.to_string(),
+            format!("{:.4}", self.share),
+        ]
+    }
+}
+
+impl CsvRow for ShieldRecord {
+    const HEADER: &'static [&'static str] = &[
+        "source_id",
+        "source",
+        "target_id",
+        "target",
+        "given",
+        "absorbed",
+        "expired",
+    ];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.source_id.to_string(),
+            opt(&self.source),
+            self.target_id.to_string(),
+            opt(&self.target),
+            self.given.to_string(),
+            self.absorbed.to_string(),
+            self.expired.to_string(),
+        ]
+    }
+}
+
+impl CsvRow for RunRecord {
+    const HEADER: &'static [&'static str] = &[
+        "raid_id",
+        "started_at",
+        "clear_time",
+        "result",
+        "revives_used",
+        "bosses_killed",
+        "encounters",
+        "battle_items_used",
+    ];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.raid_id.to_string(),
+            self.started_at.to_string(),
+            self.clear_time.map(|ms| ms.to_string()).unwrap_or_default(),
+            self.result.map(|r| r.to_string()).unwrap_or_default(),
+            self.revives_used.to_string(),
+            self.bosses_killed.to_string(),
+            self.encounters.to_string(),
+            self.battle_items_used.to_string(),
+        ]
+    }
+}
+
+impl CsvRow for BattleItemRecord {
+    const HEADER: &'static [&'static str] = &[
+        "raid_id",
+        "started_at",
+        "player",
+        "status_effect_id",
+        "battle_item",
+        "uses",
+    ];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.raid_id.to_string(),
+            self.started_at.to_string(),
+            opt(&self.player),
+            self.status_effect_id.to_string(),
+            opt(&self.battle_item),
+            self.uses.to_string(),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use super::*;
+    use crate::{
+        definitions::{BuffCategory, BuffHolder, Class},
+        meter::{BuffedDamage, Counter, Player},
+    };
+
+    fn category(name: &str) -> BuffCategory {
+        BuffCategory {
+            name: name.into(),
+            holder: BuffHolder::Attacker,
+            ids: Vec::new(),
+            buff_categories: Vec::new(),
+            kind: None,
+            stats: Vec::new(),
+            supports_only: false,
+        }
+    }
+
+    fn encounter() -> (Encounter, Environment) {
+        let env = Environment::new().with_player(1, Player::new("player", Class::Bard, 1620.0));
+        let start = Instant::now();
+        let mut enc = Encounter {
+            start,
+            end: Some(start + Duration::from_secs(10)),
+            first_damage: Some(start),
+            last_damage: Some(start + Duration::from_secs(10)),
+            buff_categories: Arc::new([category("Attack Power"), category("Brand")]),
+            ..Default::default()
+        };
+        let player = PlayerData {
+            dmg_dealt: 1000,
+            buffed: vec![
+                BuffedDamage {
+                    hits: 1,
+                    damage: 500,
+                },
+                BuffedDamage::default(),
+            ],
+            ..Default::default()
+        };
+        enc.players.insert(1, player);
+        let counter = |kind| Counter {
+            time: start,
+            target_id: 2,
+            kind,
+        };
+        enc.counters
+            .insert(1, vec![counter(Counter::SUCCESS), counter(1)]);
+        (enc, env)
+    }
+
+    fn csv<T: CsvRow + Serialize>(rows: &[T]) -> String {
+        let mut out = Vec::new();
+        write(rows, Format::Csv, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn csv_fields_are_quoted_when_needed() {
+        let row = CounterRecord {
+            timestamp: 0,
+            player_id: 1,
+            player: Some("a,b".into()),
+            target_id: 2,
+            target: Some("say \"hi\"\nbye".into()),
+            kind: 0,
+            success: true,
+        };
+        let lines = csv(&[row]);
+        let mut lines = lines.lines();
+        assert_eq!(
+            lines.next(),
+            Some("timestamp,player_id,player,target_id,target,kind,success")
+        );
+        assert_eq!(lines.next(), Some("0,1,\"a,b\",2,\"say \"\"hi\"\""));
+        assert_eq!(lines.next(), Some("bye\",0,true"));
+        assert_eq!(csv_escape("plain".into()), "plain");
+    }
+
+    #[test]
+    fn summary_header_has_a_column_per_buff_category() {
+        let (enc, env) = encounter();
+        let rows = player_summaries(&enc, &env);
+        let header = PlayerSummary::header(&rows);
+        assert_eq!(
+            header[PlayerSummary::HEADER.len()..],
+            ["attack_power_rate", "brand_rate"]
+        );
+        assert_eq!(rows[0].fields().len(), header.len());
+        let csv = csv(&rows);
+        let row = csv.lines().nth(1).unwrap();
+        assert!(row.ends_with(",0.5000,0.0000"), "{}", row);
+
+        // without rows there are no categories to name
+        assert_eq!(
+            PlayerSummary::header(&[]).len(),
+            PlayerSummary::HEADER.len()
+        );
+    }
+
+    #[test]
+    fn json_is_an_array_of_objects() {
+        let (enc, env) = encounter();
+        let mut out = Vec::new();
+        write(&player_summaries(&enc, &env), Format::Json, &mut out).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        let rows = json.as_array().unwrap();
+        assert_eq!(rows.len(), 1);
+        let row = rows[0].as_object().unwrap();
+        assert_eq!(row["name"], "player");
+        assert_eq!(row["class"], Class::Bard.to_string());
+        assert_eq!(row["damage"], 1000);
+        assert_eq!(
+            (&row["counters"], &row["counter_attempts"]),
+            (&1.into(), &2.into())
+        );
+        assert_eq!(row["buff_rates"]["attack_power"], 0.5);
+        assert_eq!(row["buff_rates"]["brand"], 0.0);
+        for column in PlayerSummary::HEADER {
+            assert!(row.contains_key(*column), "missing {}", column);
+        }
+    }
+}
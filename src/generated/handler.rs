@@ -0,0 +1,271 @@
+//! Dispatch of parsed packets to a [`PacketHandler`].
+
+// NOTE: This file is automatically generated.
+
+use crate::{
+    capture::parse_with_context,
+    definitions::Opcode,
+    packet,
+    parser::{Packet, Parser},
+};
+
+/// Parse the payload of a packet with the given opcode and pass it to `handler`.
+pub fn dispatch<P: PacketHandler>(
+    handler: &mut P,
+    opcode: Opcode,
+    parser: &mut Parser,
+    bump: &mut bumpalo::Bump,
+) -> anyhow::Result<()> {
+    match opcode {
+        Opcode::RaidBossKillNotify => {
+            let pkt = parse_with_context::<packet::PktRaidBossKillNotify>(parser, bump)?;
+            handler.on_packet(&pkt);
+            handler.on_raid_boss_kill_notify(pkt)?;
+        }
+        Opcode::NewPc => {
+            let pkt = parse_with_context::<packet::PktNewPc>(parser, bump)?;
+            handler.on_packet(&pkt);
+            handler.on_new_pc(pkt)?;
+        }
+        Opcode::SkillDamageAbnormalMoveNotify => {
+            let pkt = parse_with_context::<packet::PktSkillDamageAbnormalMoveNotify>(parser, bump)?;
+            handler.on_packet(&pkt);
+            handler.on_skill_damage_abnormal_move_notify(pkt)?;
+        }
+        Opcode::StatusEffectDurationNotify => {
+            let pkt = parse_with_context::<packet::PktStatusEffectDurationNotify>(parser, bump)?;
+            handler.on_packet(&pkt);
+            handler.on_status_effect_duration_notify(pkt)?;
+        }
+        Opcode::PassiveStatusEffectRemoveNotify => {
+            let pkt =
+                parse_with_context::<packet::PktPassiveStatusEffectRemoveNotify>(parser, bump)?;
+            handler.on_packet(&pkt);
+            handler.on_passive_status_effect_remove_notify(pkt)?;
+        }
+        Opcode::StatusEffectRemoveNotify => {
+            let pkt = parse_with_context::<packet::PktStatusEffectRemoveNotify>(parser, bump)?;
+            handler.on_packet(&pkt);
+            handler.on_status_effect_remove_notify(pkt)?;
+        }
+        Opcode::StatusEffectSyncDataNotify => {
+            let pkt = parse_with_context::<packet::PktStatusEffectSyncDataNotify>(parser, bump)?;
+            handler.on_packet(&pkt);
+            handler.on_status_effect_sync_data_notify(pkt)?;
+        }
+        Opcode::TroopMemberUpdateMinNotify => {
+            let pkt = parse_with_context::<packet::PktTroopMemberUpdateMinNotify>(parser, bump)?;
+            handler.on_packet(&pkt);
+            handler.on_troop_member_update_min_notify(pkt)?;
+        }
+        Opcode::StatusEffectAddNotify => {
+            let pkt = parse_with_context::<packet::PktStatusEffectAddNotify>(parser, bump)?;
+            handler.on_packet(&pkt);
+            handler.on_status_effect_add_notify(pkt)?;
+        }
+        Opcode::NewNpc => {
+            let pkt = parse_with_context::<packet::PktNewNpc>(parser, bump)?;
+            handler.on_packet(&pkt);
+            handler.on_new_npc(pkt)?;
+        }
+        Opcode::DeathNotify => {
+            let pkt = parse_with_context::<packet::PktDeathNotify>(parser, bump)?;
+            handler.on_packet(&pkt);
+            handler.on_death_notify(pkt)?;
+        }
+        Opcode::InitPc => {
+            let pkt = parse_with_context::<packet::PktInitPc>(parser, bump)?;
+            handler.on_packet(&pkt);
+            handler.on_init_pc(pkt)?;
+        }
+        Opcode::IdentityStanceChangeNotify => {
+            let pkt = parse_with_context::<packet::PktIdentityStanceChangeNotify>(parser, bump)?;
+            handler.on_packet(&pkt);
+            handler.on_identity_stance_change_notify(pkt)?;
+        }
+        Opcode::SkillDamageNotify => {
+            let pkt = parse_with_context::<packet::PktSkillDamageNotify>(parser, bump)?;
+            handler.on_packet(&pkt);
+            handler.on_skill_damage_notify(pkt)?;
+        }
+        Opcode::ParalyzationStateNotify => {
+            let pkt = parse_with_context::<packet::PktParalyzationStateNotify>(parser, bump)?;
+            handler.on_packet(&pkt);
+            handler.on_paralyzation_state_notify(pkt)?;
+        }
+        Opcode::CounterAttackNotify => {
+            let pkt = parse_with_context::<packet::PktCounterAttackNotify>(parser, bump)?;
+            handler.on_packet(&pkt);
+            handler.on_counter_attack_notify(pkt)?;
+        }
+        Opcode::TriggerBossBattleStatus => {
+            let pkt = parse_with_context::<packet::PktTriggerBossBattleStatus>(parser, bump)?;
+            handler.on_packet(&pkt);
+            handler.on_trigger_boss_battle_status(pkt)?;
+        }
+        Opcode::PartyStatusEffectAddNotify => {
+            let pkt = parse_with_context::<packet::PktPartyStatusEffectAddNotify>(parser, bump)?;
+            handler.on_packet(&pkt);
+            handler.on_party_status_effect_add_notify(pkt)?;
+        }
+        Opcode::BlockSkillStateNotify => {
+            let pkt = parse_with_context::<packet::PktBlockSkillStateNotify>(parser, bump)?;
+            handler.on_packet(&pkt);
+            handler.on_block_skill_state_notify(pkt)?;
+        }
+        Opcode::NewNpcSummon => {
+            let pkt = parse_with_context::<packet::PktNewNpcSummon>(parser, bump)?;
+            handler.on_packet(&pkt);
+            handler.on_new_npc_summon(pkt)?;
+        }
+        Opcode::PartyStatusEffectResultNotify => {
+            let pkt = parse_with_context::<packet::PktPartyStatusEffectResultNotify>(parser, bump)?;
+            handler.on_packet(&pkt);
+            handler.on_party_status_effect_result_notify(pkt)?;
+        }
+        Opcode::ZoneStatusEffectAddNotify => {
+            let pkt = parse_with_context::<packet::PktZoneStatusEffectAddNotify>(parser, bump)?;
+            handler.on_packet(&pkt);
+            handler.on_zone_status_effect_add_notify(pkt)?;
+        }
+        Opcode::ZoneObjectUnpublishNotify => {
+            let pkt = parse_with_context::<packet::PktZoneObjectUnpublishNotify>(parser, bump)?;
+            handler.on_packet(&pkt);
+            handler.on_zone_object_unpublish_notify(pkt)?;
+        }
+        Opcode::InitEnv => {
+            let pkt = parse_with_context::<packet::PktInitEnv>(parser, bump)?;
+            handler.on_packet(&pkt);
+            handler.on_init_env(pkt)?;
+        }
+        Opcode::IdentityGaugeChangeNotify => {
+            let pkt = parse_with_context::<packet::PktIdentityGaugeChangeNotify>(parser, bump)?;
+            handler.on_packet(&pkt);
+            handler.on_identity_gauge_change_notify(pkt)?;
+        }
+        Opcode::SkillStartNotify => {
+            let pkt = parse_with_context::<packet::PktSkillStartNotify>(parser, bump)?;
+            handler.on_packet(&pkt);
+            handler.on_skill_start_notify(pkt)?;
+        }
+        Opcode::PassiveStatusEffectAddNotify => {
+            let pkt = parse_with_context::<packet::PktPassiveStatusEffectAddNotify>(parser, bump)?;
+            handler.on_packet(&pkt);
+            handler.on_passive_status_effect_add_notify(pkt)?;
+        }
+        Opcode::PartyPassiveStatusEffectAddNotify => {
+            let pkt =
+                parse_with_context::<packet::PktPartyPassiveStatusEffectAddNotify>(parser, bump)?;
+            handler.on_packet(&pkt);
+            handler.on_party_passive_status_effect_add_notify(pkt)?;
+        }
+        Opcode::PartyInfo => {
+            let pkt = parse_with_context::<packet::PktPartyInfo>(parser, bump)?;
+            handler.on_packet(&pkt);
+            handler.on_party_info(pkt)?;
+        }
+        Opcode::PartyStatusEffectRemoveNotify => {
+            let pkt = parse_with_context::<packet::PktPartyStatusEffectRemoveNotify>(parser, bump)?;
+            handler.on_packet(&pkt);
+            handler.on_party_status_effect_remove_notify(pkt)?;
+        }
+        Opcode::TriggerStartNotify => {
+            let pkt = parse_with_context::<packet::PktTriggerStartNotify>(parser, bump)?;
+            handler.on_packet(&pkt);
+            handler.on_trigger_start_notify(pkt)?;
+        }
+        Opcode::ZoneMemberLoadStatusNotify => {
+            let pkt = parse_with_context::<packet::PktZoneMemberLoadStatusNotify>(parser, bump)?;
+            handler.on_packet(&pkt);
+            handler.on_zone_member_load_status_notify(pkt)?;
+        }
+        Opcode::NewProjectile => {
+            let pkt = parse_with_context::<packet::PktNewProjectile>(parser, bump)?;
+            handler.on_packet(&pkt);
+            handler.on_new_projectile(pkt)?;
+        }
+        Opcode::ZoneStatusEffectRemoveNotify => {
+            let pkt = parse_with_context::<packet::PktZoneStatusEffectRemoveNotify>(parser, bump)?;
+            handler.on_packet(&pkt);
+            handler.on_zone_status_effect_remove_notify(pkt)?;
+        }
+        Opcode::RemoveObject => {
+            let pkt = parse_with_context::<packet::PktRemoveObject>(parser, bump)?;
+            handler.on_packet(&pkt);
+            handler.on_remove_object(pkt)?;
+        }
+        Opcode::PartyPassiveStatusEffectRemoveNotify => {
+            let pkt = parse_with_context::<packet::PktPartyPassiveStatusEffectRemoveNotify>(
+                parser, bump,
+            )?;
+            handler.on_packet(&pkt);
+            handler.on_party_passive_status_effect_remove_notify(pkt)?;
+        }
+        Opcode::RaidResult => {
+            let pkt = parse_with_context::<packet::PktRaidResult>(parser, bump)?;
+            handler.on_packet(&pkt);
+            handler.on_raid_result(pkt)?;
+        }
+        Opcode::MigrationExecute => {
+            let pkt = parse_with_context::<packet::PktMigrationExecute>(parser, bump)?;
+            handler.on_packet(&pkt);
+            handler.on_migration_execute(pkt)?;
+        }
+        Opcode::RaidBegin => {
+            let pkt = parse_with_context::<packet::PktRaidBegin>(parser, bump)?;
+            handler.on_packet(&pkt);
+            handler.on_raid_begin(pkt)?;
+        }
+    }
+    Ok(())
+}
+
+#[rustfmt::skip]
+pub trait PacketHandler {
+    fn on_raid_boss_kill_notify(&mut self, _: packet::PktRaidBossKillNotify) -> anyhow::Result<()> { Ok(()) }
+    fn on_new_pc(&mut self, _: packet::PktNewPc) -> anyhow::Result<()> { Ok(()) }
+    fn on_skill_damage_abnormal_move_notify(&mut self, _: packet::PktSkillDamageAbnormalMoveNotify) -> anyhow::Result<()> { Ok(()) }
+    fn on_status_effect_duration_notify(&mut self, _: packet::PktStatusEffectDurationNotify) -> anyhow::Result<()> { Ok(()) }
+    fn on_passive_status_effect_remove_notify(&mut self, _: packet::PktPassiveStatusEffectRemoveNotify) -> anyhow::Result<()> { Ok(()) }
+    fn on_status_effect_remove_notify(&mut self, _: packet::PktStatusEffectRemoveNotify) -> anyhow::Result<()> { Ok(()) }
+    fn on_status_effect_sync_data_notify(&mut self, _: packet::PktStatusEffectSyncDataNotify) -> anyhow::Result<()> { Ok(()) }
+    fn on_troop_member_update_min_notify(&mut self, _: packet::PktTroopMemberUpdateMinNotify) -> anyhow::Result<()> { Ok(()) }
+    fn on_status_effect_add_notify(&mut self, _: packet::PktStatusEffectAddNotify) -> anyhow::Result<()> { Ok(()) }
+    fn on_new_npc(&mut self, _: packet::PktNewNpc) -> anyhow::Result<()> { Ok(()) }
+    fn on_death_notify(&mut self, _: packet::PktDeathNotify) -> anyhow::Result<()> { Ok(()) }
+    fn on_init_pc(&mut self, _: packet::PktInitPc) -> anyhow::Result<()> { Ok(()) }
+    fn on_identity_stance_change_notify(&mut self, _: packet::PktIdentityStanceChangeNotify) -> anyhow::Result<()> { Ok(()) }
+    fn on_skill_damage_notify(&mut self, _: packet::PktSkillDamageNotify) -> anyhow::Result<()> { Ok(()) }
+    fn on_paralyzation_state_notify(&mut self, _: packet::PktParalyzationStateNotify) -> anyhow::Result<()> { Ok(()) }
+    fn on_counter_attack_notify(&mut self, _: packet::PktCounterAttackNotify) -> anyhow::Result<()> { Ok(()) }
+    fn on_trigger_boss_battle_status(&mut self, _: packet::PktTriggerBossBattleStatus) -> anyhow::Result<()> { Ok(()) }
+    fn on_party_status_effect_add_notify(&mut self, _: packet::PktPartyStatusEffectAddNotify) -> anyhow::Result<()> { Ok(()) }
+    fn on_block_skill_state_notify(&mut self, _: packet::PktBlockSkillStateNotify) -> anyhow::Result<()> { Ok(()) }
+    fn on_new_npc_summon(&mut self, _: packet::PktNewNpcSummon) -> anyhow::Result<()> { Ok(()) }
+    fn on_party_status_effect_result_notify(&mut self, _: packet::PktPartyStatusEffectResultNotify) -> anyhow::Result<()> { Ok(()) }
+    fn on_zone_status_effect_add_notify(&mut self, _: packet::PktZoneStatusEffectAddNotify) -> anyhow::Result<()> { Ok(()) }
+    fn on_zone_object_unpublish_notify(&mut self, _: packet::PktZoneObjectUnpublishNotify) -> anyhow::Result<()> { Ok(()) }
+    fn on_init_env(&mut self, _: packet::PktInitEnv) -> anyhow::Result<()> { Ok(()) }
+    fn on_identity_gauge_change_notify(&mut self, _: packet::PktIdentityGaugeChangeNotify) -> anyhow::Result<()> { Ok(()) }
+    fn on_skill_start_notify(&mut self, _: packet::PktSkillStartNotify) -> anyhow::Result<()> { Ok(()) }
+    fn on_passive_status_effect_add_notify(&mut self, _: packet::PktPassiveStatusEffectAddNotify) -> anyhow::Result<()> { Ok(()) }
+    fn on_party_passive_status_effect_add_notify(&mut self, _: packet::PktPartyPassiveStatusEffectAddNotify) -> anyhow::Result<()> { Ok(()) }
+    fn on_party_info(&mut self, _: packet::PktPartyInfo) -> anyhow::Result<()> { Ok(()) }
+    fn on_party_status_effect_remove_notify(&mut self, _: packet::PktPartyStatusEffectRemoveNotify) -> anyhow::Result<()> { Ok(()) }
+    fn on_trigger_start_notify(&mut self, _: packet::PktTriggerStartNotify) -> anyhow::Result<()> { Ok(()) }
+    fn on_zone_member_load_status_notify(&mut self, _: packet::PktZoneMemberLoadStatusNotify) -> anyhow::Result<()> { Ok(()) }
+    fn on_new_projectile(&mut self, _: packet::PktNewProjectile) -> anyhow::Result<()> { Ok(()) }
+    fn on_zone_status_effect_remove_notify(&mut self, _: packet::PktZoneStatusEffectRemoveNotify) -> anyhow::Result<()> { Ok(()) }
+    fn on_remove_object(&mut self, _: packet::PktRemoveObject) -> anyhow::Result<()> { Ok(()) }
+    fn on_party_passive_status_effect_remove_notify(&mut self, _: packet::PktPartyPassiveStatusEffectRemoveNotify) -> anyhow::Result<()> { Ok(()) }
+    fn on_raid_result(&mut self, _: packet::PktRaidResult) -> anyhow::Result<()> { Ok(()) }
+    fn on_migration_execute(&mut self, _: packet::PktMigrationExecute) -> anyhow::Result<()> { Ok(()) }
+    fn on_raid_begin(&mut self, _: packet::PktRaidBegin) -> anyhow::Result<()> { Ok(()) }
+
+    fn on_packet<P>(&mut self, _: &P) where P: Packet + serde::Serialize {}
+
+    /// Used to filter out unnecessary opcodes before parsing.
+    fn filter(_: &Opcode) -> bool {
+        true
+    }
+}
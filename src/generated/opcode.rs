@@ -5,15 +5,11 @@ pub enum Opcode {
     RaidBossKillNotify,
     NewPc,
     SkillDamageAbnormalMoveNotify,
-    AddonSkillFeatureChangeNotify,
     StatusEffectDurationNotify,
     PassiveStatusEffectRemoveNotify,
     StatusEffectRemoveNotify,
     StatusEffectSyncDataNotify,
     TroopMemberUpdateMinNotify,
-    InitItem,
-    ActiveAbilityNotify,
-    SkillStageNotify,
     StatusEffectAddNotify,
     NewNpc,
     DeathNotify,
@@ -21,14 +17,9 @@ pub enum Opcode {
     IdentityStanceChangeNotify,
     SkillDamageNotify,
     ParalyzationStateNotify,
-    EquipLifeToolChangeNotify,
-    AuthTokenResult,
     CounterAttackNotify,
     TriggerBossBattleStatus,
     PartyStatusEffectAddNotify,
-    InitAbility,
-    SkillCastNotify,
-    NewTrap,
     BlockSkillStateNotify,
     NewNpcSummon,
     PartyStatusEffectResultNotify,
@@ -37,24 +28,18 @@ pub enum Opcode {
     InitEnv,
     IdentityGaugeChangeNotify,
     SkillStartNotify,
-    InitLocal,
-    PartyLeaveResult,
     PassiveStatusEffectAddNotify,
     PartyPassiveStatusEffectAddNotify,
     PartyInfo,
-    TriggerFinishNotify,
     PartyStatusEffectRemoveNotify,
     TriggerStartNotify,
     ZoneMemberLoadStatusNotify,
     NewProjectile,
     ZoneStatusEffectRemoveNotify,
     RemoveObject,
-    StatChangeOriginNotify,
     PartyPassiveStatusEffectRemoveNotify,
     RaidResult,
-    AbilityChangeNotify,
     MigrationExecute,
-    EquipChangeNotify,
     RaidBegin,
 }
 
@@ -64,15 +49,11 @@ impl Opcode {
             20341 => Opcode::RaidBossKillNotify,
             33762 => Opcode::NewPc,
             42318 => Opcode::SkillDamageAbnormalMoveNotify,
-            9808 => Opcode::AddonSkillFeatureChangeNotify,
             19054 => Opcode::StatusEffectDurationNotify,
             2648 => Opcode::PassiveStatusEffectRemoveNotify,
             22672 => Opcode::StatusEffectRemoveNotify,
             33630 => Opcode::StatusEffectSyncDataNotify,
             44092 => Opcode::TroopMemberUpdateMinNotify,
-            9176 => Opcode::InitItem,
-            23742 => Opcode::ActiveAbilityNotify,
-            30803 => Opcode::SkillStageNotify,
             33978 => Opcode::StatusEffectAddNotify,
             59333 => Opcode::NewNpc,
             20789 => Opcode::DeathNotify,
@@ -80,14 +61,9 @@ impl Opcode {
             74 => Opcode::IdentityStanceChangeNotify,
             20086 => Opcode::SkillDamageNotify,
             33543 => Opcode::ParalyzationStateNotify,
-            15165 => Opcode::EquipLifeToolChangeNotify,
-            24597 => Opcode::AuthTokenResult,
             2889 => Opcode::CounterAttackNotify,
             6420 => Opcode::TriggerBossBattleStatus,
             25859 => Opcode::PartyStatusEffectAddNotify,
-            27635 => Opcode::InitAbility,
-            56741 => Opcode::SkillCastNotify,
-            59491 => Opcode::NewTrap,
             43110 => Opcode::BlockSkillStateNotify,
             47111 => Opcode::NewNpcSummon,
             16085 => Opcode::PartyStatusEffectResultNotify,
@@ -96,24 +72,18 @@ impl Opcode {
             43544 => Opcode::InitEnv,
             19646 => Opcode::IdentityGaugeChangeNotify,
             110 => Opcode::SkillStartNotify,
-            24890 => Opcode::InitLocal,
-            43463 => Opcode::PartyLeaveResult,
             7138 => Opcode::PassiveStatusEffectAddNotify,
             47487 => Opcode::PartyPassiveStatusEffectAddNotify,
             59330 => Opcode::PartyInfo,
-            38704 => Opcode::TriggerFinishNotify,
             20302 => Opcode::PartyStatusEffectRemoveNotify,
             20231 => Opcode::TriggerStartNotify,
             14585 => Opcode::ZoneMemberLoadStatusNotify,
             25491 => Opcode::NewProjectile,
             35401 => Opcode::ZoneStatusEffectRemoveNotify,
             46115 => Opcode::RemoveObject,
-            2349 => Opcode::StatChangeOriginNotify,
             12028 => Opcode::PartyPassiveStatusEffectRemoveNotify,
             23056 => Opcode::RaidResult,
-            34237 => Opcode::AbilityChangeNotify,
             1461 => Opcode::MigrationExecute,
-            46264 => Opcode::EquipChangeNotify,
             49450 => Opcode::RaidBegin,
             _ => return None,
         })
@@ -123,15 +93,11 @@ impl Opcode {
             Opcode::RaidBossKillNotify => 20341,
             Opcode::NewPc => 33762,
             Opcode::SkillDamageAbnormalMoveNotify => 42318,
-            Opcode::AddonSkillFeatureChangeNotify => 9808,
             Opcode::StatusEffectDurationNotify => 19054,
             Opcode::PassiveStatusEffectRemoveNotify => 2648,
             Opcode::StatusEffectRemoveNotify => 22672,
             Opcode::StatusEffectSyncDataNotify => 33630,
             Opcode::TroopMemberUpdateMinNotify => 44092,
-            Opcode::InitItem => 9176,
-            Opcode::ActiveAbilityNotify => 23742,
-            Opcode::SkillStageNotify => 30803,
             Opcode::StatusEffectAddNotify => 33978,
             Opcode::NewNpc => 59333,
             Opcode::DeathNotify => 20789,
@@ -139,14 +105,9 @@ impl Opcode {
             Opcode::IdentityStanceChangeNotify => 74,
             Opcode::SkillDamageNotify => 20086,
             Opcode::ParalyzationStateNotify => 33543,
-            Opcode::EquipLifeToolChangeNotify => 15165,
-            Opcode::AuthTokenResult => 24597,
             Opcode::CounterAttackNotify => 2889,
             Opcode::TriggerBossBattleStatus => 6420,
             Opcode::PartyStatusEffectAddNotify => 25859,
-            Opcode::InitAbility => 27635,
-            Opcode::SkillCastNotify => 56741,
-            Opcode::NewTrap => 59491,
             Opcode::BlockSkillStateNotify => 43110,
             Opcode::NewNpcSummon => 47111,
             Opcode::PartyStatusEffectResultNotify => 16085,
@@ -155,24 +116,18 @@ impl Opcode {
             Opcode::InitEnv => 43544,
             Opcode::IdentityGaugeChangeNotify => 19646,
             Opcode::SkillStartNotify => 110,
-            Opcode::InitLocal => 24890,
-            Opcode::PartyLeaveResult => 43463,
             Opcode::PassiveStatusEffectAddNotify => 7138,
             Opcode::PartyPassiveStatusEffectAddNotify => 47487,
             Opcode::PartyInfo => 59330,
-            Opcode::TriggerFinishNotify => 38704,
             Opcode::PartyStatusEffectRemoveNotify => 20302,
             Opcode::TriggerStartNotify => 20231,
             Opcode::ZoneMemberLoadStatusNotify => 14585,
             Opcode::NewProjectile => 25491,
             Opcode::ZoneStatusEffectRemoveNotify => 35401,
             Opcode::RemoveObject => 46115,
-            Opcode::StatChangeOriginNotify => 2349,
             Opcode::PartyPassiveStatusEffectRemoveNotify => 12028,
             Opcode::RaidResult => 23056,
-            Opcode::AbilityChangeNotify => 34237,
             Opcode::MigrationExecute => 1461,
-            Opcode::EquipChangeNotify => 46264,
             Opcode::RaidBegin => 49450,
         }
     }
@@ -7,14 +7,17 @@
 
 pub mod capture;
 pub mod definitions;
+pub mod export;
 pub mod meter;
 pub mod oodle;
 pub mod parser;
+pub mod persist;
 pub mod socket;
 pub mod ui;
 pub mod util;
 
 mod generated {
+    pub mod handler;
     pub mod opcode;
     pub mod packet;
 }
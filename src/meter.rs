@@ -12,50 +12,139 @@ use parking_lot::Mutex;
 
 use crate::{
     capture::PacketHandler,
-    definitions::{Boss, Class, HitFlag, HitOption},
+    definitions::{
+        Boss, BossEncounter, BossRole, BuffCategory, BuffDefinitions, BuffHolder, Class,
+        DamageStat, Difficulty, HitFlag, HitOption, StatusEffectCategory,
+    },
     packet::{
-        PktInitEnv, PktInitPc, PktNewNpc, PktNewPc, PktNewProjectile, PktParalyzationStateNotify,
-        PktRaidBossKillNotify, PktRaidResult, PktSkillDamageAbnormalMoveNotify,
-        PktSkillDamageNotify, PktTriggerBossBattleStatus, PktTriggerStartNotify, SkillDamageEvent,
+        PktBlockSkillStateNotify, PktCounterAttackNotify, PktDeathNotify,
+        PktIdentityGaugeChangeNotify, PktIdentityStanceChangeNotify, PktInitEnv, PktInitPc,
+        PktNewNpc, PktNewNpcSummon, PktNewPc, PktNewProjectile, PktParalyzationStateNotify,
+        PktPartyPassiveStatusEffectAddNotify, PktPartyPassiveStatusEffectRemoveNotify,
+        PktPassiveStatusEffectAddNotify, PktPassiveStatusEffectRemoveNotify, PktRaidBegin,
+        PktRaidBossKillNotify, PktRaidResult, PktRemoveObject, PktSkillDamageAbnormalMoveNotify,
+        PktSkillDamageNotify, PktSkillStartNotify, PktStatusEffectDurationNotify,
+        PktStatusEffectSyncDataNotify, PktTriggerBossBattleStatus, PktTriggerStartNotify,
+        PktTroopMemberUpdateMinNotify, PktZoneMemberLoadStatusNotify, PktZoneObjectUnpublishNotify,
+        PktZoneStatusEffectAddNotify, PktZoneStatusEffectRemoveNotify, SkillDamageEvent,
     },
     parser::Packet,
+    persist::LogWriter,
     util::snappy_file_reader,
 };
 
 #[allow(dead_code)]
 pub mod log {
-    use serde::Serialize;
+    use serde::{Deserialize, Serialize};
     use std::collections::BTreeMap;
 
+    /// Marks the start of a log file.
+    pub const MAGIC: [u8; 4] = *b"LRPL";
+    /// Version of the log structures, to be bumped whenever their serialized form changes.
+    /// Files without a [`Header`] are version 0.
+    pub const VERSION: u16 = 1;
+    /// Marks the start of a raid run file.
+    pub const RUN_MAGIC: [u8; 4] = *b"LRPR";
+    /// Version of the raid run structures, to be bumped whenever their serialized form changes.
+    pub const RUN_VERSION: u16 = 0;
+
+    /// Uncompressed preamble of a log file, followed by the compressed [`Log`].
+    #[derive(Debug, Serialize, Deserialize)]
+    pub struct Header {
+        pub magic: [u8; 4],
+        pub version: u16,
+        /// Game client build the log was recorded on, if known.
+        pub client_build: Option<String>,
+        /// Version of the meter that wrote the log.
+        pub meter_version: String,
+    }
+
+    impl Header {
+        pub fn new(client_build: Option<String>) -> Self {
+            Self {
+                magic: MAGIC,
+                version: VERSION,
+                client_build,
+                meter_version: env!("CARGO_PKG_VERSION").to_string(),
+            }
+        }
+
+        /// Preamble of a raid run file, followed by the compressed [`Run`].
+        pub fn run(client_build: Option<String>) -> Self {
+            Self {
+                magic: RUN_MAGIC,
+                version: RUN_VERSION,
+                ..Self::new(client_build)
+            }
+        }
+    }
+
+    /// Overview of a log, written uncompressed after its [`Header`] so that logs can be listed
+    /// without reading their body.
+    #[derive(Debug, Serialize, Deserialize)]
+    pub struct Summary {
+        /// Unix time the log was saved at, in seconds.
+        pub saved_at: u64,
+        pub zone: Option<String>,
+        pub boss: Option<String>,
+        pub difficulty: Option<crate::definitions::Difficulty>,
+        /// Milliseconds from the first to the last damage dealt.
+        pub duration: u64,
+    }
+
     // milliseconds since start
-    #[derive(Serialize)]
+    #[derive(Serialize, Deserialize)]
     pub struct Timestamp(u64);
-    #[derive(Serialize)]
+    #[derive(Serialize, Deserialize)]
     pub struct Damage(i64);
-    #[derive(Debug, Copy, Clone, Serialize)]
-    pub struct EntityIndex(usize);
-    #[derive(Debug, Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Serialize)]
+    #[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+    pub struct EntityIndex(pub usize);
+    #[derive(Debug, Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Serialize, Deserialize)]
     pub struct SkillId(u32);
-    #[derive(Serialize)]
+    #[derive(Serialize, Deserialize)]
     pub struct SpeciesId(u32);
 
-    #[derive(Serialize)]
+    #[derive(Serialize, Deserialize)]
     pub enum Status {
         Wipe,
         Clear,
     }
 
-    #[derive(Serialize)]
+    #[derive(Serialize, Deserialize)]
     pub struct Log {
         pub end: Timestamp,
         pub entities: Vec<Entity>,
         pub pov: Option<EntityIndex>,
         pub targets: Vec<EntityIndex>,
         pub status: Option<Status>,
+        pub zone: Option<Zone>,
+        pub raid_id: Option<u32>,
+        pub deaths: Vec<Death>,
+        pub counters: Vec<(Timestamp, Counter)>,
+        pub identities: Vec<Identity>,
+        pub status_effects: Vec<StatusEffect>,
+        pub zone_status_effects: Vec<ZoneStatusEffect>,
+        pub stagger: Vec<Stagger>,
+        /// Categories of buffs that hits were dealt under, indexed like [`BuffedHit::buffed`].
+        pub buff_categories: Vec<crate::definitions::BuffCategory>,
+        pub buffed_hits: Vec<BuffedHit>,
+        pub buff_contributions: Vec<BuffContribution>,
+        /// Party of each player when the log was saved.
+        pub parties: Vec<(EntityIndex, u32)>,
+        pub shields: Vec<Shielding>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub struct Zone {
+        pub id: u32,
+        pub name: Option<String>,
+        pub difficulty: crate::definitions::Difficulty,
     }
 
-    #[derive(Serialize)]
+    #[derive(Serialize, Deserialize)]
     pub struct Entity {
+        /// ID of the entity in its [`crate::meter::Environment`].
+        pub id: u64,
         pub name: Option<String>,
         pub damage: Vec<(Timestamp, Damage)>,
         pub casts: Vec<(Timestamp, SkillId)>,
@@ -63,19 +152,24 @@ pub mod log {
         pub kind: EntityKind,
     }
 
-    #[derive(Serialize)]
+    #[derive(Serialize, Deserialize)]
     pub enum EntityKind {
-        Player,
+        Player {
+            class_id: u16,
+            ilvl: f32,
+        },
         Npc(SpeciesId),
+        /// An entity that was hit but never seen spawning.
+        Unknown,
     }
 
-    #[derive(Serialize)]
+    #[derive(Serialize, Deserialize)]
     pub struct Skill {
         pub name: Option<String>,
         pub hits: Vec<(Timestamp, SkillHit)>,
     }
 
-    #[derive(Serialize)]
+    #[derive(Serialize, Deserialize)]
     pub struct SkillHit {
         pub damage: Damage,
         pub target: EntityIndex,
@@ -84,108 +178,343 @@ pub mod log {
         pub is_front_attack: bool,
     }
 
+    /// Buff categories a hit was dealt under, kept apart from its [`SkillHit`] so that hits are
+    /// stored as they were in earlier versions. Hits dealt under no category aren't listed.
+    #[derive(Serialize, Deserialize)]
+    pub struct BuffedHit {
+        pub entity: EntityIndex,
+        pub skill: SkillId,
+        /// Index of the hit in the skill's hits.
+        pub hit: usize,
+        pub buffed: Vec<bool>,
+        /// Players whose buffs the hit was dealt under, by category index.
+        pub buffed_by: Vec<(usize, EntityIndex)>,
+    }
+
+    /// Damage that a player's buffs added to the hits of another player.
+    #[derive(Serialize, Deserialize)]
+    pub struct BuffContribution {
+        pub source: EntityIndex,
+        pub applicant: EntityIndex,
+        pub damage: Damage,
+    }
+
+    /// Totals of the shields a player applied to an entity, as shield value.
+    #[derive(Serialize, Deserialize)]
+    pub struct Shielding {
+        pub applicant: EntityIndex,
+        pub target: EntityIndex,
+        pub given: u64,
+        pub absorbed: u64,
+        pub expired: u64,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub struct Death {
+        pub entity: EntityIndex,
+        pub time: Timestamp,
+        pub source: EntityIndex,
+        pub revived: Option<Timestamp>,
+        pub recap: Vec<(Timestamp, RecapHit)>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub struct RecapHit {
+        pub source: EntityIndex,
+        pub skill: SkillId,
+        pub name: Option<String>,
+        pub damage: Damage,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub struct Counter {
+        pub source: EntityIndex,
+        pub target: EntityIndex,
+        pub kind: u32,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub struct Identity {
+        pub entity: EntityIndex,
+        pub gauge: Vec<(Timestamp, [u32; 3])>,
+        pub stances: Vec<(Timestamp, u8)>,
+        pub ultimates: Vec<(Timestamp, SkillId)>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub struct StatusEffect {
+        pub target: EntityIndex,
+        pub source: EntityIndex,
+        pub id: u32,
+        pub name: Option<String>,
+        pub scope: crate::meter::StatusEffectScope,
+        pub start: Timestamp,
+        pub end: Option<Timestamp>,
+        pub stacks: Vec<(Timestamp, u8)>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub struct ZoneStatusEffect {
+        pub id: u32,
+        pub name: Option<String>,
+        pub target: crate::meter::ZoneTarget,
+        pub start: Timestamp,
+        pub end: Option<Timestamp>,
+        pub stacks: Vec<(Timestamp, u8)>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub struct Stagger {
+        pub entity: EntityIndex,
+        /// Stagger bar updates, as points and maximum points.
+        pub samples: Vec<(Timestamp, u32, u32)>,
+        pub checks: Vec<StaggerCheck>,
+        /// Stagger dealt by each player.
+        pub contributions: Vec<(EntityIndex, u64)>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub struct StaggerCheck {
+        pub start: Timestamp,
+        pub end: Option<Timestamp>,
+        pub max_point: u32,
+        pub success: bool,
+    }
+
+    /// A raid run, saved alongside the logs of its encounters.
+    #[derive(Serialize, Deserialize)]
+    pub struct Run {
+        pub raid_id: u32,
+        /// Unix time the run was first seen at, in seconds.
+        pub started_at: u64,
+        /// Official time taken to clear the raid, in milliseconds.
+        pub clear_time: Option<u64>,
+        /// Milliseconds spent in the raid when it was last (re-)entered.
+        pub total_time: u64,
+        pub result: Option<u8>,
+        pub brave_hearts: u8,
+        pub init_brave_hearts: u8,
+        pub bosses: Vec<(u32, bool)>,
+        /// Milliseconds since the run was first seen, of each boss kill.
+        pub kills: Vec<u64>,
+        pub battle_items: Vec<BattleItemUse>,
+        pub encounters: Vec<RunEncounter>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub struct BattleItemUse {
+        pub player: Option<String>,
+        pub status_effect_id: u32,
+        pub name: Option<String>,
+        pub uses: u32,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    pub struct RunEncounter {
+        pub name: Option<String>,
+        pub difficulty: Option<crate::definitions::Difficulty>,
+        /// Milliseconds from the first to the last damage dealt.
+        pub duration: u64,
+        pub status: Option<Status>,
+    }
+
+    impl Entity {
+        fn new(id: u64, name: Option<String>, kind: EntityKind) -> Self {
+            Self {
+                id,
+                name,
+                damage: Vec::new(),
+                casts: Vec::new(),
+                skills: BTreeMap::new(),
+                kind,
+            }
+        }
+    }
+
     impl Log {
+        /// Build a log of a finished encounter. `parties` maps player IDs to their party, like
+        /// [`crate::meter::LiveData::parties`].
         pub fn from_encounter(
             enc: &crate::meter::Encounter,
             env: &crate::meter::Environment,
+            parties: &BTreeMap<u64, u32>,
         ) -> Option<Self> {
             let start = enc.start;
             let to_ts = |instant: std::time::Instant| -> Timestamp {
-                instant
-                    .duration_since(start)
-                    .as_millis()
-                    .try_into()
-                    .map(Timestamp)
-                    .expect("millis to fit in u64")
+                Timestamp(
+                    instant
+                        .saturating_duration_since(start)
+                        .as_millis()
+                        .try_into()
+                        .unwrap_or(u64::MAX),
+                )
             };
-            let end = to_ts(enc.end.unwrap());
+            let end = to_ts(enc.end?);
+
             let mut entities = Vec::new();
             let mut entity_map: BTreeMap<u64, EntityIndex> = BTreeMap::new();
-            for (&id, player) in &env.players {
-                let Some(enc_data) = enc.players.get(&id) else {
-                    continue;
-                };
-                let entity = Entity {
-                    name: player.name.clone(),
-                    damage: enc_data
-                        .damage
-                        .iter()
-                        .map(|&(i, dmg)| (to_ts(i), Damage(dmg)))
-                        .collect(),
-                    casts: enc_data
-                        .casts
-                        .iter()
-                        .map(|&(i, id)| (to_ts(i), SkillId(id)))
-                        .collect(),
-                    skills: BTreeMap::new(),
-                    kind: EntityKind::Player,
+            let mut add = |entity: Entity| {
+                entity_map.entry(entity.id).or_insert_with(|| {
+                    entities.push(entity);
+                    EntityIndex(entities.len() - 1)
+                });
+            };
+
+            for &id in enc.players.keys().chain(enc.damage_taken.keys()) {
+                let player = env.players.get(&id).cloned().unwrap_or_default();
+                let kind = EntityKind::Player {
+                    class_id: player.class.id(),
+                    ilvl: player.ilvl,
                 };
-                entity_map.insert(id, EntityIndex(entities.len()));
-                entities.push(entity);
+                add(Entity::new(id, player.name, kind));
             }
-
             for (&id, npc) in &env.npcs {
-                let entity = Entity {
-                    name: Some(npc.name.clone()),
-                    damage: Vec::new(),
-                    casts: Vec::new(),
-                    skills: BTreeMap::new(),
-                    kind: EntityKind::Npc(SpeciesId(npc.kind)),
-                };
-                entity_map.insert(id, EntityIndex(entities.len()));
-                entities.push(entity);
+                let kind = EntityKind::Npc(SpeciesId(npc.kind));
+                add(Entity::new(id, npc.name.clone(), kind));
+            }
+
+            // every source and target of a hit needs an entity for hits to refer to, as does
+            // everyone involved in a death, counter, status effect, stagger, buffed hit, buff
+            // contribution or shield, and everyone with identity data
+            let sources = || enc.players.iter().chain(&enc.npcs);
+            let targets = sources()
+                .flat_map(|(_, data)| data.skills.values())
+                .flat_map(|skill| skill.hits.iter().map(|(_, hit)| hit.target_id));
+            let death_ids = enc.deaths.iter().flat_map(|death| {
+                [death.player_id, death.source_id]
+                    .into_iter()
+                    .chain(death.recap.iter().map(|hit| hit.source_id))
+            });
+            let counter_ids = enc.counters.iter().flat_map(|(&id, counters)| {
+                std::iter::once(id).chain(counters.iter().map(|counter| counter.target_id))
+            });
+            let status_effect_ids = enc.status_effects.iter().flat_map(|(&id, effects)| {
+                std::iter::once(id).chain(effects.iter().map(|effect| effect.source_id))
+            });
+            let stagger_ids = enc.stagger.iter().flat_map(|(&id, stagger)| {
+                std::iter::once(id).chain(stagger.contribution.keys().copied())
+            });
+            let applicant_ids = enc.players.values().flat_map(|data| {
+                let hits = data.skills.values().flat_map(|skill| &skill.hits);
+                data.buff_contributions.keys().copied().chain(
+                    hits.flat_map(|(_, hit)| hit.buffed_by.iter().map(|&(_, applicant)| applicant)),
+                )
+            });
+            for id in sources()
+                .map(|(&id, _)| id)
+                .chain(targets)
+                .chain(death_ids)
+                .chain(counter_ids)
+                .chain(enc.identity.keys().copied())
+                .chain(status_effect_ids)
+                .chain(stagger_ids)
+                .chain(applicant_ids)
+                .chain(
+                    enc.shields
+                        .keys()
+                        .flat_map(|&(applicant, target)| [applicant, target]),
+                )
+            {
+                add(Entity::new(id, None, EntityKind::Unknown));
             }
 
-            for (i, entity) in entities.iter_mut().enumerate() {
-                // need to get their id back out
-                let (id, _) = entity_map
+            for (id, data) in sources() {
+                let entity = &mut entities[entity_map[id].0];
+                entity.damage = data
+                    .damage
                     .iter()
-                    .find(|(_, &EntityIndex(idx))| idx == i)
-                    .unwrap();
-                let Some(enc_data) = enc.players.get(&id) else {
-                    continue;
-                };
-                let skills = enc_data
+                    .map(|&(i, dmg)| (to_ts(i), Damage(dmg)))
+                    .collect();
+                entity.casts = data
+                    .casts
+                    .iter()
+                    .map(|&(i, id)| (to_ts(i), SkillId(id)))
+                    .collect();
+                entity.skills = data
                     .skills
                     .iter()
                     .map(|(&id, skill)| {
-                        let id = SkillId(id);
+                        let hits = skill
+                            .hits
+                            .iter()
+                            .map(|(i, hit)| {
+                                let hit = SkillHit {
+                                    damage: Damage(hit.damage),
+                                    target: entity_map[&hit.target_id],
+                                    is_crit: hit.is_crit,
+                                    is_back_attack: hit.is_back_attack,
+                                    is_front_attack: hit.is_front_attack,
+                                };
+                                (to_ts(*i), hit)
+                            })
+                            .collect();
                         let skill = Skill {
                             name: skill.name.clone(),
-                            hits: skill
-                                .hits
-                                .iter()
-                                .filter_map(|(i, hit)| {
-                                    // TODO: unsure what to do when target is not in env
-                                    let Some(target) = entity_map.get(&hit.target_id).copied()
-                                    else {
-                                        println!("target wasn't in env: {}", hit.target_id);
-                                        return None;
-                                    };
-                                    Some((
-                                        to_ts(*i),
-                                        SkillHit {
-                                            damage: Damage(hit.damage),
-                                            target,
-                                            is_crit: hit.is_crit,
-                                            is_back_attack: hit.is_back_attack,
-                                            is_front_attack: hit.is_front_attack,
-                                        },
-                                    ))
-                                })
-                                .collect(),
+                            hits,
                         };
-                        (id, skill)
+                        (SkillId(id), skill)
                     })
                     .collect();
-                entity.skills = skills;
             }
 
+            let mut buffed_hits = Vec::new();
+            for (id, data) in sources() {
+                for (&skill_id, skill) in &data.skills {
+                    let hits = skill.hits.iter().map(|(_, hit)| hit).enumerate();
+                    for (idx, hit) in hits.filter(|(_, hit)| hit.buffed.contains(&true)) {
+                        buffed_hits.push(BuffedHit {
+                            entity: entity_map[id],
+                            skill: SkillId(skill_id),
+                            hit: idx,
+                            buffed: hit.buffed.clone(),
+                            buffed_by: hit
+                                .buffed_by
+                                .iter()
+                                .map(|&(category, applicant)| (category, entity_map[&applicant]))
+                                .collect(),
+                        });
+                    }
+                }
+            }
+
+            let buff_contributions = enc
+                .players
+                .iter()
+                .flat_map(|(id, data)| {
+                    data.buff_contributions
+                        .iter()
+                        .map(move |(applicant, &damage)| (id, applicant, damage))
+                })
+                .map(|(id, applicant, damage)| BuffContribution {
+                    source: entity_map[id],
+                    applicant: entity_map[applicant],
+                    damage: Damage(damage),
+                })
+                .collect();
+
+            let shields = enc
+                .shields
+                .iter()
+                .map(|((applicant, target), shielding)| Shielding {
+                    applicant: entity_map[applicant],
+                    target: entity_map[target],
+                    given: shielding.given,
+                    absorbed: shielding.absorbed,
+                    expired: shielding.expired,
+                })
+                .collect();
+
+            // parties also list players by character ID, which no entity is identified by
+            let parties = parties
+                .iter()
+                .filter_map(|(id, &party)| Some((*entity_map.get(id)?, party)))
+                .collect();
+
             let pov = env.pov.and_then(|id| entity_map.get(&id)).copied();
             let targets = enc
                 .tracked
                 .iter()
-                .flat_map(|(id, _)| entity_map.get(&id))
+                .flat_map(|(id, _)| entity_map.get(id))
                 .copied()
                 .collect();
 
@@ -197,166 +526,1262 @@ pub mod log {
                 None
             };
 
+            let zone = enc.zone.as_ref().map(|zone| Zone {
+                id: zone.id,
+                name: zone.name.clone(),
+                difficulty: zone.difficulty,
+            });
+
+            let deaths = enc
+                .deaths
+                .iter()
+                .map(|death| Death {
+                    entity: entity_map[&death.player_id],
+                    time: to_ts(death.time),
+                    source: entity_map[&death.source_id],
+                    revived: death.revived.map(to_ts),
+                    recap: death
+                        .recap
+                        .iter()
+                        .map(|hit| {
+                            let recap = RecapHit {
+                                source: entity_map[&hit.source_id],
+                                skill: SkillId(hit.skill_id),
+                                name: hit.skill.clone(),
+                                damage: Damage(hit.damage),
+                            };
+                            (to_ts(hit.time), recap)
+                        })
+                        .collect(),
+                })
+                .collect();
+
+            let counters = enc
+                .counters
+                .iter()
+                .flat_map(|(id, counters)| counters.iter().map(move |counter| (id, counter)))
+                .map(|(id, counter)| {
+                    let log_counter = Counter {
+                        source: entity_map[id],
+                        target: entity_map[&counter.target_id],
+                        kind: counter.kind,
+                    };
+                    (to_ts(counter.time), log_counter)
+                })
+                .collect();
+
+            let identities = enc
+                .identity
+                .iter()
+                .map(|(id, identity)| Identity {
+                    entity: entity_map[id],
+                    gauge: identity
+                        .gauge
+                        .iter()
+                        .map(|&(i, gauge)| (to_ts(i), gauge))
+                        .collect(),
+                    stances: identity
+                        .stances
+                        .iter()
+                        .map(|&(i, stance)| (to_ts(i), stance))
+                        .collect(),
+                    ultimates: identity
+                        .ultimates
+                        .iter()
+                        .map(|&(i, id)| (to_ts(i), SkillId(id)))
+                        .collect(),
+                })
+                .collect();
+
+            let status_effects = enc
+                .status_effects
+                .iter()
+                .flat_map(|(id, effects)| effects.iter().map(move |effect| (id, effect)))
+                .map(|(id, effect)| StatusEffect {
+                    target: entity_map[id],
+                    source: entity_map[&effect.source_id],
+                    id: effect.status_effect_id,
+                    name: effect.name.clone(),
+                    scope: effect.scope,
+                    start: to_ts(effect.start),
+                    end: effect.end.map(to_ts),
+                    stacks: effect
+                        .stacks
+                        .iter()
+                        .map(|&(i, stacks)| (to_ts(i), stacks))
+                        .collect(),
+                })
+                .collect();
+            let zone_status_effects = enc
+                .zone_status_effects
+                .iter()
+                .map(|effect| ZoneStatusEffect {
+                    id: effect.status_effect_id,
+                    name: effect.name.clone(),
+                    target: match effect.scope {
+                        crate::meter::StatusEffectScope::Zone(target) => target,
+                        _ => crate::meter::ZoneTarget::Players,
+                    },
+                    start: to_ts(effect.start),
+                    end: effect.end.map(to_ts),
+                    stacks: effect
+                        .stacks
+                        .iter()
+                        .map(|&(i, stacks)| (to_ts(i), stacks))
+                        .collect(),
+                })
+                .collect();
+
+            let stagger = enc
+                .stagger
+                .iter()
+                .map(|(id, stagger)| Stagger {
+                    entity: entity_map[id],
+                    samples: stagger
+                        .samples
+                        .iter()
+                        .map(|sample| (to_ts(sample.time), sample.point, sample.max_point))
+                        .collect(),
+                    checks: stagger
+                        .checks
+                        .iter()
+                        .map(|check| StaggerCheck {
+                            start: to_ts(check.start),
+                            end: check.end.map(to_ts),
+                            max_point: check.max_point,
+                            success: check.success,
+                        })
+                        .collect(),
+                    contributions: stagger
+                        .contribution
+                        .iter()
+                        .map(|(id, &amount)| (entity_map[id], amount))
+                        .collect(),
+                })
+                .collect();
+
             let log = Self {
                 end,
                 entities,
                 pov,
                 targets,
                 status,
+                zone,
+                raid_id: enc.raid_id,
+                deaths,
+                counters,
+                identities,
+                status_effects,
+                zone_status_effects,
+                stagger,
+                buff_categories: enc.buff_categories.to_vec(),
+                buffed_hits,
+                buff_contributions,
+                parties,
+                shields,
             };
 
             Some(log)
         }
-    }
-}
-
-/// Processes packets and updates [`Data`].
-pub struct Meter {
-    ui_ctx: egui::Context,
-    data: Arc<Mutex<Data>>,
-    skill_data: SkillData,
-
-    #[cfg(feature = "packet_logging")]
-    log: Vec<u8>,
-}
-
-impl Meter {
-    pub fn new(ui_ctx: egui::Context, data: Arc<Mutex<Data>>) -> anyhow::Result<Self> {
-        Ok(Self {
-            ui_ctx,
-            data,
-            skill_data: SkillData::load()?,
-
-            #[cfg(feature = "packet_logging")]
-            log: Vec::new(),
-        })
-    }
-
-    // process an incoming set of damage events
-    fn process_damages<'a>(
-        &mut self,
-        source_id: u64,
-        skill_id: u32,
-        events: impl Iterator<Item = &'a SkillDamageEvent>,
-    ) -> anyhow::Result<()> {
-        let timestamp = Instant::now();
 
-        let data = &mut *self.data.lock();
-        let mut id = source_id;
-        while let Some(p) = data.current_env().projectiles.get(&id) {
-            println!("projectile");
-            id = p.owner_id;
-        }
+        /// Rebuild a viewable encounter from the log, along with an environment containing its
+        /// entities.
+        pub fn into_encounter(self) -> (crate::meter::Environment, crate::meter::Encounter) {
+            use crate::meter;
+            use std::time::{Duration, Instant};
 
-        // add dummy player to environment
-        if let Entry::Vacant(e) = data.current_env_mut().players.entry(id) {
-            let info = Player {
-                name: None,
-                class: match self.skill_data.class_for(skill_id) {
-                    Some(p) => p,
-                    None => return Ok(()),
-                },
-                ilvl: 0.0,
-                character_id: None,
+            let start = Instant::now();
+            let to_instant = |Timestamp(ms): Timestamp| {
+                start
+                    .checked_add(Duration::from_millis(ms))
+                    .unwrap_or(start)
             };
-            println!("adding dummy player {}", id);
-            e.insert(info);
-            // println!("players: {:#?}", data.current_env().players);
-        }
-
-        let enc = {
-            let len = data.encounters.len();
-            &mut data.encounters[len - 1]
-        };
-
-        let player = enc.players.entry(id).or_insert_with(Default::default);
-        let party = data.live.parties.get(&id).copied();
-        let has_ap_buff = data.live.player_has_ap_buff(id);
-        let has_ident_buff = data.live.player_has_ident_buff(id);
-        let mut target_is_boss = false;
 
-        for evt in events {
-            let overkill = evt.cur_hp.min(0).abs();
-            let damage = evt.damage.saturating_sub(overkill).max(0);
-            let flag = evt.flag()?;
-            if damage == 0 {
-                continue;
-            }
-            if flag == HitFlag::DamageShare
-            /* && skill_id == 0 */
-            {
-                println!("is this sidereal damage?");
-                continue;
-            }
-            let option = evt.option()?;
-            let branded = data.live.target_has_brand(id, evt.target_id, party);
-            player.damage.push((timestamp, damage));
-            player.dmg_dealt += damage;
-            player.hits += 1;
-
-            let skill = player.skills.entry(skill_id).or_insert_with(|| SkillUsage {
-                name: match self.skill_data.name(skill_id) {
-                    None if skill_id == 0 && flag.is_dot() => Some("Bleed"),
-                    rest => rest,
-                }
-                .map(ToOwned::to_owned),
+            let ids: Vec<u64> = self.entities.iter().map(|e| e.id).collect();
+            let mut env = meter::Environment {
+                pov: self.pov.map(|EntityIndex(i)| ids[i]),
                 ..Default::default()
-            });
-
-            player.casts.push((timestamp, skill_id));
-
-            let hit = SkillHit {
-                damage,
-                target_id: evt.target_id,
-                is_crit: flag.is_crit(),
-                is_back_attack: matches!(option, HitOption::BackAttack),
-                is_front_attack: matches!(option, HitOption::FrontalAttack),
             };
-            skill.hits.push((timestamp, hit));
-
-            skill.count += 1;
-            skill.damage += damage;
-            if flag.is_crit() {
-                skill.crits += 1;
+            let mut buffed_hits: BTreeMap<usize, BTreeMap<(SkillId, usize), BuffedHit>> =
+                BTreeMap::new();
+            for hit in self.buffed_hits {
+                buffed_hits
+                    .entry(hit.entity.0)
+                    .or_default()
+                    .insert((hit.skill, hit.hit), hit);
             }
-            match option {
-                HitOption::BackAttack => skill.back += 1,
-                HitOption::FrontalAttack => skill.front += 1,
-                _ => {}
-            }
-            if branded {
-                skill.brand += 1;
-                player.brand_hits += 1;
-                player.brand_dmg += damage;
-            }
-            if has_ap_buff {
-                skill.ap_buff += 1;
-                player.ap_hits += 1;
-                player.ap_dmg += damage;
+
+            let mut players = BTreeMap::new();
+            let mut npcs = BTreeMap::new();
+            for (idx, entity) in self.entities.into_iter().enumerate() {
+                let id = entity.id;
+                let data = entity_data(
+                    entity.damage,
+                    entity.casts,
+                    entity.skills,
+                    buffed_hits.remove(&idx).unwrap_or_default(),
+                    &ids,
+                    to_instant,
+                );
+                match entity.kind {
+                    EntityKind::Player { class_id, ilvl } => {
+                        let player = meter::Player {
+                            name: entity.name,
+                            class: crate::definitions::Class::from_id(class_id),
+                            ilvl,
+                            character_id: None,
+                        };
+                        env.add_player(id, player);
+                        if !data.damage.is_empty() {
+                            players.insert(id, data);
+                        }
+                    }
+                    EntityKind::Npc(SpeciesId(kind)) => {
+                        let npc = meter::Npc {
+                            id,
+                            kind,
+                            name: entity.name,
+                            grade: Default::default(),
+                            bars: None,
+                        };
+                        env.add_npc(id, npc);
+                        if !data.damage.is_empty() {
+                            npcs.insert(id, data);
+                        }
+                    }
+                    EntityKind::Unknown => {}
+                }
             }
-            if has_ident_buff {
-                skill.ident_buff += 1;
-                player.ident_hits += 1;
-                player.ident_dmg += damage;
+
+            for contribution in self.buff_contributions {
+                if let Some(data) = players.get_mut(&ids[contribution.source.0]) {
+                    *data
+                        .buff_contributions
+                        .entry(ids[contribution.applicant.0])
+                        .or_default() += contribution.damage.0;
+                }
             }
 
-            for &(id, tracked) in &enc.tracked {
-                if id == evt.target_id {
-                    target_is_boss = true;
-                    data.live.recently_tracked = Some(id);
-                    data.live.tracked.insert(
-                        id,
-                        BossInfo {
-                            max_hp: evt.max_hp,
-                            cur_hp: evt.cur_hp,
-                            bar_count: tracked.max_bars(),
-                        },
-                    );
+            // damage taken isn't stored, but is implied by npc hits
+            let mut damage_taken: BTreeMap<u64, meter::DamageTaken> = BTreeMap::new();
+            for skills in npcs.values().map(|data: &meter::PlayerData| &data.skills) {
+                for (&skill_id, skill) in skills {
+                    for (_, hit) in &skill.hits {
+                        if env.players.contains_key(&hit.target_id) {
+                            damage_taken.entry(hit.target_id).or_default().add(
+                                skill_id,
+                                skill.name.as_deref(),
+                                hit.damage,
+                            );
+                        }
+                    }
                 }
             }
-        }
 
-        let encounter = data.current_enc_mut();
+            let deaths = self
+                .deaths
+                .into_iter()
+                .map(|death| meter::Death {
+                    player_id: ids[death.entity.0],
+                    time: to_instant(death.time),
+                    source_id: ids[death.source.0],
+                    revived: death.revived.map(to_instant),
+                    recap: death
+                        .recap
+                        .into_iter()
+                        .map(|(ts, hit)| meter::RecapHit {
+                            time: to_instant(ts),
+                            source_id: ids[hit.source.0],
+                            skill_id: hit.skill.0,
+                            skill: hit.name,
+                            damage: hit.damage.0,
+                        })
+                        .collect(),
+                })
+                .collect();
+
+            let mut counters: BTreeMap<u64, Vec<meter::Counter>> = BTreeMap::new();
+            for (ts, counter) in self.counters {
+                counters
+                    .entry(ids[counter.source.0])
+                    .or_default()
+                    .push(meter::Counter {
+                        time: to_instant(ts),
+                        target_id: ids[counter.target.0],
+                        kind: counter.kind,
+                    });
+            }
+
+            let identity = self
+                .identities
+                .into_iter()
+                .map(|identity| {
+                    let data = meter::Identity {
+                        gauge: identity
+                            .gauge
+                            .into_iter()
+                            .map(|(ts, gauge)| (to_instant(ts), gauge))
+                            .collect(),
+                        stances: identity
+                            .stances
+                            .into_iter()
+                            .map(|(ts, stance)| (to_instant(ts), stance))
+                            .collect(),
+                        ultimates: identity
+                            .ultimates
+                            .into_iter()
+                            .map(|(ts, SkillId(id))| (to_instant(ts), id))
+                            .collect(),
+                    };
+                    (ids[identity.entity.0], data)
+                })
+                .collect();
+
+            let mut status_effects: BTreeMap<u64, Vec<meter::StatusEffectInterval>> =
+                BTreeMap::new();
+            for effect in self.status_effects {
+                let interval = meter::StatusEffectInterval {
+                    status_effect_id: effect.id,
+                    instance_id: 0,
+                    source_id: ids[effect.source.0],
+                    name: effect.name,
+                    scope: effect.scope,
+                    start: to_instant(effect.start),
+                    end: effect.end.map(to_instant),
+                    stacks: effect
+                        .stacks
+                        .into_iter()
+                        .map(|(ts, stacks)| (to_instant(ts), stacks))
+                        .collect(),
+                    total_time: 0.0,
+                    end_tick: 0,
+                    refreshes: Vec::new(),
+                };
+                status_effects
+                    .entry(ids[effect.target.0])
+                    .or_default()
+                    .push(interval);
+            }
+            let zone_status_effects = self
+                .zone_status_effects
+                .into_iter()
+                .map(|effect| meter::StatusEffectInterval {
+                    status_effect_id: effect.id,
+                    instance_id: 0,
+                    source_id: 0,
+                    name: effect.name,
+                    scope: meter::StatusEffectScope::Zone(effect.target),
+                    start: to_instant(effect.start),
+                    end: effect.end.map(to_instant),
+                    stacks: effect
+                        .stacks
+                        .into_iter()
+                        .map(|(ts, stacks)| (to_instant(ts), stacks))
+                        .collect(),
+                    total_time: 0.0,
+                    end_tick: 0,
+                    refreshes: Vec::new(),
+                })
+                .collect();
+
+            let stagger = self
+                .stagger
+                .into_iter()
+                .map(|stagger| {
+                    let data = meter::Stagger {
+                        samples: stagger
+                            .samples
+                            .into_iter()
+                            .map(|(ts, point, max_point)| meter::StaggerSample {
+                                time: to_instant(ts),
+                                point,
+                                max_point,
+                            })
+                            .collect(),
+                        checks: stagger
+                            .checks
+                            .into_iter()
+                            .map(|check| meter::StaggerCheck {
+                                start: to_instant(check.start),
+                                end: check.end.map(to_instant),
+                                max_point: check.max_point,
+                                success: check.success,
+                            })
+                            .collect(),
+                        contribution: stagger
+                            .contributions
+                            .into_iter()
+                            .map(|(EntityIndex(i), amount)| (ids[i], amount))
+                            .collect(),
+                        last_point: BTreeMap::new(),
+                    };
+                    (ids[stagger.entity.0], data)
+                })
+                .collect();
+
+            let shields = self
+                .shields
+                .into_iter()
+                .map(|shielding| {
+                    let totals = meter::Shielding {
+                        given: shielding.given,
+                        absorbed: shielding.absorbed,
+                        expired: shielding.expired,
+                    };
+                    (
+                        (ids[shielding.applicant.0], ids[shielding.target.0]),
+                        totals,
+                    )
+                })
+                .collect();
+
+            let damage_times = || {
+                players
+                    .values()
+                    .flat_map(|p: &meter::PlayerData| p.damage.iter().map(|&(i, _)| i))
+            };
+            let first_damage = damage_times().min();
+            let last_damage = damage_times().max();
+            let encounter = meter::Encounter {
+                start,
+                end: Some(to_instant(self.end)),
+                first_damage,
+                last_damage,
+                wipe: matches!(self.status, Some(Status::Wipe)),
+                clear: matches!(self.status, Some(Status::Clear)),
+                zone: self.zone.map(|zone| meter::Zone {
+                    id: zone.id,
+                    name: zone.name,
+                    difficulty: zone.difficulty,
+                }),
+                raid_id: self.raid_id,
+                players,
+                npcs,
+                damage_taken,
+                deaths,
+                counters,
+                identity,
+                status_effects,
+                zone_status_effects,
+                stagger,
+                buff_categories: self.buff_categories.into(),
+                shields,
+                ..Default::default()
+            };
+            (env, encounter)
+        }
+
+        /// Party of each player when the log was saved, by player ID.
+        pub fn parties(&self) -> BTreeMap<u64, u32> {
+            self.parties
+                .iter()
+                .map(|&(EntityIndex(i), party)| (self.entities[i].id, party))
+                .collect()
+        }
+
+        /// Deserialize a log body written in format `version`, upgrading it if it's older.
+        pub fn from_versioned(version: u16, body: &[u8]) -> anyhow::Result<Self> {
+            let log = match version {
+                0 => Self::upgrade::<v0::Log>(body)?,
+                VERSION => serde_bare::from_slice(body)?,
+                _ => anyhow::bail!(
+                    "log format version {} is newer than the supported version {}",
+                    version,
+                    VERSION
+                ),
+            };
+            Ok(log)
+        }
+
+        fn upgrade<T>(body: &[u8]) -> anyhow::Result<Self>
+        where
+            T: serde::de::DeserializeOwned + Into<Self>,
+        {
+            Ok(serde_bare::from_slice::<T>(body)?.into())
+        }
+
+        /// Check that entity and buff category indices refer to entities and categories in the
+        /// log.
+        pub fn validate(&self) -> anyhow::Result<()> {
+            let count = self.entities.len();
+            let hit_targets = self
+                .entities
+                .iter()
+                .flat_map(|e| e.skills.values())
+                .flat_map(|s| s.hits.iter().map(|(_, hit)| hit.target));
+            let death_entities = self.deaths.iter().flat_map(|death| {
+                [death.entity, death.source]
+                    .into_iter()
+                    .chain(death.recap.iter().map(|(_, hit)| hit.source))
+            });
+            let counter_entities = self
+                .counters
+                .iter()
+                .flat_map(|(_, counter)| [counter.source, counter.target]);
+            let identity_entities = self.identities.iter().map(|identity| identity.entity);
+            let status_effect_entities = self
+                .status_effects
+                .iter()
+                .flat_map(|effect| [effect.target, effect.source]);
+            let stagger_entities = self.stagger.iter().flat_map(|stagger| {
+                std::iter::once(stagger.entity)
+                    .chain(stagger.contributions.iter().map(|&(entity, _)| entity))
+            });
+            let buffed_hit_entities = self.buffed_hits.iter().flat_map(|hit| {
+                std::iter::once(hit.entity).chain(hit.buffed_by.iter().map(|&(_, entity)| entity))
+            });
+            let contribution_entities = self
+                .buff_contributions
+                .iter()
+                .flat_map(|contribution| [contribution.source, contribution.applicant]);
+            let shield_entities = self
+                .shields
+                .iter()
+                .flat_map(|shielding| [shielding.applicant, shielding.target]);
+            let mut indices = self
+                .pov
+                .iter()
+                .chain(&self.targets)
+                .copied()
+                .chain(hit_targets)
+                .chain(death_entities)
+                .chain(counter_entities)
+                .chain(identity_entities)
+                .chain(status_effect_entities)
+                .chain(stagger_entities)
+                .chain(buffed_hit_entities)
+                .chain(contribution_entities)
+                .chain(self.parties.iter().map(|&(entity, _)| entity))
+                .chain(shield_entities);
+            if let Some(EntityIndex(idx)) = indices.find(|&EntityIndex(idx)| idx >= count) {
+                anyhow::bail!("entity index {} out of range of {} entities", idx, count);
+            }
+
+            let categories = self.buff_categories.len();
+            let mut category_indices = self.buffed_hits.iter().flat_map(|hit| {
+                // `buffed` has at most an entry per category
+                let buffed = hit.buffed.len().checked_sub(1);
+                buffed
+                    .into_iter()
+                    .chain(hit.buffed_by.iter().map(|&(idx, _)| idx))
+            });
+            if let Some(idx) = category_indices.find(|&idx| idx >= categories) {
+                anyhow::bail!(
+                    "buff category index {} out of range of {} categories",
+                    idx,
+                    categories
+                );
+            }
+            Ok(())
+        }
+    }
+
+    /// Log structures as written before logs had a [`Header`], which recorded neither entity IDs
+    /// nor player classes and item levels.
+    mod v0 {
+        use super::{EntityIndex, Skill, SkillId, SpeciesId, Status, Timestamp};
+        use serde::Deserialize;
+        use std::collections::BTreeMap;
+
+        #[derive(Deserialize)]
+        #[cfg_attr(test, derive(serde::Serialize))]
+        pub struct Log {
+            pub end: Timestamp,
+            pub entities: Vec<Entity>,
+            pub pov: Option<EntityIndex>,
+            pub targets: Vec<EntityIndex>,
+            pub status: Option<Status>,
+        }
+
+        #[derive(Deserialize)]
+        #[cfg_attr(test, derive(serde::Serialize))]
+        pub struct Entity {
+            pub name: Option<String>,
+            pub damage: Vec<(Timestamp, super::Damage)>,
+            pub casts: Vec<(Timestamp, SkillId)>,
+            pub skills: BTreeMap<SkillId, Skill>,
+            pub kind: EntityKind,
+        }
+
+        #[derive(Deserialize)]
+        #[cfg_attr(test, derive(serde::Serialize))]
+        pub enum EntityKind {
+            Player,
+            Npc(SpeciesId),
+        }
+    }
+
+    impl From<v0::Log> for Log {
+        // indices stand in for entity IDs, and everything recorded since starts out empty
+        fn from(log: v0::Log) -> Self {
+            let entities = log
+                .entities
+                .into_iter()
+                .enumerate()
+                .map(|(i, e)| Entity {
+                    id: i as u64,
+                    name: e.name,
+                    damage: e.damage,
+                    casts: e.casts,
+                    skills: e.skills,
+                    kind: match e.kind {
+                        v0::EntityKind::Player => EntityKind::Player {
+                            class_id: 0,
+                            ilvl: 0.0,
+                        },
+                        v0::EntityKind::Npc(species) => EntityKind::Npc(species),
+                    },
+                })
+                .collect();
+            Self {
+                end: log.end,
+                entities,
+                pov: log.pov,
+                targets: log.targets,
+                status: log.status,
+                zone: None,
+                raid_id: None,
+                deaths: Vec::new(),
+                counters: Vec::new(),
+                identities: Vec::new(),
+                status_effects: Vec::new(),
+                zone_status_effects: Vec::new(),
+                stagger: Vec::new(),
+                buff_categories: Vec::new(),
+                buffed_hits: Vec::new(),
+                buff_contributions: Vec::new(),
+                parties: Vec::new(),
+                shields: Vec::new(),
+            }
+        }
+    }
+
+    /// Metrics of an entity, with hit targets and buff applicants mapped from indices back to
+    /// entity IDs. `buffed` holds the entity's [`BuffedHit`]s, by skill and hit index.
+    fn entity_data(
+        damage: Vec<(Timestamp, Damage)>,
+        casts: Vec<(Timestamp, SkillId)>,
+        skills: BTreeMap<SkillId, Skill>,
+        mut buffed: BTreeMap<(SkillId, usize), BuffedHit>,
+        ids: &[u64],
+        to_instant: impl Fn(Timestamp) -> std::time::Instant,
+    ) -> crate::meter::PlayerData {
+        let mut data = crate::meter::PlayerData {
+            damage: damage
+                .into_iter()
+                .map(|(ts, Damage(dmg))| (to_instant(ts), dmg))
+                .collect(),
+            casts: casts
+                .into_iter()
+                .map(|(ts, SkillId(id))| (to_instant(ts), id))
+                .collect(),
+            ..Default::default()
+        };
+        for (SkillId(id), skill) in skills {
+            let mut usage = crate::meter::SkillUsage {
+                name: skill.name,
+                ..Default::default()
+            };
+            for (idx, (ts, hit)) in skill.hits.into_iter().enumerate() {
+                let (buffed, buffed_by) = match buffed.remove(&(SkillId(id), idx)) {
+                    Some(hit) => (hit.buffed, hit.buffed_by),
+                    None => (Vec::new(), Vec::new()),
+                };
+                let buffed_by: Vec<_> = buffed_by
+                    .into_iter()
+                    .map(|(category, EntityIndex(i))| (category, ids[i]))
+                    .collect();
+                usage.count += 1;
+                usage.crits += hit.is_crit as usize;
+                usage.back += hit.is_back_attack as usize;
+                usage.front += hit.is_front_attack as usize;
+                usage.damage += hit.damage.0;
+                crate::meter::add_buffed(&mut usage.buffed, &buffed, |hits| *hits += 1);
+                crate::meter::add_buffed(&mut data.buffed, &buffed, |buffed| {
+                    buffed.hits += 1;
+                    buffed.damage += hit.damage.0;
+                });
+                for &key in &buffed_by {
+                    let by = data.buffed_by.entry(key).or_default();
+                    by.hits += 1;
+                    by.damage += hit.damage.0;
+                }
+                usage.hits.push((
+                    to_instant(ts),
+                    crate::meter::SkillHit {
+                        damage: hit.damage.0,
+                        target_id: ids[hit.target.0],
+                        is_crit: hit.is_crit,
+                        is_back_attack: hit.is_back_attack,
+                        is_front_attack: hit.is_front_attack,
+                        buffed,
+                        buffed_by,
+                    },
+                ));
+            }
+            data.dmg_dealt += usage.damage;
+            data.hits += usage.count as u64;
+            data.skills.insert(id, usage);
+        }
+        data
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn skills() -> BTreeMap<SkillId, Skill> {
+            let hit = SkillHit {
+                damage: Damage(100),
+                target: EntityIndex(1),
+                is_crit: true,
+                is_back_attack: false,
+                is_front_attack: false,
+            };
+            let skill = Skill {
+                name: Some("skill".into()),
+                hits: vec![(Timestamp(1000), hit)],
+            };
+            BTreeMap::from([(SkillId(7), skill)])
+        }
+
+        fn entities() -> Vec<Entity> {
+            let player = EntityKind::Player {
+                class_id: 102,
+                ilvl: 1620.0,
+            };
+            let mut player = Entity::new(10, Some("player".into()), player);
+            player.skills = skills();
+            let npc = Entity::new(20, Some("boss".into()), EntityKind::Npc(SpeciesId(42)));
+            vec![player, npc]
+        }
+
+        fn base() -> Log {
+            Log {
+                end: Timestamp(10_000),
+                entities: entities(),
+                pov: Some(EntityIndex(0)),
+                targets: vec![EntityIndex(1)],
+                status: Some(Status::Clear),
+                zone: None,
+                raid_id: Some(3),
+                deaths: Vec::new(),
+                counters: Vec::new(),
+                identities: Vec::new(),
+                status_effects: Vec::new(),
+                zone_status_effects: Vec::new(),
+                stagger: Vec::new(),
+                buff_categories: Vec::new(),
+                buffed_hits: Vec::new(),
+                buff_contributions: Vec::new(),
+                parties: Vec::new(),
+                shields: Vec::new(),
+            }
+        }
+
+        fn death() -> Death {
+            Death {
+                entity: EntityIndex(0),
+                time: Timestamp(5000),
+                source: EntityIndex(1),
+                revived: None,
+                recap: Vec::new(),
+            }
+        }
+
+        fn stagger() -> Stagger {
+            Stagger {
+                entity: EntityIndex(1),
+                samples: vec![(Timestamp(1000), 50, 100)],
+                checks: vec![StaggerCheck {
+                    start: Timestamp(1000),
+                    end: Some(Timestamp(2000)),
+                    max_point: 100,
+                    success: true,
+                }],
+                contributions: vec![(EntityIndex(0), 100)],
+            }
+        }
+
+        fn brand() -> crate::definitions::BuffCategory {
+            crate::definitions::BuffCategory {
+                name: "Brand".into(),
+                holder: crate::definitions::BuffHolder::Target,
+                ids: Vec::new(),
+                buff_categories: vec!["classskill".into()],
+                kind: Some(crate::definitions::StatusEffectCategory::Debuff),
+                stats: Vec::new(),
+                supports_only: true,
+            }
+        }
+
+        fn buffed_hit() -> BuffedHit {
+            BuffedHit {
+                entity: EntityIndex(0),
+                skill: SkillId(7),
+                hit: 0,
+                buffed: vec![true],
+                buffed_by: vec![(0, EntityIndex(1))],
+            }
+        }
+
+        fn buff_contribution() -> BuffContribution {
+            BuffContribution {
+                source: EntityIndex(0),
+                applicant: EntityIndex(1),
+                damage: Damage(10),
+            }
+        }
+
+        fn shielding() -> Shielding {
+            Shielding {
+                applicant: EntityIndex(0),
+                target: EntityIndex(1),
+                given: 1000,
+                absorbed: 600,
+                expired: 400,
+            }
+        }
+
+        fn read(version: u16, log: &impl Serialize) -> Log {
+            let body = serde_bare::to_vec(log).unwrap();
+            let log = Log::from_versioned(version, &body).unwrap();
+            log.validate().unwrap();
+            log
+        }
+
+        #[test]
+        fn headerless_logs_are_upgraded() {
+            let entities = vec![
+                v0::Entity {
+                    name: Some("player".into()),
+                    damage: Vec::new(),
+                    casts: Vec::new(),
+                    skills: skills(),
+                    kind: v0::EntityKind::Player,
+                },
+                v0::Entity {
+                    name: Some("boss".into()),
+                    damage: Vec::new(),
+                    casts: Vec::new(),
+                    skills: BTreeMap::new(),
+                    kind: v0::EntityKind::Npc(SpeciesId(42)),
+                },
+            ];
+            let v0 = v0::Log {
+                end: Timestamp(10_000),
+                entities,
+                pov: Some(EntityIndex(0)),
+                targets: vec![EntityIndex(1)],
+                status: Some(Status::Clear),
+            };
+            let log = read(0, &v0);
+            let ids: Vec<_> = log.entities.iter().map(|e| e.id).collect();
+            assert_eq!(ids, [0, 1]);
+            assert!(matches!(
+                log.entities[0].kind,
+                EntityKind::Player { class_id: 0, .. }
+            ));
+            assert!(matches!(
+                log.entities[1].kind,
+                EntityKind::Npc(SpeciesId(42))
+            ));
+            assert_eq!(log.entities[0].skills[&SkillId(7)].hits.len(), 1);
+            assert!(matches!(log.status, Some(Status::Clear)));
+            assert!(log.deaths.is_empty());
+        }
+
+        #[test]
+        fn current_version_round_trips() {
+            let mut log = base();
+            log.entities[0].damage.push((Timestamp(1000), Damage(100)));
+            log.deaths.push(death());
+            log.stagger.push(stagger());
+            log.buff_categories.push(brand());
+            log.buffed_hits.push(buffed_hit());
+            log.buff_contributions.push(buff_contribution());
+            log.parties.push((EntityIndex(0), 4));
+            log.shields.push(shielding());
+            let log = read(VERSION, &log);
+            assert_eq!(log.parties(), BTreeMap::from([(10, 4)]));
+            assert_eq!(log.entities.len(), 2);
+            assert_eq!(log.deaths.len(), 1);
+
+            let (_, enc) = log.into_encounter();
+            assert_eq!(enc.buff_categories[0].name, "Brand");
+            let player = &enc.players[&10];
+            assert_eq!(player.skills[&7].buffed, [1]);
+            assert_eq!(player.skills[&7].hits[0].1.buffed, [true]);
+            assert_eq!(player.buffed_rate(0), 1.0);
+            assert_eq!(player.buff_contributions[&20], 10);
+            assert_eq!(player.buffed_by[&(0, 20)].damage, 100);
+            assert_eq!(player.skills[&7].hits[0].1.buffed_by, [(0, 20)]);
+            let stagger = &enc.stagger[&20];
+            assert_eq!(stagger.samples[0].fill(), 0.5);
+            assert_eq!(
+                stagger.checks[0].time_to_stagger(),
+                Some(std::time::Duration::from_secs(1))
+            );
+            assert_eq!(stagger.contribution[&10], 100);
+            let shielding = enc.shields[&(10, 20)];
+            assert_eq!(
+                (shielding.given, shielding.absorbed, shielding.expired),
+                (1000, 600, 400)
+            );
+        }
+
+        #[test]
+        fn newer_and_truncated_logs_are_rejected() {
+            let log = base();
+            let body = serde_bare::to_vec(&log).unwrap();
+            assert!(Log::from_versioned(VERSION + 1, &body).is_err());
+            assert!(Log::from_versioned(VERSION, &body[..body.len() / 2]).is_err());
+        }
+
+        #[test]
+        fn out_of_range_indices_are_invalid() {
+            let mut log = base();
+            log.targets.push(EntityIndex(2));
+            assert!(log.validate().is_err());
+
+            let mut log = base();
+            log.stagger.push(Stagger {
+                entity: EntityIndex(1),
+                samples: Vec::new(),
+                checks: Vec::new(),
+                contributions: vec![(EntityIndex(2), 100)],
+            });
+            assert!(log.validate().is_err());
+
+            let mut log = base();
+            log.buffed_hits.push(BuffedHit {
+                entity: EntityIndex(2),
+                ..buffed_hit()
+            });
+            assert!(log.validate().is_err());
+
+            let mut log = base();
+            log.buffed_hits.push(BuffedHit {
+                buffed_by: vec![(0, EntityIndex(2))],
+                ..buffed_hit()
+            });
+            assert!(log.validate().is_err());
+
+            // hits can't be dealt under categories the log doesn't have
+            let mut log = base();
+            log.buff_categories.push(brand());
+            log.buffed_hits.push(buffed_hit());
+            log.validate().unwrap();
+            log.buffed_hits.push(BuffedHit {
+                buffed: vec![false, true],
+                buffed_by: Vec::new(),
+                ..buffed_hit()
+            });
+            assert!(log.validate().is_err());
+
+            let mut log = base();
+            log.buff_categories.push(brand());
+            log.buffed_hits.push(BuffedHit {
+                buffed_by: vec![(1, EntityIndex(1))],
+                ..buffed_hit()
+            });
+            assert!(log.validate().is_err());
+
+            let mut log = base();
+            log.buff_contributions.push(BuffContribution {
+                applicant: EntityIndex(2),
+                ..buff_contribution()
+            });
+            assert!(log.validate().is_err());
+
+            let mut log = base();
+            log.parties.push((EntityIndex(2), 4));
+            assert!(log.validate().is_err());
+
+            let mut log = base();
+            log.shields.push(Shielding {
+                target: EntityIndex(2),
+                ..shielding()
+            });
+            assert!(log.validate().is_err());
+        }
+    }
+}
+
+/// The hit a damage event represents, excluding overkill damage. Events that don't count
+/// towards damage dealt produce `None`.
+fn hit_from_event(evt: &SkillDamageEvent) -> anyhow::Result<Option<(SkillHit, HitFlag)>> {
+    let overkill = evt.cur_hp.min(0).abs();
+    let damage = evt.damage.saturating_sub(overkill).max(0);
+    let flag = evt.flag()?;
+    if damage == 0 {
+        return Ok(None);
+    }
+    if flag == HitFlag::DamageShare
+    /* && skill_id == 0 */
+    {
+        println!("is this sidereal damage?");
+        return Ok(None);
+    }
+    let option = evt.option()?;
+    let hit = SkillHit {
+        damage,
+        target_id: evt.target_id,
+        is_crit: flag.is_crit(),
+        is_back_attack: matches!(option, HitOption::BackAttack),
+        is_front_attack: matches!(option, HitOption::FrontalAttack),
+        buffed: Vec::new(),
+        buffed_by: Vec::new(),
+    };
+    Ok(Some((hit, flag)))
+}
+
+/// Indices of the `categories` a status effect is in, given its entry in the status effect
+/// database and whether it comes from a skill of a support class.
+fn categories_of(
+    categories: &[BuffCategory],
+    id: u32,
+    info: Option<&StatusEffectInfo>,
+    from_support: bool,
+) -> Vec<usize> {
+    let in_category = |category: &BuffCategory| {
+        if category.ids.contains(&id) {
+            return true;
+        }
+        let Some(info) = info else { return false };
+        info.buff_category
+            .as_ref()
+            .is_some_and(|name| category.buff_categories.contains(name))
+            && category.kind.map_or(true, |kind| kind == info.category)
+            && (category.stats.is_empty()
+                || info
+                    .stats
+                    .iter()
+                    .any(|(stat, _)| category.stats.contains(stat)))
+            && (from_support || !category.supports_only)
+    };
+    categories
+        .iter()
+        .enumerate()
+        .filter(|(_, category)| in_category(category))
+        .map(|(idx, _)| idx)
+        .collect()
+}
+
+/// Size a shield was applied with. The packet only defines a status effect's `value` as raw
+/// bytes; for shields they lead with two little-endian u64s, the smaller of which is the shield's
+/// current size. This is the layout other Lost Ark meters (e.g. LOA Logs) read shields with.
+/// `None` if `raw` is too short to hold both.
+fn shield_value(raw: &[u8]) -> Option<u64> {
+    let first = u64::from_le_bytes(raw.get(0..8)?.try_into().ok()?);
+    let second = u64::from_le_bytes(raw.get(8..16)?.try_into().ok()?);
+    Some(first.min(second))
+}
+
+/// Count a hit towards each buff category it was dealt under.
+fn add_buffed<T: Default>(counts: &mut Vec<T>, buffed: &[bool], mut add: impl FnMut(&mut T)) {
+    if counts.len() < buffed.len() {
+        counts.resize_with(buffed.len(), T::default);
+    }
+    for (count, _) in counts.iter_mut().zip(buffed).filter(|(_, &buffed)| buffed) {
+        add(count);
+    }
+}
+
+/// Processes packets and updates [`Data`].
+pub struct Meter {
+    ui_ctx: egui::Context,
+    data: Arc<Mutex<Data>>,
+    skill_data: SkillData,
+    npc_data: NpcData,
+    status_effect_data: StatusEffectData,
+    boss_data: BossData,
+    zone_data: ZoneData,
+    buff_categories: Arc<[BuffCategory]>,
+    support_identity_skills: Vec<u32>,
+
+    #[cfg(feature = "packet_logging")]
+    log: Vec<u8>,
+}
+
+impl Meter {
+    pub fn new(ui_ctx: egui::Context, data: Arc<Mutex<Data>>) -> anyhow::Result<Self> {
+        let buff_definitions = load_buff_definitions()?;
+        let buff_categories: Arc<[BuffCategory]> = buff_definitions.categories.into();
+        data.lock()
+            .set_buff_categories(Arc::clone(&buff_categories));
+
+        Ok(Self {
+            ui_ctx,
+            data,
+            skill_data: SkillData::load()?,
+            // the NPC, status effect and zone databases aren't checked in, but generated by the
+            // updater
+            npc_data: NpcData::load().unwrap_or_else(|e| {
+                println!("failed to load npc data, npcs will be unnamed: {}", e);
+                NpcData::default()
+            }),
+            status_effect_data: StatusEffectData::load().unwrap_or_else(|e| {
+                println!(
+                    "failed to load status effect data, buffs won't be tracked: {}",
+                    e
+                );
+                StatusEffectData::default()
+            }),
+            boss_data: BossData::load()?,
+            zone_data: ZoneData::load().unwrap_or_else(|e| {
+                println!("failed to load zone data, zones will be shown by ID: {}", e);
+                ZoneData::default()
+            }),
+            buff_categories,
+            support_identity_skills: buff_definitions.support_identity_skills,
+
+            #[cfg(feature = "packet_logging")]
+            log: Vec::new(),
+        })
+    }
+
+    // process an incoming set of damage events
+    fn process_damages<'a>(
+        &mut self,
+        source_id: u64,
+        skill_id: u32,
+        events: impl Iterator<Item = &'a SkillDamageEvent>,
+    ) -> anyhow::Result<()> {
+        let timestamp = Instant::now();
+
+        let data = &mut *self.data.lock();
+        data.expire_status_effects(timestamp);
+        let mut id = source_id;
+        while let Some(p) = data.current_env().projectiles.get(&id) {
+            println!("projectile");
+            id = p.owner_id;
+        }
+
+        // anything that isn't a player is treated as an npc, whose damage isn't shown but is
+        // kept for logs, and as damage taken by players if it's an npc that was seen spawning
+        let env = data.current_env();
+        let known_npc = env.npcs.contains_key(&id);
+        let is_npc = known_npc
+            || !env.players.contains_key(&id) && self.skill_data.class_for(skill_id).is_none();
+        if is_npc {
+            let skill_name = self.skill_data.name(skill_id);
+            let enc = data.encounters.last_mut().unwrap();
+            let npc = enc.npcs.entry(id).or_default();
+            for evt in events {
+                let Some((hit, _)) = hit_from_event(evt)? else {
+                    continue;
+                };
+                if data.environments[enc.environment]
+                    .players
+                    .contains_key(&hit.target_id)
+                {
+                    if known_npc {
+                        let taken = enc.damage_taken.entry(hit.target_id).or_default();
+                        taken.add(skill_id, skill_name, hit.damage);
+                    }
+                    data.live.record_hit_taken(
+                        hit.target_id,
+                        RecapHit {
+                            time: timestamp,
+                            source_id: id,
+                            skill_id,
+                            skill: skill_name.map(ToOwned::to_owned),
+                            damage: hit.damage,
+                        },
+                    );
+                }
+                npc.add_hit(timestamp, skill_id, hit, || {
+                    skill_name.map(ToOwned::to_owned)
+                });
+            }
+            return Ok(());
+        }
+
+        // add dummy player to environment
+        if let Entry::Vacant(e) = data.current_env_mut().players.entry(id) {
+            let info = Player {
+                name: None,
+                class: match self.skill_data.class_for(skill_id) {
+                    Some(p) => p,
+                    None => return Ok(()),
+                },
+                ilvl: 0.0,
+                character_id: None,
+            };
+            println!("adding dummy player {}", id);
+            e.insert(info);
+            // println!("players: {:#?}", data.current_env().players);
+        }
+
+        let enc = {
+            let len = data.encounters.len();
+            &mut data.encounters[len - 1]
+        };
+
+        // dealing damage means they're alive
+        enc.revive(id, timestamp);
+
+        let player = enc.players.entry(id).or_insert_with(Default::default);
+        let party = data.live.parties.get(&id).copied();
+        let mut target_is_boss = false;
+
+        for evt in events {
+            let Some((mut hit, flag)) = hit_from_event(evt)? else {
+                continue;
+            };
+            let damage = hit.damage;
+            data.live.last_hits.insert(evt.target_id, (id, timestamp));
+            let players = &data.environments[enc.environment].players;
+            let is_player = |id| players.contains_key(&id);
+            let buffed = data
+                .live
+                .hit_categories(id, evt.target_id, party, is_player);
+            hit.buffed = buffed
+                .iter()
+                .map(|applicants| !applicants.is_empty())
+                .collect();
+            hit.buffed_by = buffed
+                .iter()
+                .enumerate()
+                .flat_map(|(idx, applicants)| applicants.iter().map(move |&id| (idx, id)))
+                .collect();
+            let contributions =
+                data.live
+                    .buff_contributions(id, evt.target_id, party, damage, is_player);
+            for (applicant, damage) in contributions {
+                *player.buff_contributions.entry(applicant).or_default() += damage;
+            }
+            player.add_hit(timestamp, skill_id, hit, || {
+                match self.skill_data.name(skill_id) {
+                    None if skill_id == 0 && flag.is_dot() => Some("Bleed"),
+                    rest => rest,
+                }
+                .map(ToOwned::to_owned)
+            });
+
+            for (id, tracked) in &enc.tracked {
+                let id = *id;
+                if id == evt.target_id {
+                    target_is_boss = true;
+                    data.live.recently_tracked = Some(id);
+                    data.live.tracked.insert(
+                        id,
+                        BossInfo {
+                            max_hp: evt.max_hp,
+                            cur_hp: evt.cur_hp,
+                            bar_count: data.environments[enc.environment]
+                                .npcs
+                                .get(&id)
+                                .and_then(|npc| npc.bars)
+                                .or(tracked.max_bars()),
+                        },
+                    );
+                }
+            }
+        }
+
+        let encounter = data.current_enc_mut();
         if encounter.tracked.is_empty() || target_is_boss {
             if encounter.first_damage.is_none() {
                 println!("first damage set");
@@ -369,6 +1794,82 @@ impl Meter {
         Ok(())
     }
 
+    fn buff_info(&self, raw: &crate::packet::StatusEffectData, now: Instant) -> BuffInfo {
+        BuffInfo {
+            status_effect_id: raw.status_effect_id,
+            stacks: raw.stack_count,
+            applicant: raw.source_id,
+            instance_id: raw.effect_instance_id,
+            scope: StatusEffectScope::Target,
+            // effects without a duration last until removed
+            expires: Duration::try_from_secs_f32(raw.total_time)
+                .ok()
+                .filter(|duration| !duration.is_zero())
+                .and_then(|duration| now.checked_add(duration)),
+            name: self
+                .status_effect_data
+                .get(raw.status_effect_id)
+                .map(|info| info.name.clone()),
+            categories: self.buff_categories_of(raw.status_effect_id),
+            bonuses: self.damage_bonuses(raw.status_effect_id),
+            shield: self
+                .status_effect_data
+                .get(raw.status_effect_id)
+                .is_some_and(|info| info.shield),
+        }
+    }
+
+    /// Registry entry of a passive or zone-wide status effect, which has no instance or
+    /// duration of its own.
+    fn passive_info(&self, id: u32, applicant: u64, scope: StatusEffectScope) -> BuffInfo {
+        BuffInfo {
+            status_effect_id: id,
+            stacks: 1,
+            applicant,
+            instance_id: 0,
+            scope,
+            expires: None,
+            name: self
+                .status_effect_data
+                .get(id)
+                .map(|info| info.name.clone()),
+            categories: self.buff_categories_of(id),
+            bonuses: self.damage_bonuses(id),
+            shield: false,
+        }
+    }
+
+    /// Damage increases a status effect grants per stack, as fractions.
+    fn damage_bonuses(&self, id: u32) -> Vec<(DamageStat, f64)> {
+        let Some(info) = self.status_effect_data.get(id) else {
+            return Vec::new();
+        };
+        info.stats
+            .iter()
+            .filter_map(|(name, value)| {
+                Some((DamageStat::from_name(name)?, *value as f64 / 10000.0))
+            })
+            .collect()
+    }
+
+    /// Whether a status effect is applied by using a battle item.
+    fn is_battle_item(&self, id: u32) -> bool {
+        self.status_effect_data
+            .get(id)
+            .and_then(|info| info.buff_category.as_deref())
+            == Some("battleitem")
+    }
+
+    /// Indices of the buff categories a status effect is in.
+    fn buff_categories_of(&self, id: u32) -> Vec<usize> {
+        let info = self.status_effect_data.get(id);
+        let from_support = info
+            .and_then(|info| info.source_skill)
+            .and_then(|skill| self.skill_data.class_for(skill))
+            .is_some_and(|class| class.is_support());
+        categories_of(&self.buff_categories, id, info, from_support)
+    }
+
     // defer starting a new encounter for a few seconds as some final events may be missed
     // if swapping to new encounter immediately
     fn defer_new_encounter(&self) {
@@ -413,6 +1914,20 @@ impl PacketHandler for Meter {
         Ok(())
     }
 
+    fn on_new_npc_summon(&mut self, pkt: PktNewNpcSummon) -> anyhow::Result<()> {
+        // summons deal damage on behalf of their owner, the same as projectiles
+        let id = pkt.npc_data.object_id;
+        let summon = Projectile {
+            id,
+            owner_id: pkt.owner_id,
+        };
+        self.data
+            .lock()
+            .current_env_mut()
+            .add_projectile(id, summon);
+        Ok(())
+    }
+
     fn on_new_projectile(&mut self, pkt: PktNewProjectile) -> anyhow::Result<()> {
         let id = pkt.projectile_info.projectile_id;
         let projectile = Projectile::from_raw(pkt.projectile_info);
@@ -445,6 +1960,9 @@ impl PacketHandler for Meter {
 
     fn on_raid_boss_kill_notify(&mut self, pkt: PktRaidBossKillNotify) -> anyhow::Result<()> {
         println!("raid boss kill notify");
+        if let Some(run) = self.data.lock().current_raid_mut() {
+            run.kills.push(Instant::now());
+        }
         self.defer_new_encounter();
         Ok(())
     }
@@ -467,11 +1985,68 @@ impl PacketHandler for Meter {
     }
 
     fn on_raid_result(&mut self, pkt: PktRaidResult) -> anyhow::Result<()> {
-        println!("raid result");
+        println!("raid result: {}", pkt.raid_result);
+        if let Some(run) = self.data.lock().current_raid_mut() {
+            run.result = Some(pkt.raid_result);
+            run.end = Some(Instant::now());
+        }
         self.defer_new_encounter();
         Ok(())
     }
 
+    fn on_zone_member_load_status_notify(
+        &mut self,
+        pkt: PktZoneMemberLoadStatusNotify,
+    ) -> anyhow::Result<()> {
+        let zone = Zone {
+            id: pkt.zone_id,
+            name: self.zone_data.get(pkt.zone_id).map(str::to_owned),
+            difficulty: Difficulty::from_level(pkt.zone_level),
+        };
+        println!("zone: {} ({})", zone.id, zone.difficulty.name());
+        let mut data = self.data.lock();
+        data.current_enc_mut().zone = Some(zone.clone());
+        data.live.zone = Some(zone);
+        Ok(())
+    }
+
+    fn on_raid_begin(&mut self, pkt: PktRaidBegin) -> anyhow::Result<()> {
+        println!("raid begin: {}", pkt.raid_id);
+        let mut data = self.data.lock();
+        data.current_enc_mut().raid_id = Some(pkt.raid_id);
+        data.live.raid_id = Some(pkt.raid_id);
+
+        // re-entering a raid after a gate or disconnect resends this packet for the same run
+        let resumed = data
+            .raids
+            .last()
+            .is_some_and(|run| run.raid_id == pkt.raid_id && run.start_tick == pkt.start_tick);
+        if !resumed {
+            data.raids.push(RaidRun {
+                raid_id: pkt.raid_id,
+                start_tick: pkt.start_tick,
+                start: Instant::now(),
+                ..Default::default()
+            });
+        }
+
+        let enc_idx = data.encounters.len() - 1;
+        let run = data.raids.last_mut().unwrap();
+        run.end_tick = (pkt.end_tick != 0).then_some(pkt.end_tick);
+        run.total_time = Duration::from_millis(pkt.total_time);
+        run.brave_hearts = pkt.brave_heart_count;
+        run.init_brave_hearts = pkt.init_brave_heart_count;
+        run.bosses = pkt
+            .boss_kill_data_list
+            .iter()
+            .map(|boss| (boss.npc_id, boss.is_dead))
+            .collect();
+        if !run.encounters.contains(&enc_idx) {
+            run.encounters.push(enc_idx);
+        }
+        Ok(())
+    }
+
     fn on_init_pc(&mut self, pkt: PktInitPc) -> anyhow::Result<()> {
         println!("init pc");
         let mut data = self.data.lock();
@@ -503,18 +2078,25 @@ impl PacketHandler for Meter {
     }
 
     fn on_new_npc(&mut self, pkt: PktNewNpc) -> anyhow::Result<()> {
+        let kind = pkt.npc_struct.type_id;
+        let info = self.npc_data.get(kind);
         let npc = Npc {
             id: pkt.npc_struct.object_id,
-            kind: pkt.npc_struct.type_id,
-            name: "Boss".to_owned(),
+            kind,
+            name: info.map(|info| info.name.clone()),
+            grade: info.map(|info| info.grade).unwrap_or_default(),
+            bars: info.and_then(|info| info.hp_bars),
         };
         let mut data = self.data.lock();
-        if let Some(boss) = crate::definitions::Boss::from_id(npc.kind) {
-            println!("boss found: {}", npc.kind);
-            if data.current_enc().tracked.is_empty() {
+        if let Some(boss) = self.boss_data.get(npc.kind) {
+            println!("boss found: {} ({})", npc.kind, boss.name());
+            let untracked = data.current_enc().tracked.is_empty();
+            if untracked && boss.npc.role == BossRole::Start {
                 data.new_encounter();
             }
-            data.current_enc_mut().tracked.push((npc.id, boss));
+            if !untracked || boss.npc.role == BossRole::Start {
+                data.current_enc_mut().tracked.push((npc.id, boss));
+            }
         }
         data.current_env_mut().add_npc(npc.id, npc);
         Ok(())
@@ -541,6 +2123,40 @@ impl PacketHandler for Meter {
         &mut self,
         pkt: PktParalyzationStateNotify,
     ) -> anyhow::Result<()> {
+        let mut data = self.data.lock();
+        let now = Instant::now();
+        let hitter = data.live.recent_hitter(pkt.object_id, now);
+        let sample = StaggerSample {
+            time: now,
+            point: pkt.paralyzation_point,
+            max_point: pkt.paralyzation_max_point,
+        };
+        data.current_enc_mut().record_stagger(
+            pkt.object_id,
+            StaggerGauge::Paralyzation,
+            sample,
+            pkt.enable,
+            hitter,
+        );
+        Ok(())
+    }
+
+    fn on_block_skill_state_notify(&mut self, pkt: PktBlockSkillStateNotify) -> anyhow::Result<()> {
+        let mut data = self.data.lock();
+        let now = Instant::now();
+        let hitter = data.live.recent_hitter(pkt.object_id, now);
+        let sample = StaggerSample {
+            time: now,
+            point: pkt.paralyzation_point,
+            max_point: pkt.paralyzation_max_point,
+        };
+        data.current_enc_mut().record_stagger(
+            pkt.object_id,
+            StaggerGauge::BlockSkill,
+            sample,
+            pkt.paralyzation_max_point > 0,
+            hitter,
+        );
         Ok(())
     }
 
@@ -548,48 +2164,24 @@ impl PacketHandler for Meter {
         &mut self,
         pkt: crate::packet::PktStatusEffectAddNotify,
     ) -> anyhow::Result<()> {
-        // Bard
-        // Note Brand (3s: Sound Shock, Harp, Stigma, Note Bundle) 210230
-        // Note Brand (4s: Rhapsody) 212610
-        // Note Brand (5s: Sonatina) 212906
-        // Heavenly Tune AP 211601
-        // Sonic Vibration AP 211749
-        // Serenade of Courage, 1 bar (party) 211400
-        // Serenade of Courage, 2 bar (party) 211410
-        // Serenade of Courage, 3 bar (party) 211420
-
-        // Paladin
-        // Light's Vestige (6s: Light Shock) 360506
-        // Light's Vestige (10s: Sword of Justice) 360804
-        // Light's Vestige (12s: Holy Explosion) 361004
-        // Light's Vestige (12s: Godsent Law) 361505
-        // Wrath of God AP 361708
-        // Heavenly Blessings AP 362000
-        // Blessed Aura 500150
-
-        // Artist
-        // Ink Brand (i12s: Paint: Drawing Orchids) 314260
-        // Paint: Sunsketch AP 314004
-        // Paint: Sun Well AP 314181
-        // Moonfall 310501
-
         // println!(
         //     "status effect add: {} ({})",
         //     pkt.status_effect_data.status_effect_id, pkt.status_effect_data.effect_instance_id
         // );
 
+        let now = Instant::now();
+        let info = self.buff_info(&pkt.status_effect_data, now);
         let mut data = self.data.lock();
-        data.live.buffs.entry(pkt.object_id).or_default().insert(
-            pkt.status_effect_data.status_effect_id,
-            BuffInfo {
-                stacks: pkt.status_effect_data.stack_count,
-                applicant: pkt.status_effect_data.source_id,
-            },
-        );
-        data.live.instance_id_lookup.insert(
-            pkt.status_effect_data.effect_instance_id,
-            pkt.status_effect_data.status_effect_id,
-        );
+        data.expire_status_effects(now);
+        if self.is_battle_item(pkt.status_effect_data.status_effect_id) {
+            data.record_battle_item(
+                pkt.status_effect_data.source_id,
+                pkt.status_effect_data.status_effect_id,
+                info.name.clone(),
+                now,
+            );
+        }
+        data.add_status_effect(pkt.object_id, &pkt.status_effect_data, info, now);
 
         Ok(())
     }
@@ -599,17 +2191,59 @@ impl PacketHandler for Meter {
         pkt: crate::packet::PktStatusEffectRemoveNotify,
     ) -> anyhow::Result<()> {
         let mut data = self.data.lock();
-        for instance_id in &pkt.status_effect_ids {
-            if let Some(effect_id) = data.live.instance_id_lookup.get(instance_id).copied() {
-                if let Some(buff_map) = data.live.buffs.get_mut(&pkt.object_id) {
-                    buff_map.remove(&effect_id);
-                }
-            }
-            data.live.instance_id_lookup.remove(instance_id);
+        let now = Instant::now();
+        for &instance_id in &pkt.status_effect_ids {
+            data.remove_status_effect(pkt.object_id, instance_id, now);
         }
         Ok(())
     }
 
+    fn on_status_effect_duration_notify(
+        &mut self,
+        pkt: PktStatusEffectDurationNotify,
+    ) -> anyhow::Result<()> {
+        let mut data = self.data.lock();
+        let now = Instant::now();
+        data.current_enc_mut().refresh_status_effect(
+            pkt.target_id,
+            pkt.effect_instance_id,
+            pkt.expiration_tick,
+            now,
+        );
+        data.live
+            .refresh_buff(pkt.target_id, pkt.effect_instance_id, pkt.expiration_tick);
+        Ok(())
+    }
+
+    fn on_status_effect_sync_data_notify(
+        &mut self,
+        pkt: PktStatusEffectSyncDataNotify,
+    ) -> anyhow::Result<()> {
+        self.data
+            .lock()
+            .sync_shield(pkt.object_id, pkt.effect_instance_id, pkt.value as u64);
+        Ok(())
+    }
+
+    fn on_remove_object(&mut self, pkt: PktRemoveObject) -> anyhow::Result<()> {
+        let mut data = self.data.lock();
+        let now = Instant::now();
+        for object in &pkt.unpublished_objects {
+            data.clear_status_effects(object.object_id, now);
+        }
+        Ok(())
+    }
+
+    fn on_zone_object_unpublish_notify(
+        &mut self,
+        pkt: PktZoneObjectUnpublishNotify,
+    ) -> anyhow::Result<()> {
+        self.data
+            .lock()
+            .clear_status_effects(pkt.object_id, Instant::now());
+        Ok(())
+    }
+
     fn on_party_status_effect_add_notify(
         &mut self,
         pkt: crate::packet::PktPartyStatusEffectAddNotify,
@@ -620,18 +2254,19 @@ impl PacketHandler for Meter {
                 .is_some_and(|pid| pid == pkt.character_id && id != pid)
                 .then_some(id)
         }) {
-            let entry = data.live.buffs.entry(object_id).or_default();
+            let now = Instant::now();
+            data.expire_status_effects(now);
             for eff in &pkt.status_effect_datas {
-                entry.insert(
-                    eff.status_effect_id,
-                    BuffInfo {
-                        stacks: eff.stack_count,
-                        applicant: eff.source_id,
-                    },
-                );
-                data.live
-                    .instance_id_lookup
-                    .insert(eff.effect_instance_id, eff.status_effect_id);
+                let info = self.buff_info(eff, now);
+                if self.is_battle_item(eff.status_effect_id) {
+                    data.record_battle_item(
+                        eff.source_id,
+                        eff.status_effect_id,
+                        info.name.clone(),
+                        now,
+                    );
+                }
+                data.add_status_effect(object_id, eff, info, now);
             }
         }
 
@@ -648,18 +2283,98 @@ impl PacketHandler for Meter {
                 .is_some_and(|pid| pid == pkt.character_id && id != pid)
                 .then_some(id)
         }) {
-            for instance_id in &pkt.status_effect_ids {
-                if let Some(effect_id) = data.live.instance_id_lookup.get(instance_id).copied() {
-                    if let Some(buff_map) = data.live.buffs.get_mut(&object_id) {
-                        buff_map.remove(&effect_id);
-                    }
-                }
-                data.live.instance_id_lookup.remove(instance_id);
+            let now = Instant::now();
+            for &instance_id in &pkt.status_effect_ids {
+                data.remove_status_effect(object_id, instance_id, now);
             }
         }
         Ok(())
     }
 
+    fn on_passive_status_effect_add_notify(
+        &mut self,
+        pkt: PktPassiveStatusEffectAddNotify,
+    ) -> anyhow::Result<()> {
+        let mut data = self.data.lock();
+        // passives are only sent for the local player
+        let Some(pov) = data.current_env().pov else {
+            return Ok(());
+        };
+        let now = Instant::now();
+        for &id in &pkt.passive_status_effect_list {
+            let info = self.passive_info(id, pov, StatusEffectScope::Passive);
+            data.add_passive_status_effect(&[pov], id, info, now);
+        }
+        Ok(())
+    }
+
+    fn on_passive_status_effect_remove_notify(
+        &mut self,
+        pkt: PktPassiveStatusEffectRemoveNotify,
+    ) -> anyhow::Result<()> {
+        let mut data = self.data.lock();
+        let Some(pov) = data.current_env().pov else {
+            return Ok(());
+        };
+        let now = Instant::now();
+        for &id in &pkt.passive_status_effect_list {
+            data.remove_passive_status_effect(id, pov, StatusEffectScope::Passive, now);
+        }
+        Ok(())
+    }
+
+    fn on_party_passive_status_effect_add_notify(
+        &mut self,
+        pkt: PktPartyPassiveStatusEffectAddNotify,
+    ) -> anyhow::Result<()> {
+        let mut data = self.data.lock();
+        let members = data.live.party_members(pkt.object_id);
+        let now = Instant::now();
+        for &id in &pkt.passive_status_effect_list {
+            let info = self.passive_info(id, pkt.object_id, StatusEffectScope::Party);
+            data.add_passive_status_effect(&members, id, info, now);
+        }
+        Ok(())
+    }
+
+    fn on_party_passive_status_effect_remove_notify(
+        &mut self,
+        pkt: PktPartyPassiveStatusEffectRemoveNotify,
+    ) -> anyhow::Result<()> {
+        let mut data = self.data.lock();
+        let now = Instant::now();
+        for &id in &pkt.passive_status_effect_list {
+            data.remove_passive_status_effect(id, pkt.object_id, StatusEffectScope::Party, now);
+        }
+        Ok(())
+    }
+
+    fn on_zone_status_effect_add_notify(
+        &mut self,
+        pkt: PktZoneStatusEffectAddNotify,
+    ) -> anyhow::Result<()> {
+        let mut data = self.data.lock();
+        let now = Instant::now();
+        for effect in &pkt.zone_status_effect_data_list {
+            let scope = StatusEffectScope::Zone(ZoneTarget::from_raw(effect.target));
+            let mut info = self.passive_info(effect.id, 0, scope);
+            info.stacks = effect.stack_count;
+            info.instance_id = effect.instance_id;
+            data.add_zone_status_effect(info, now);
+        }
+        Ok(())
+    }
+
+    fn on_zone_status_effect_remove_notify(
+        &mut self,
+        pkt: PktZoneStatusEffectRemoveNotify,
+    ) -> anyhow::Result<()> {
+        self.data
+            .lock()
+            .remove_zone_status_effect(pkt.status_effect_id, Instant::now());
+        Ok(())
+    }
+
     fn on_party_status_effect_result_notify(
         &mut self,
         pkt: crate::packet::PktPartyStatusEffectResultNotify,
@@ -732,6 +2447,117 @@ impl PacketHandler for Meter {
         Ok(())
     }
 
+    fn on_identity_gauge_change_notify(
+        &mut self,
+        pkt: PktIdentityGaugeChangeNotify,
+    ) -> anyhow::Result<()> {
+        let mut data = self.data.lock();
+        if !data.current_env().players.contains_key(&pkt.player_id) {
+            return Ok(());
+        }
+        let gauge = [
+            pkt.identity_gauge1,
+            pkt.identity_gauge2,
+            pkt.identity_gauge3,
+        ];
+        data.current_enc_mut()
+            .identity
+            .entry(pkt.player_id)
+            .or_default()
+            .gauge
+            .push((Instant::now(), gauge));
+        Ok(())
+    }
+
+    fn on_identity_stance_change_notify(
+        &mut self,
+        pkt: PktIdentityStanceChangeNotify,
+    ) -> anyhow::Result<()> {
+        let mut data = self.data.lock();
+        if !data.current_env().players.contains_key(&pkt.object_id) {
+            return Ok(());
+        }
+        data.current_enc_mut()
+            .identity
+            .entry(pkt.object_id)
+            .or_default()
+            .stances
+            .push((Instant::now(), pkt.stance));
+        Ok(())
+    }
+
+    fn on_skill_start_notify(&mut self, pkt: PktSkillStartNotify) -> anyhow::Result<()> {
+        if !self.support_identity_skills.contains(&pkt.skill_id) {
+            return Ok(());
+        }
+        let mut data = self.data.lock();
+        if !data.current_env().players.contains_key(&pkt.source_id) {
+            return Ok(());
+        }
+        data.current_enc_mut()
+            .identity
+            .entry(pkt.source_id)
+            .or_default()
+            .ultimates
+            .push((Instant::now(), pkt.skill_id));
+        Ok(())
+    }
+
+    fn on_counter_attack_notify(&mut self, pkt: PktCounterAttackNotify) -> anyhow::Result<()> {
+        let mut data = self.data.lock();
+        if !data.current_env().players.contains_key(&pkt.source_id) {
+            return Ok(());
+        }
+        let counter = Counter {
+            time: Instant::now(),
+            target_id: pkt.target_id,
+            kind: pkt.r#type,
+        };
+        data.current_enc_mut()
+            .counters
+            .entry(pkt.source_id)
+            .or_default()
+            .push(counter);
+        Ok(())
+    }
+
+    fn on_death_notify(&mut self, pkt: PktDeathNotify) -> anyhow::Result<()> {
+        let mut data = self.data.lock();
+        if !data.current_env().players.contains_key(&pkt.target_id) {
+            return Ok(());
+        }
+        println!("player died: {}", pkt.target_id);
+        let recap = data.live.take_hits_taken(pkt.target_id);
+        data.current_enc_mut().deaths.push(Death {
+            player_id: pkt.target_id,
+            time: Instant::now(),
+            source_id: pkt.source_id,
+            revived: None,
+            recap,
+        });
+        Ok(())
+    }
+
+    fn on_troop_member_update_min_notify(
+        &mut self,
+        pkt: PktTroopMemberUpdateMinNotify,
+    ) -> anyhow::Result<()> {
+        if pkt.cur_hp <= 0 {
+            return Ok(());
+        }
+        let mut data = self.data.lock();
+        let Some((&id, _)) = data
+            .current_env()
+            .players
+            .iter()
+            .find(|(_, p)| p.character_id == Some(pkt.character_id))
+        else {
+            return Ok(());
+        };
+        data.current_enc_mut().revive(id, Instant::now());
+        Ok(())
+    }
+
     fn on_migration_execute(
         &mut self,
         pkt: crate::packet::PktMigrationExecute,
@@ -852,14 +2678,36 @@ pub struct LiveData {
     pub tracked: BTreeMap<u64, BossInfo>,
     pub recently_tracked: Option<u64>,
     pub parties: BTreeMap<u64, u32>,
+    /// Status effects on each entity, by instance ID. The same status effect can be on an
+    /// entity more than once when applied by different players.
     pub buffs: BTreeMap<u64, BTreeMap<u32, BuffInfo>>,
-    pub instance_id_lookup: BTreeMap<u32, u32>,
+    /// Zone the player is currently in, if known.
+    pub zone: Option<Zone>,
+    /// ID of the raid currently in progress, if any.
+    pub raid_id: Option<u32>,
+    /// Most recent hits taken by each player, oldest first.
+    pub hits_taken: BTreeMap<u64, std::collections::VecDeque<RecapHit>>,
+    /// Maps entity ID to the player who last hit it, and when.
+    pub last_hits: BTreeMap<u64, (u64, Instant)>,
+    /// A server tick and the time it was current, for converting ticks to times.
+    pub tick_anchor: Option<(u64, Instant)>,
+    /// Passive status effects on each entity, by scope and status effect ID. Kept apart from
+    /// `buffs` since passives have no instances of their own.
+    pub passive_buffs: BTreeMap<u64, BTreeMap<(StatusEffectScope, u32), BuffInfo>>,
+    /// Status effects applied to the whole zone, by status effect ID.
+    pub zone_buffs: BTreeMap<u32, BuffInfo>,
+    /// Shields currently up, by status effect instance ID.
+    pub shields: BTreeMap<u32, Shield>,
+    /// Categories of buffs that damage dealt under is tracked for.
+    pub buff_categories: Arc<[BuffCategory]>,
 }
 
 impl LiveData {
     fn clear_encounter_data(&mut self) {
         self.tracked.clear();
         self.recently_tracked = None;
+        self.hits_taken.clear();
+        self.last_hits.clear();
     }
 
     fn clear_all(&mut self) {
@@ -867,67 +2715,306 @@ impl LiveData {
         self.recently_tracked = None;
         self.parties.clear();
         self.buffs.clear();
-        self.instance_id_lookup.clear();
+        self.passive_buffs.clear();
+        self.zone_buffs.clear();
+        self.shields.clear();
+        // a new environment isn't necessarily followed by its zone being announced again, so
+        // the zone is kept until it is
+        self.raid_id = None;
+        self.hits_taken.clear();
+        self.last_hits.clear();
+        self.tick_anchor = None;
     }
 
-    fn player_has_ap_buff(&self, player_id: u64) -> bool {
-        if let Some(buffs) = self.buffs.get(&player_id) {
-            if buffs.contains_key(&211601) // bard
-                || buffs.contains_key(&211749)
-                || buffs.contains_key(&361708) // paladin
-                || buffs.contains_key(&362000)
-                || buffs.contains_key(&314004) // artist
-                || buffs.contains_key(&314181)
-            {
-                return true;
-            }
+    fn add_buff(
+        &mut self,
+        target_id: u64,
+        raw: &crate::packet::StatusEffectData,
+        info: BuffInfo,
+        now: Instant,
+    ) {
+        // an effect's end tick minus its duration is the tick it was applied at
+        let total_ms = (raw.total_time * 1000.0) as u64;
+        if total_ms > 0 && raw.end_tick > total_ms {
+            self.tick_anchor = Some((raw.end_tick - total_ms, now));
         }
-        false
+        self.buffs
+            .entry(target_id)
+            .or_default()
+            .insert(raw.effect_instance_id, info);
     }
 
-    fn player_has_ident_buff(&self, player_id: u64) -> bool {
-        if let Some(buffs) = self.buffs.get(&player_id) {
-            if buffs.contains_key(&211400) // bard
-                || buffs.contains_key(&211410)
-                || buffs.contains_key(&211420)
-                // || buffs.contains_key(&500128)
-                // || buffs.contains_key(&500146)
-                || buffs.contains_key(&500153) // paladin
-                || buffs.contains_key(&310501)
-            {
-                return true;
-            }
+    /// Move a buff's expiration to a new server tick.
+    fn refresh_buff(&mut self, target_id: u64, instance_id: u32, end_tick: u64) {
+        let expires = self.tick_to_instant(end_tick);
+        let info = self
+            .buffs
+            .get_mut(&target_id)
+            .and_then(|buffs| buffs.get_mut(&instance_id));
+        if let (Some(info), Some(expires)) = (info, expires) {
+            info.expires = Some(expires);
+        }
+    }
+
+    fn tick_to_instant(&self, tick: u64) -> Option<Instant> {
+        let (anchor_tick, anchor) = self.tick_anchor?;
+        match tick.checked_sub(anchor_tick) {
+            Some(ms) => anchor.checked_add(Duration::from_millis(ms)),
+            None => anchor.checked_sub(Duration::from_millis(anchor_tick - tick)),
+        }
+    }
+
+    /// Remove buffs that should have expired by `now`, returning their targets, instance IDs
+    /// and expiration times.
+    fn take_expired_buffs(&mut self, now: Instant) -> Vec<(u64, u32, Instant)> {
+        let mut expired = Vec::new();
+        for (&target_id, buffs) in &mut self.buffs {
+            buffs.retain(|_, info| match info.expires {
+                Some(expires) if expires + BUFF_EXPIRY_GRACE <= now => {
+                    expired.push((target_id, info.instance_id, expires));
+                    false
+                }
+                _ => true,
+            });
+        }
+        self.buffs.retain(|_, buffs| !buffs.is_empty());
+        expired
+    }
+
+    /// Remove every buff on an entity.
+    fn remove_buffs(&mut self, target_id: u64) {
+        self.passive_buffs.remove(&target_id);
+        self.buffs.remove(&target_id);
+    }
+
+    /// The player who hit `target_id` just before `time`, if any.
+    fn recent_hitter(&self, target_id: u64, time: Instant) -> Option<u64> {
+        let &(player_id, hit_time) = self.last_hits.get(&target_id)?;
+        (time.saturating_duration_since(hit_time) <= STAGGER_HIT_WINDOW).then_some(player_id)
+    }
+
+    fn record_hit_taken(&mut self, player_id: u64, hit: RecapHit) {
+        let hits = self.hits_taken.entry(player_id).or_default();
+        if hits.len() == DEATH_RECAP_LEN {
+            hits.pop_front();
+        }
+        hits.push_back(hit);
+    }
+
+    fn take_hits_taken(&mut self, player_id: u64) -> Vec<RecapHit> {
+        self.hits_taken
+            .remove(&player_id)
+            .map(Vec::from)
+            .unwrap_or_default()
+    }
+
+    /// Players in the same party as `player_id`, including them. Players not known to be in a
+    /// party only have themselves.
+    fn party_members(&self, player_id: u64) -> Vec<u64> {
+        match self.parties.get(&player_id) {
+            Some(party) => self
+                .parties
+                .iter()
+                .filter(|(_, p)| *p == party)
+                .map(|(&id, _)| id)
+                .collect(),
+            None => vec![player_id],
         }
-        false
     }
 
-    fn target_has_brand(&self, source_id: u64, target_id: u64, party: Option<u32>) -> bool {
+    /// Status effects on an entity, including passive ones and the zone-wide ones applying to
+    /// players or NPCs, whichever it is.
+    fn active_buffs(&self, target_id: u64, is_player: bool) -> impl Iterator<Item = &BuffInfo> {
+        self.buffs
+            .get(&target_id)
+            .into_iter()
+            .flat_map(BTreeMap::values)
+            .chain(
+                self.passive_buffs
+                    .get(&target_id)
+                    .into_iter()
+                    .flat_map(BTreeMap::values),
+            )
+            .chain(
+                self.zone_buffs
+                    .values()
+                    .filter(move |info| info.scope.applies_to(is_player)),
+            )
+    }
+
+    /// Split the damage of a hit from `source_id` on `target_id` into the parts added by each
+    /// player's buffs on the attacker and debuffs on the target, by the buff's applicant.
+    ///
+    /// Within a [`DamageStat`] group bonuses add up, so a buff with bonus `b` in a group totaling
+    /// `B` is credited `damage * b / (1 + B)`. Groups multiply, so crediting each of them like
+    /// that would count what they add on top of each other more than once. Credits are scaled
+    /// down to add up to the damage all bonuses added together, `damage * (1 - 1 / Π(1 + B))`.
+    /// The attacker's own buffs and those of non-players only count towards the totals.
+    fn buff_contributions(
+        &self,
+        source_id: u64,
+        target_id: u64,
+        party: Option<u32>,
+        damage: i64,
+        is_player: impl Fn(u64) -> bool,
+    ) -> Vec<(u64, i64)> {
         let parties = &self.parties;
-        // let Some(party) = party else { return false };
-        if let Some(buffs) = self.buffs.get(&target_id) {
-            for (id, info) in buffs.iter().take_while(|&(&id, _)| id <= 361505) {
-                if matches!(
-                    id,
-                    210230 | 212610 | 212906 | // bard
-                    360506 | 360804 | 361004 | 361505 | // paladin
-                    314260 // artist
-                ) {
-                    if party.is_some() && parties.get(&info.applicant).copied() == party
-                        || info.applicant == source_id
-                    {
-                        return true;
-                    }
+        let from_party = |info: &&BuffInfo| {
+            party.is_some() && parties.get(&info.applicant).copied() == party
+                || info.applicant == source_id
+        };
+        let bonuses: Vec<_> = self
+            .active_buffs(source_id, is_player(source_id))
+            .chain(
+                self.active_buffs(target_id, is_player(target_id))
+                    .filter(from_party),
+            )
+            .flat_map(|info| {
+                info.bonuses
+                    .iter()
+                    .map(|&(stat, rate)| (info.applicant, stat, rate * info.stacks.max(1) as f64))
+            })
+            .filter(|&(_, _, rate)| rate > 0.0)
+            .collect();
+
+        if bonuses.is_empty() {
+            return Vec::new();
+        }
+
+        let mut totals = BTreeMap::<DamageStat, f64>::new();
+        for &(_, stat, rate) in &bonuses {
+            *totals.entry(stat).or_default() += rate;
+        }
+        let added = 1.0
+            - totals
+                .values()
+                .map(|total| 1.0 / (1.0 + total))
+                .product::<f64>();
+        let credited: f64 = totals.values().map(|total| total / (1.0 + total)).sum();
+        let scale = added / credited;
+        let mut contributions = BTreeMap::<u64, f64>::new();
+        for (applicant, stat, rate) in bonuses {
+            if applicant != source_id && is_player(applicant) {
+                *contributions.entry(applicant).or_default() +=
+                    damage as f64 * rate / (1.0 + totals[&stat]) * scale;
+            }
+        }
+        contributions
+            .into_iter()
+            .map(|(applicant, damage)| (applicant, damage.round() as i64))
+            .collect()
+    }
+
+    /// Applicants of the buffs of each category that a hit from `source_id` on `target_id` was
+    /// dealt under, indexed like [`LiveData::buff_categories`].
+    fn hit_categories(
+        &self,
+        source_id: u64,
+        target_id: u64,
+        party: Option<u32>,
+        is_player: impl Fn(u64) -> bool,
+    ) -> Vec<Vec<u64>> {
+        let mut active = vec![Vec::new(); self.buff_categories.len()];
+        let mut mark = |info: &BuffInfo, holder: BuffHolder| {
+            for &idx in &info.categories {
+                let applicants = &mut active[idx];
+                if self.buff_categories[idx].holder == holder
+                    && !applicants.contains(&info.applicant)
+                {
+                    applicants.push(info.applicant);
                 }
             }
+        };
+        for info in self.active_buffs(source_id, is_player(source_id)) {
+            mark(info, BuffHolder::Attacker);
+        }
+        // debuffs only count for the party that applied them
+        let parties = &self.parties;
+        for info in self.active_buffs(target_id, is_player(target_id)) {
+            if party.is_some() && parties.get(&info.applicant).copied() == party
+                || info.applicant == source_id
+            {
+                mark(info, BuffHolder::Target);
+            }
         }
-        false
+        active
     }
 }
 
-#[derive(Debug)]
+/// A shield status effect that is up.
+#[derive(Debug, Copy, Clone)]
+pub struct Shield {
+    pub applicant: u64,
+    pub target_id: u64,
+    /// Shield value left.
+    pub remaining: u64,
+}
+
+/// How long past its expiration a buff is kept, to allow for refreshes arriving late.
+const BUFF_EXPIRY_GRACE: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone)]
 pub struct BuffInfo {
+    pub status_effect_id: u32,
     pub stacks: u8,
     pub applicant: u64,
+    /// ID of this application of the buff.
+    pub instance_id: u32,
+    pub scope: StatusEffectScope,
+    /// Time the buff runs out if not refreshed or removed first, if it has a duration.
+    pub expires: Option<Instant>,
+    /// Name of the status effect, if it exists in the status effect database.
+    pub name: Option<String>,
+    /// Indices into [`LiveData::buff_categories`] of the categories the buff is in.
+    pub categories: Vec<usize>,
+    /// Damage increases granted per stack, as fractions.
+    pub bonuses: Vec<(DamageStat, f64)>,
+    /// Whether the buff is a shield, tracked as a [`Shield`] while it's up.
+    pub shield: bool,
+}
+
+/// What a status effect applies to.
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+pub enum StatusEffectScope {
+    /// The entity it was applied to.
+    Target,
+    /// The player whose passive it is.
+    Passive,
+    /// The party of the player whose passive it is.
+    Party,
+    /// Every player or every NPC in the zone.
+    Zone(ZoneTarget),
+}
+
+impl StatusEffectScope {
+    /// Whether a status effect of this scope that's active applies to an entity.
+    fn applies_to(self, is_player: bool) -> bool {
+        match self {
+            StatusEffectScope::Zone(target) => (target == ZoneTarget::Players) == is_player,
+            _ => true,
+        }
+    }
+}
+
+/// Which entities in the zone a zone-wide status effect applies to.
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+pub enum ZoneTarget {
+    Players,
+    Npcs,
+}
+
+impl ZoneTarget {
+    /// From the target of a zone status effect packet, which is 0 for effects on players.
+    fn from_raw(raw: u8) -> Self {
+        match raw {
+            0 => ZoneTarget::Players,
+            _ => ZoneTarget::Npcs,
+        }
+    }
 }
 
 /// Collection of [`Environment`]s and [`Encounter`]s recorded during runtime.
@@ -936,6 +3023,11 @@ pub struct Data {
     pub live: LiveData,
     pub environments: Vec<Environment>,
     pub encounters: Vec<Encounter>,
+    pub raids: Vec<RaidRun>,
+    /// Saves encounters as they finish, if set.
+    pub log_writer: Option<LogWriter>,
+    /// Encounters loaded from saved logs.
+    pub saved: Vec<SavedEncounter>,
 }
 
 impl Data {
@@ -944,75 +3036,610 @@ impl Data {
             live: LiveData::default(),
             environments: vec![Environment::default()],
             encounters: vec![Encounter::default()],
+            raids: Vec::new(),
+            log_writer: None,
+            saved: Vec::new(),
         }))
     }
 
+    /// Set the buff categories tracked from now on, including in the current encounter.
+    fn set_buff_categories(&mut self, categories: Arc<[BuffCategory]>) {
+        self.current_enc_mut().buff_categories = Arc::clone(&categories);
+        self.live.buff_categories = categories;
+    }
+
     pub fn current_env(&self) -> &Environment {
         self.environments.last().unwrap()
     }
     pub fn current_env_mut(&mut self) -> &mut Environment {
         self.environments.last_mut().unwrap()
     }
-    pub fn current_enc(&self) -> &Encounter {
-        self.encounters.last().unwrap()
+    pub fn current_enc(&self) -> &Encounter {
+        self.encounters.last().unwrap()
+    }
+    pub fn current_enc_mut(&mut self) -> &mut Encounter {
+        self.encounters.last_mut().unwrap()
+    }
+
+    /// The raid run in progress, if any.
+    fn current_raid_mut(&mut self) -> Option<&mut RaidRun> {
+        let raid_id = self.live.raid_id?;
+        self.raids
+            .last_mut()
+            .filter(|run| run.raid_id == raid_id && run.end.is_none())
+    }
+
+    /// Name of the main target of an encounter, falling back to the zone's name.
+    pub fn encounter_name<'a>(&'a self, enc: &'a Encounter) -> Option<&'a str> {
+        let target = enc.tracked.first().and_then(|(id, _)| {
+            self.environments[enc.environment]
+                .npcs
+                .get(id)?
+                .name
+                .as_deref()
+        });
+        target.or_else(|| enc.zone.as_ref()?.name.as_deref())
+    }
+
+    /// Name an encounter is saved under: its boss encounter if it tracked one, otherwise its
+    /// main target or zone.
+    fn saved_name(&self, enc: &Encounter) -> Option<String> {
+        match enc.tracked.first() {
+            Some((_, boss)) => Some(boss.name()),
+            None => self.encounter_name(enc).map(str::to_owned),
+        }
+    }
+
+    /// Count a battle item used by a player towards the raid run in progress.
+    fn record_battle_item(
+        &mut self,
+        player_id: u64,
+        status_effect_id: u32,
+        name: Option<String>,
+        now: Instant,
+    ) {
+        let Some(player) = self.current_env().players.get(&player_id) else {
+            return;
+        };
+        let player = player.name.clone();
+        if let Some(run) = self.current_raid_mut() {
+            run.record_battle_item(player, status_effect_id, name, now);
+        }
+    }
+
+    /// Save the raid runs that can't take part in any more encounters: those that ended, and
+    /// those that were left for another run. When `closing`, the run in progress is saved too.
+    fn save_runs(&mut self, closing: bool) {
+        let Some(writer) = &self.log_writer else {
+            return;
+        };
+        let last = self.raids.len().saturating_sub(1);
+        let done = |idx, run: &RaidRun| closing || run.end.is_some() || idx != last;
+        for (idx, run) in self.raids.iter().enumerate() {
+            if !run.saved && done(idx, run) {
+                writer.save_run(self.run_log(run));
+            }
+        }
+        for (idx, run) in self.raids.iter_mut().enumerate() {
+            run.saved |= done(idx, run);
+        }
+    }
+
+    /// Save the encounter and raid run in progress, then wait for the log writer to finish.
+    /// Called on shutdown, as nothing starts a new encounter to save them otherwise.
+    pub fn close_log_writer(&mut self) {
+        if self.log_writer.is_none() {
+            return;
+        }
+        self.current_enc_mut().end = Some(Instant::now());
+        if let Some(writer) = &self.log_writer {
+            let enc = self.current_enc();
+            let name = self.saved_name(enc);
+            let env = &self.environments[enc.environment];
+            writer.save(enc, env, &self.live.parties, name.as_deref());
+        }
+        self.save_runs(true);
+        if let Some(writer) = self.log_writer.take() {
+            writer.close();
+        }
+    }
+
+    /// Saved form of a raid run, along with a summary of each of its encounters.
+    fn run_log(&self, run: &RaidRun) -> log::Run {
+        let ms = |duration: Duration| duration.as_millis().try_into().unwrap_or(u64::MAX);
+        let started_at = std::time::SystemTime::now()
+            .checked_sub(run.start.elapsed())
+            .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+            .map_or(0, |since| since.as_secs());
+        let encounters = run
+            .encounters
+            .iter()
+            .map(|&idx| &self.encounters[idx])
+            .filter(|enc| enc.first_damage.is_some())
+            .map(|enc| log::RunEncounter {
+                name: self.saved_name(enc),
+                difficulty: enc.difficulty(),
+                duration: ms(enc.duration()),
+                status: if enc.clear {
+                    Some(log::Status::Clear)
+                } else if enc.wipe {
+                    Some(log::Status::Wipe)
+                } else {
+                    None
+                },
+            })
+            .collect();
+        log::Run {
+            raid_id: run.raid_id,
+            started_at,
+            clear_time: run.clear_time().map(ms),
+            total_time: ms(run.total_time),
+            result: run.result,
+            brave_hearts: run.brave_hearts,
+            init_brave_hearts: run.init_brave_hearts,
+            bosses: run.bosses.clone(),
+            kills: run
+                .kills
+                .iter()
+                .map(|kill| ms(kill.saturating_duration_since(run.start)))
+                .collect(),
+            battle_items: run
+                .battle_items
+                .iter()
+                .map(|item| log::BattleItemUse {
+                    player: item.player.clone(),
+                    status_effect_id: item.status_effect_id,
+                    name: item.name.clone(),
+                    uses: item.uses,
+                })
+                .collect(),
+            encounters,
+        }
+    }
+
+    /// Which attempt at its content an encounter is, counting recent encounters of the same
+    /// boss, zone and difficulty up to and including it.
+    pub fn attempt(&self, idx: usize) -> usize {
+        let enc = &self.encounters[idx];
+        self.recent_encounters()
+            .filter(|&(i, other)| i <= idx && other.same_content(enc))
+            .count()
+    }
+
+    /// Returns an iterator of recent encounters where at least one player performed one action.
+    pub fn recent_encounters(&self) -> impl Iterator<Item = (usize, &Encounter)> + '_ {
+        self.encounters
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, e)| e.first_damage.is_some())
+            .filter(|(_, e)| !e.players.is_empty())
+            .filter(|(_, e)| !self.environments[e.environment].players.is_empty())
+    }
+
+    /// Index into [`Data::saved`] of a log that was already loaded.
+    pub fn saved_index(&self, path: &std::path::Path) -> Option<usize> {
+        self.saved.iter().position(|saved| saved.path == path)
+    }
+
+    /// Add a log read with [`SavedEncounter::read`], returning its index into [`Data::saved`].
+    /// A log loaded in the meantime is kept instead.
+    pub fn add_saved(&mut self, saved: SavedEncounter) -> usize {
+        if let Some(idx) = self.saved_index(&saved.path) {
+            return idx;
+        }
+        self.saved.push(saved);
+        self.saved.len() - 1
+    }
+
+    /// Drops status effects whose duration ran out without them being removed.
+    fn expire_status_effects(&mut self, now: Instant) {
+        let expired = self.live.take_expired_buffs(now);
+        for (target_id, instance_id, expires) in expired {
+            self.current_enc_mut()
+                .remove_status_effect(target_id, instance_id, expires);
+            self.end_shield(instance_id);
+        }
+    }
+
+    /// Apply a status effect to `target_id`, replacing any previous instance of it from the same
+    /// applicant. Shields start out at the value they were applied with.
+    fn add_status_effect(
+        &mut self,
+        target_id: u64,
+        raw: &crate::packet::StatusEffectData,
+        info: BuffInfo,
+        now: Instant,
+    ) {
+        self.current_enc_mut()
+            .apply_status_effect(target_id, raw, info.name.clone(), now);
+        let replaced = self.live.buffs.get_mut(&target_id).and_then(|buffs| {
+            let (&instance_id, _) = buffs.iter().find(|(&instance_id, info)| {
+                instance_id != raw.effect_instance_id
+                    && info.status_effect_id == raw.status_effect_id
+                    && info.applicant == raw.source_id
+            })?;
+            buffs.remove(&instance_id).map(|_| instance_id)
+        });
+        if let Some(instance_id) = replaced {
+            self.end_shield(instance_id);
+        }
+        if info.shield {
+            // a shield whose size can't be read starts out empty, and is credited once synced
+            self.update_shield(
+                info.applicant,
+                target_id,
+                raw.effect_instance_id,
+                shield_value(raw.value).unwrap_or(0),
+            );
+        }
+        self.live.add_buff(target_id, raw, info, now);
+    }
+
+    /// Remove a status effect from `target_id`, ending it if it's a shield.
+    fn remove_status_effect(&mut self, target_id: u64, instance_id: u32, now: Instant) {
+        self.current_enc_mut()
+            .remove_status_effect(target_id, instance_id, now);
+        self.end_shield(instance_id);
+        if let Some(buffs) = self.live.buffs.get_mut(&target_id) {
+            buffs.remove(&instance_id);
+        }
+    }
+
+    /// Update the value left of a shield from its status effect's synced value. Status effects
+    /// that aren't being tracked or aren't shields are ignored.
+    fn sync_shield(&mut self, target_id: u64, instance_id: u32, value: u64) {
+        let Some(applicant) = self
+            .live
+            .buffs
+            .get(&target_id)
+            .and_then(|buffs| buffs.get(&instance_id))
+            .filter(|info| info.shield)
+            .map(|info| info.applicant)
+        else {
+            return;
+        };
+        self.update_shield(applicant, target_id, instance_id, value);
+    }
+
+    /// Set the value left of a shield, crediting any increase as given and any decrease as
+    /// absorbed.
+    fn update_shield(&mut self, applicant: u64, target_id: u64, instance_id: u32, value: u64) {
+        let shield = self.live.shields.entry(instance_id).or_insert(Shield {
+            applicant,
+            target_id,
+            remaining: 0,
+        });
+        let previous = std::mem::replace(&mut shield.remaining, value);
+
+        let shielding = self
+            .current_enc_mut()
+            .shields
+            .entry((applicant, target_id))
+            .or_default();
+        if value > previous {
+            shielding.given += value - previous;
+        } else {
+            shielding.absorbed += previous - value;
+        }
+    }
+
+    /// Stop tracking a shield, crediting whatever was left of it as expired.
+    fn end_shield(&mut self, instance_id: u32) {
+        let Some(shield) = self.live.shields.remove(&instance_id) else {
+            return;
+        };
+        self.current_enc_mut()
+            .shields
+            .entry((shield.applicant, shield.target_id))
+            .or_default()
+            .expired += shield.remaining;
+    }
+
+    /// Apply a passive status effect to each of `targets`.
+    fn add_passive_status_effect(
+        &mut self,
+        targets: &[u64],
+        id: u32,
+        info: BuffInfo,
+        now: Instant,
+    ) {
+        for &target_id in targets {
+            let interval = StatusEffectInterval::passive(id, &info, now);
+            self.current_enc_mut()
+                .apply_passive_status_effect(Some(target_id), interval);
+            self.live
+                .passive_buffs
+                .entry(target_id)
+                .or_default()
+                .insert((info.scope, id), info.clone());
+        }
+    }
+
+    /// Remove a passive status effect from every entity it was applied to.
+    fn remove_passive_status_effect(
+        &mut self,
+        id: u32,
+        source_id: u64,
+        scope: StatusEffectScope,
+        now: Instant,
+    ) {
+        for buffs in self.live.passive_buffs.values_mut() {
+            if buffs
+                .get(&(scope, id))
+                .is_some_and(|info| info.applicant == source_id)
+            {
+                buffs.remove(&(scope, id));
+            }
+        }
+        self.live.passive_buffs.retain(|_, buffs| !buffs.is_empty());
+        self.current_enc_mut()
+            .remove_passive_status_effect(id, source_id, scope, now);
+    }
+
+    /// Apply a zone-wide status effect to the players or NPCs in the zone.
+    fn add_zone_status_effect(&mut self, info: BuffInfo, now: Instant) {
+        let interval = StatusEffectInterval::passive(info.status_effect_id, &info, now);
+        self.current_enc_mut()
+            .apply_passive_status_effect(None, interval);
+        self.live.zone_buffs.insert(info.status_effect_id, info);
+    }
+
+    fn remove_zone_status_effect(&mut self, id: u32, now: Instant) {
+        self.live.zone_buffs.remove(&id);
+        // removals don't say who the effect applied to
+        for target in [ZoneTarget::Players, ZoneTarget::Npcs] {
+            self.current_enc_mut().remove_passive_status_effect(
+                id,
+                0,
+                StatusEffectScope::Zone(target),
+                now,
+            );
+        }
+    }
+
+    /// Drop every status effect on an entity that has left.
+    fn clear_status_effects(&mut self, target_id: u64, now: Instant) {
+        self.live.remove_buffs(target_id);
+        let shields: Vec<_> = self
+            .live
+            .shields
+            .iter()
+            .filter(|(_, shield)| shield.target_id == target_id)
+            .map(|(&instance_id, _)| instance_id)
+            .collect();
+        for instance_id in shields {
+            self.end_shield(instance_id);
+        }
+        if let Some(effects) = self.current_enc_mut().status_effects.get_mut(&target_id) {
+            for effect in effects.iter_mut().filter(|effect| effect.end.is_none()) {
+                effect.end = Some(now);
+            }
+        }
+    }
+
+    /// Begins a new encounter.
+    fn new_encounter(&mut self) {
+        println!("encounter reset");
+        let timestamp = Instant::now();
+        self.current_enc_mut().end = Some(timestamp);
+
+        if let Some(writer) = &self.log_writer {
+            let enc = self.current_enc();
+            let name = self.saved_name(enc);
+            let env = &self.environments[enc.environment];
+            writer.save(enc, env, &self.live.parties, name.as_deref());
+        }
+        self.save_runs(false);
+
+        // status effects still applied carry over, unless live state was just cleared
+        let (mut status_effects, mut zone_status_effects) =
+            self.current_enc_mut().split_status_effects(timestamp);
+        for (target_id, effects) in &mut status_effects {
+            let buffs = self.live.buffs.get(target_id);
+            let passives = self.live.passive_buffs.get(target_id);
+            effects.retain(|effect| match effect.scope {
+                StatusEffectScope::Passive | StatusEffectScope::Party => {
+                    passives.is_some_and(|buffs| {
+                        buffs.contains_key(&(effect.scope, effect.status_effect_id))
+                    })
+                }
+                _ => buffs.is_some_and(|buffs| buffs.contains_key(&effect.instance_id)),
+            });
+        }
+        status_effects.retain(|_, effects| !effects.is_empty());
+        zone_status_effects
+            .retain(|effect| self.live.zone_buffs.contains_key(&effect.status_effect_id));
+
+        self.live.clear_encounter_data();
+        self.encounters.push(Encounter {
+            start: timestamp,
+            status_effects,
+            zone_status_effects,
+            environment: self.environments.len() - 1,
+            zone: self.live.zone.clone(),
+            raid_id: self.live.raid_id,
+            buff_categories: Arc::clone(&self.live.buff_categories),
+            ..Default::default()
+        });
+        let enc_idx = self.encounters.len() - 1;
+        if let Some(run) = self.current_raid_mut() {
+            run.encounters.push(enc_idx);
+        }
+    }
+}
+
+/// A single entry into a raid, grouping the encounters of each of its gates.
+#[derive(Debug, Clone)]
+pub struct RaidRun {
+    pub raid_id: u32,
+    /// Server tick the raid was entered at, in milliseconds.
+    pub start_tick: u64,
+    /// Server tick the raid was completed at, in milliseconds, if reported.
+    pub end_tick: Option<u64>,
+    /// Time already spent in the raid when it was last (re-)entered.
+    pub total_time: Duration,
+    /// Time the raid was first seen.
+    pub start: Instant,
+    /// Time the raid result was received.
+    pub end: Option<Instant>,
+    /// Result code from `PktRaidResult`.
+    pub result: Option<u8>,
+    /// Revives remaining.
+    pub brave_hearts: u8,
+    /// Revives available when the raid began.
+    pub init_brave_hearts: u8,
+    /// NPC type IDs of the raid's bosses, and whether they've been killed.
+    pub bosses: Vec<(u32, bool)>,
+    /// Times bosses were killed, as notified by `PktRaidBossKillNotify`.
+    pub kills: Vec<Instant>,
+    /// Battle items used by each player.
+    pub battle_items: Vec<BattleItemUse>,
+    /// Indices into [`Data::encounters`] of encounters that took place during the run.
+    pub encounters: Vec<usize>,
+    /// Whether the run was handed to the log writer.
+    saved: bool,
+}
+
+/// Uses of a battle item by a player, as seen from the status effects it applies.
+#[derive(Debug, Clone)]
+pub struct BattleItemUse {
+    pub player: Option<String>,
+    pub status_effect_id: u32,
+    pub name: Option<String>,
+    pub uses: u32,
+    /// Time of the latest use.
+    last: Instant,
+}
+
+impl Default for RaidRun {
+    fn default() -> Self {
+        Self {
+            raid_id: 0,
+            start_tick: 0,
+            end_tick: None,
+            total_time: Duration::ZERO,
+            start: Instant::now(),
+            end: None,
+            result: None,
+            brave_hearts: 0,
+            init_brave_hearts: 0,
+            bosses: Vec::new(),
+            kills: Vec::new(),
+            battle_items: Vec::new(),
+            encounters: Vec::new(),
+            saved: false,
+        }
+    }
+}
+
+impl RaidRun {
+    /// Official time taken to clear the raid, as measured by the server.
+    pub fn clear_time(&self) -> Option<Duration> {
+        let end_tick = self.end_tick?;
+        Some(Duration::from_millis(
+            end_tick.checked_sub(self.start_tick)?,
+        ))
+    }
+
+    pub fn revives_used(&self) -> u8 {
+        self.init_brave_hearts.saturating_sub(self.brave_hearts)
     }
-    pub fn current_enc_mut(&mut self) -> &mut Encounter {
-        self.encounters.last_mut().unwrap()
+
+    pub fn battle_items_used(&self) -> u32 {
+        self.battle_items.iter().map(|item| item.uses).sum()
     }
 
-    /// Returns an iterator of recent encounters where at least one player performed one action.
-    pub fn recent_encounters(&self) -> impl Iterator<Item = (usize, &Encounter)> + '_ {
-        self.encounters
-            .iter()
-            .enumerate()
-            .rev()
-            .filter(|(_, e)| e.first_damage.is_some())
-            .filter(|(_, e)| !e.players.is_empty())
-            .filter(|(_, e)| !self.environments[e.environment].players.is_empty())
+    /// Count a battle item's status effect being applied. A single use can apply it to
+    /// several targets at once, so applications within a second of each other are one use.
+    fn record_battle_item(
+        &mut self,
+        player: Option<String>,
+        status_effect_id: u32,
+        name: Option<String>,
+        now: Instant,
+    ) {
+        const SAME_USE: Duration = Duration::from_secs(1);
+        let item = self
+            .battle_items
+            .iter_mut()
+            .find(|item| item.status_effect_id == status_effect_id && item.player == player);
+        match item {
+            Some(item) if now.saturating_duration_since(item.last) < SAME_USE => {}
+            Some(item) => {
+                item.uses += 1;
+                item.last = now;
+            }
+            None => self.battle_items.push(BattleItemUse {
+                player,
+                status_effect_id,
+                name,
+                uses: 1,
+                last: now,
+            }),
+        }
     }
+}
 
-    /// Begins a new encounter.
-    fn new_encounter(&mut self) {
-        println!("encounter reset");
-        let timestamp = Instant::now();
-        self.current_enc_mut().end = Some(timestamp);
+/// A zone along with the difficulty it was entered at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Zone {
+    pub id: u32,
+    pub name: Option<String>,
+    pub difficulty: Difficulty,
+}
 
-        // save log
-        // let enc = self.current_enc();
-        // if let Some((_, boss)) = enc.tracked.first()
-        //     && enc.duration() > Duration::from_secs(5)
-        // {
-        //     if let Some(log) = log::Log::from_encounter(enc, self.current_env()) {
-        //         // save to file
-        //         let timestamp = std::time::SystemTime::now()
-        //             .duration_since(std::time::SystemTime::UNIX_EPOCH)
-        //             .unwrap()
-        //             .as_secs();
-
-        //         let write_log = |ts, log| -> anyhow::Result<()> {
-        //             let f = std::fs::File::create(format!("proc_logs/{}_{}", ts, boss.name()))?;
-        //             let w = snap::write::FrameEncoder::new(f);
-        //             serde_bare::to_writer(w, log)?;
-        //             Ok(())
-        //         };
-        //         match write_log(timestamp, &log) {
-        //             Ok(()) => println!("wrote processed log"),
-        //             Err(e) => println!("error writing logfile: {}", e),
-        //         }
-        //     }
-        // }
+impl Zone {
+    /// Name of the zone, or its ID if it isn't in the zone database.
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("zone {}", self.id))
+    }
+}
 
-        self.live.clear_encounter_data();
-        self.encounters.push(Encounter {
-            start: timestamp,
-            environment: self.environments.len() - 1,
-            ..Default::default()
-        });
+/// An encounter loaded from a saved log, along with the entities it references.
+pub struct SavedEncounter {
+    pub path: std::path::PathBuf,
+    /// Name of the encounter's main target, if known.
+    pub name: Option<String>,
+    pub environment: Environment,
+    pub encounter: Encounter,
+    /// Party of each player when the log was saved.
+    pub parties: BTreeMap<u64, u32>,
+}
+
+impl SavedEncounter {
+    /// Read and decode a saved log. This can take a while for long encounters, so it shouldn't
+    /// be done while holding the [`Data`] lock.
+    pub fn read(path: &std::path::Path) -> anyhow::Result<Self> {
+        let log = crate::persist::read_log(path)
+            .with_context(|| format!("reading log {}", path.display()))?;
+        anyhow::ensure!(
+            log.entities
+                .iter()
+                .any(|e| matches!(e.kind, log::EntityKind::Player { .. })),
+            "log {} has no players",
+            path.display()
+        );
+        let name = log
+            .targets
+            .first()
+            .and_then(|&target| log.entities.get(target.0)?.name.clone());
+        let parties = log.parties();
+        let (environment, encounter) = log.into_encounter();
+        Ok(Self {
+            path: path.to_owned(),
+            name,
+            environment,
+            encounter,
+            parties,
+        })
     }
 }
 
 /// List of entities present in a map during one or more [`Encounter`]s.
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct Environment {
     /// The ID of the player running the meter, the point of view. This may not be known.
     pub pov: Option<u64>,
@@ -1107,70 +3734,817 @@ impl Player {
     }
 }
 
-/// Representation of a game encounter.
-pub struct Encounter {
-    /// Time recording began.
+/// Representation of a game encounter.
+#[derive(Clone)]
+pub struct Encounter {
+    /// Time recording began.
+    pub start: Instant,
+    /// Time recording ended.
+    pub end: Option<Instant>,
+    /// Time of first damage dealt, if any.
+    pub first_damage: Option<Instant>,
+    /// Time of last damage dealt before recording ended, if any.
+    pub last_damage: Option<Instant>,
+    /// The environment that referenced entity IDs are valid in.
+    pub environment: usize,
+    /// Maps player ID found in [`Environment`] to their metrics.
+    pub players: BTreeMap<u64, PlayerData>,
+    /// Maps NPC ID found in [`Environment`] to their metrics.
+    pub npcs: BTreeMap<u64, PlayerData>,
+    /// Maps player ID found in [`Environment`] to damage they took from NPCs.
+    pub damage_taken: BTreeMap<u64, DamageTaken>,
+    /// Player deaths, in order.
+    pub deaths: Vec<Death>,
+    /// Maps player ID found in [`Environment`] to counterattacks they attempted, in order.
+    pub counters: BTreeMap<u64, Vec<Counter>>,
+    /// Maps NPC ID found in [`Environment`] to its stagger history.
+    pub stagger: BTreeMap<u64, Stagger>,
+    /// Maps player ID found in [`Environment`] to their identity gauge and stance history.
+    pub identity: BTreeMap<u64, Identity>,
+    /// Maps entity ID to status effects applied to it, in order of application.
+    pub status_effects: BTreeMap<u64, Vec<StatusEffectInterval>>,
+    /// Maps applicant and target ID found in [`Environment`] to shields between them.
+    pub shields: BTreeMap<(u64, u64), Shielding>,
+    /// Status effects applied to the whole zone, in order of application.
+    pub zone_status_effects: Vec<StatusEffectInterval>,
+    /// Main target of the encounter, if any.
+    pub tracked: Vec<(u64, Boss)>,
+    /// Whether the encounter ended in failure.
+    pub wipe: bool,
+    /// Whether the encounter ended in success.
+    pub clear: bool,
+    /// Zone the encounter took place in, if known.
+    pub zone: Option<Zone>,
+    /// ID of the raid the encounter was a part of, if any.
+    pub raid_id: Option<u32>,
+    /// Categories of buffs that [`PlayerData::buffed`] is tracked for.
+    pub buff_categories: Arc<[BuffCategory]>,
+}
+
+impl Default for Encounter {
+    fn default() -> Self {
+        Self {
+            start: Instant::now(),
+            end: None,
+            first_damage: None,
+            last_damage: None,
+            environment: 0,
+            players: BTreeMap::new(),
+            npcs: BTreeMap::new(),
+            damage_taken: BTreeMap::new(),
+            deaths: Vec::new(),
+            counters: BTreeMap::new(),
+            stagger: BTreeMap::new(),
+            identity: BTreeMap::new(),
+            status_effects: BTreeMap::new(),
+            shields: BTreeMap::new(),
+            zone_status_effects: Vec::new(),
+            tracked: Vec::new(),
+            wipe: false,
+            clear: false,
+            zone: None,
+            raid_id: None,
+            buff_categories: Arc::new([]),
+        }
+    }
+}
+
+impl Encounter {
+    /// Counterattacks a player attempted and landed.
+    pub fn counter_count(&self, player_id: u64) -> CounterCount {
+        let counters = self.counters.get(&player_id).map_or(&[][..], Vec::as_slice);
+        CounterCount {
+            attempts: counters.len(),
+            successes: counters.iter().filter(|counter| counter.success()).count(),
+        }
+    }
+
+    /// Damage that a player's buffs added to the hits of other players.
+    pub fn damage_enabled_by(&self, player_id: u64) -> i64 {
+        self.players
+            .values()
+            .filter_map(|player| player.buff_contributions.get(&player_id))
+            .sum()
+    }
+
+    /// Share of the damage dealt by a player's party that was dealt under their buffs of a
+    /// category, including their own damage. `parties` maps player IDs to their party, like
+    /// [`LiveData::parties`]. Players not known to be in a party only cover themselves.
+    pub fn party_coverage(
+        &self,
+        player_id: u64,
+        category: usize,
+        parties: &BTreeMap<u64, u32>,
+    ) -> f64 {
+        let party = parties.get(&player_id);
+        let (mut covered, mut total) = (0, 0);
+        for (&id, player) in &self.players {
+            if id != player_id && (party.is_none() || parties.get(&id) != party) {
+                continue;
+            }
+            total += player.dmg_dealt;
+            covered += player
+                .buffed_by
+                .get(&(category, player_id))
+                .map_or(0, |buffed| buffed.damage);
+        }
+        if total > 0 {
+            covered as f64 / total as f64
+        } else {
+            0.0
+        }
+    }
+
+    /// Shields a player applied, to anyone.
+    pub fn shields_given_by(&self, player_id: u64) -> Shielding {
+        self.shields
+            .iter()
+            .filter(|((applicant, _), _)| *applicant == player_id)
+            .map(|(_, shielding)| *shielding)
+            .sum()
+    }
+
+    /// Shields an entity received, from anyone.
+    pub fn shields_received_by(&self, target_id: u64) -> Shielding {
+        self.shields
+            .iter()
+            .filter(|((_, target), _)| *target == target_id)
+            .map(|(_, shielding)| *shielding)
+            .sum()
+    }
+
+    /// Mark a player's most recent death as having ended.
+    fn revive(&mut self, player_id: u64, time: Instant) {
+        let death = self
+            .deaths
+            .iter_mut()
+            .rev()
+            .find(|death| death.player_id == player_id);
+        if let Some(death) = death.filter(|death| death.revived.is_none()) {
+            death.revived = Some(time);
+        }
+    }
+
+    /// Record a change of an NPC's stagger bar, crediting any increase of `gauge` since its last
+    /// sample to `hitter`.
+    fn record_stagger(
+        &mut self,
+        npc_id: u64,
+        gauge: StaggerGauge,
+        sample: StaggerSample,
+        active: bool,
+        hitter: Option<u64>,
+    ) {
+        let StaggerSample {
+            time,
+            point,
+            max_point,
+        } = sample;
+        let stagger = self.stagger.entry(npc_id).or_default();
+        // the first sample of a gauge may include stagger dealt before it was seen
+        let prev = stagger.last_point.insert(gauge, point);
+        stagger.samples.push(sample);
+        if let (Some(player_id), Some(prev)) = (hitter, prev.filter(|&prev| point > prev)) {
+            *stagger.contribution.entry(player_id).or_default() += u64::from(point - prev);
+        }
+
+        let staggered = max_point > 0 && point >= max_point;
+        match stagger
+            .checks
+            .last_mut()
+            .filter(|check| check.end.is_none())
+        {
+            Some(check) if staggered => {
+                check.end = Some(time);
+                check.success = true;
+            }
+            Some(check) if !active => check.end = Some(time),
+            Some(_) => {}
+            None if active && max_point > 0 && !staggered => stagger.checks.push(StaggerCheck {
+                start: time,
+                end: None,
+                max_point,
+                success: false,
+            }),
+            None => {}
+        }
+    }
+
+    pub fn deaths_of(&self, player_id: u64) -> impl Iterator<Item = &Death> + '_ {
+        self.deaths
+            .iter()
+            .filter(move |death| death.player_id == player_id)
+    }
+
+    pub fn difficulty(&self) -> Option<Difficulty> {
+        self.zone.as_ref().map(|zone| zone.difficulty)
+    }
+
+    /// Whether two encounters are of the same content -- the same boss, in the same zone, at
+    /// the same difficulty.
+    pub fn same_content(&self, other: &Encounter) -> bool {
+        let boss = |enc: &Encounter| enc.tracked.first().map(|(_, boss)| boss.npc.id);
+        let zone = |enc: &Encounter| enc.zone.as_ref().map(|zone| (zone.id, zone.difficulty));
+        boss(self) == boss(other) && zone(self) == zone(other) && self.raid_id == other.raid_id
+    }
+
+    pub fn duration(&self) -> Duration {
+        let (start, end) = self.fight_window();
+        end.duration_since(start)
+    }
+
+    /// Start and end of the fight, from the first to the last damage dealt where known.
+    pub fn fight_window(&self) -> (Instant, Instant) {
+        let start = self.first_damage.unwrap_or(self.start);
+        let end = self.last_damage.or(self.end).unwrap_or_else(Instant::now);
+        (start, end.max(start))
+    }
+
+    /// Status effects applied to an entity, in order of application.
+    pub fn status_effects_on(&self, target_id: u64) -> &[StatusEffectInterval] {
+        self.status_effects
+            .get(&target_id)
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Whether an entity took part in the encounter as a player, which decides the zone-wide
+    /// status effects that apply to it.
+    fn is_player(&self, id: u64) -> bool {
+        self.players.contains_key(&id)
+            || self.damage_taken.contains_key(&id)
+            || self.identity.contains_key(&id)
+    }
+
+    /// Share of the fight, from 0 to 1, that an entity had any status effect matching `filter`,
+    /// including the zone-wide ones applying to it.
+    pub fn uptime(&self, target_id: u64, filter: impl Fn(&StatusEffectInterval) -> bool) -> f64 {
+        let is_player = self.is_player(target_id);
+        let zone = self
+            .zone_status_effects
+            .iter()
+            .filter(|effect| effect.scope.applies_to(is_player));
+        let effects = self.status_effects_on(target_id).iter().chain(zone);
+        self.covered_share(effects.filter(|effect| filter(effect)))
+    }
+
+    /// Share of the fight, from 0 to 1, that a zone-wide status effect was applied, to anyone.
+    pub fn zone_status_effect_uptime(&self, status_effect_id: u32) -> f64 {
+        let effects = self
+            .zone_status_effects
+            .iter()
+            .filter(|effect| effect.status_effect_id == status_effect_id);
+        self.covered_share(effects)
+    }
+
+    /// Share of the fight, from 0 to 1, covered by any of `effects`.
+    fn covered_share<'a>(&self, effects: impl Iterator<Item = &'a StatusEffectInterval>) -> f64 {
+        let (start, end) = self.fight_window();
+        let total = end.duration_since(start);
+        if total.is_zero() {
+            return 0.0;
+        }
+
+        let mut intervals: Vec<_> = effects
+            .map(|effect| (effect.start.max(start), effect.end.unwrap_or(end).min(end)))
+            .filter(|(from, to)| from < to)
+            .collect();
+        intervals.sort_by_key(|&(from, _)| from);
+
+        // overlapping instances, such as the same buff from two players, only count once
+        let mut covered = Duration::ZERO;
+        let mut current: Option<(Instant, Instant)> = None;
+        for (from, to) in intervals {
+            match current.as_mut() {
+                Some((_, cur_to)) if from <= *cur_to => *cur_to = (*cur_to).max(to),
+                _ => {
+                    if let Some((cur_from, cur_to)) = current.replace((from, to)) {
+                        covered += cur_to - cur_from;
+                    }
+                }
+            }
+        }
+        if let Some((cur_from, cur_to)) = current {
+            covered += cur_to - cur_from;
+        }
+        covered.as_secs_f64() / total.as_secs_f64()
+    }
+
+    /// Share of the fight, from 0 to 1, that an entity had a status effect.
+    pub fn status_effect_uptime(&self, target_id: u64, status_effect_id: u32) -> f64 {
+        self.uptime(target_id, |effect| {
+            effect.status_effect_id == status_effect_id
+        })
+    }
+
+    fn open_status_effect_mut(
+        &mut self,
+        target_id: u64,
+        instance_id: u32,
+    ) -> Option<&mut StatusEffectInterval> {
+        self.status_effects
+            .get_mut(&target_id)?
+            .iter_mut()
+            .rev()
+            .find(|effect| effect.instance_id == instance_id && effect.end.is_none())
+    }
+
+    /// Record a status effect being applied to an entity, or a change to one already applied.
+    fn apply_status_effect(
+        &mut self,
+        target_id: u64,
+        raw: &crate::packet::StatusEffectData,
+        name: Option<String>,
+        time: Instant,
+    ) {
+        if let Some(effect) = self.open_status_effect_mut(target_id, raw.effect_instance_id) {
+            if effect.stacks.last().map(|&(_, stacks)| stacks) != Some(raw.stack_count) {
+                effect.stacks.push((time, raw.stack_count));
+            }
+            if effect.end_tick != raw.end_tick {
+                effect.end_tick = raw.end_tick;
+                effect.refreshes.push((time, raw.end_tick));
+            }
+            return;
+        }
+        self.status_effects
+            .entry(target_id)
+            .or_default()
+            .push(StatusEffectInterval {
+                status_effect_id: raw.status_effect_id,
+                instance_id: raw.effect_instance_id,
+                source_id: raw.source_id,
+                name,
+                scope: StatusEffectScope::Target,
+                start: time,
+                end: None,
+                stacks: vec![(time, raw.stack_count)],
+                total_time: raw.total_time,
+                end_tick: raw.end_tick,
+                refreshes: Vec::new(),
+            });
+    }
+
+    fn refresh_status_effect(
+        &mut self,
+        target_id: u64,
+        instance_id: u32,
+        end_tick: u64,
+        time: Instant,
+    ) {
+        if let Some(effect) = self.open_status_effect_mut(target_id, instance_id) {
+            effect.end_tick = end_tick;
+            effect.refreshes.push((time, end_tick));
+        }
+    }
+
+    fn remove_status_effect(&mut self, target_id: u64, instance_id: u32, time: Instant) {
+        if let Some(effect) = self.open_status_effect_mut(target_id, instance_id) {
+            effect.end = Some(time);
+        }
+    }
+
+    /// Record a passive or zone-wide status effect, unless it's already applied. Zone-wide
+    /// effects have no target.
+    fn apply_passive_status_effect(
+        &mut self,
+        target_id: Option<u64>,
+        effect: StatusEffectInterval,
+    ) {
+        let effects = match target_id {
+            Some(id) => self.status_effects.entry(id).or_default(),
+            None => &mut self.zone_status_effects,
+        };
+        let applied = effects.iter().any(|applied| {
+            applied.end.is_none()
+                && applied.status_effect_id == effect.status_effect_id
+                && applied.scope == effect.scope
+                && applied.source_id == effect.source_id
+        });
+        if !applied {
+            effects.push(effect);
+        }
+    }
+
+    fn remove_passive_status_effect(
+        &mut self,
+        id: u32,
+        source_id: u64,
+        scope: StatusEffectScope,
+        time: Instant,
+    ) {
+        let effects = self
+            .status_effects
+            .values_mut()
+            .flatten()
+            .chain(&mut self.zone_status_effects)
+            .filter(|effect| {
+                effect.end.is_none()
+                    && effect.status_effect_id == id
+                    && effect.scope == scope
+                    && effect.source_id == source_id
+            });
+        for effect in effects {
+            effect.end = Some(time);
+        }
+    }
+
+    /// End every status effect still applied at `time`, returning copies of them starting at
+    /// `time`, and of the zone-wide ones.
+    fn split_status_effects(
+        &mut self,
+        time: Instant,
+    ) -> (
+        BTreeMap<u64, Vec<StatusEffectInterval>>,
+        Vec<StatusEffectInterval>,
+    ) {
+        let mut split = BTreeMap::new();
+        for (&target_id, effects) in &mut self.status_effects {
+            let open = split_open_status_effects(effects, time);
+            if !open.is_empty() {
+                split.insert(target_id, open);
+            }
+        }
+        let zone = split_open_status_effects(&mut self.zone_status_effects, time);
+        (split, zone)
+    }
+}
+
+fn split_open_status_effects(
+    effects: &mut [StatusEffectInterval],
+    time: Instant,
+) -> Vec<StatusEffectInterval> {
+    effects
+        .iter_mut()
+        .filter(|effect| effect.end.is_none())
+        .map(|effect| {
+            effect.end = Some(time);
+            let stacks = effect.stacks.last().map_or(0, |&(_, stacks)| stacks);
+            StatusEffectInterval {
+                start: time,
+                end: None,
+                stacks: vec![(time, stacks)],
+                refreshes: Vec::new(),
+                name: effect.name.clone(),
+                ..*effect
+            }
+        })
+        .collect()
+}
+
+/// Metrics for a player.
+#[derive(Debug, Default, Clone)]
+pub struct PlayerData {
+    /// Total damage dealt by player.
+    pub dmg_dealt: i64,
+    pub hits: u64,
+    /// Damage dealt under each of [`Encounter::buff_categories`], by index.
+    pub buffed: Vec<BuffedDamage>,
+    /// Damage dealt under each buff category by the applicant of the buff, keyed by category
+    /// index and applicant ID.
+    pub buffed_by: BTreeMap<(usize, u64), BuffedDamage>,
+    /// Damage that other players' buffs added to this player's hits, by the buff's applicant.
+    pub buff_contributions: BTreeMap<u64, i64>,
+    /// Map of skill ID to data about that skill.
+    pub skills: BTreeMap<u32, SkillUsage>,
+
+    pub damage: Vec<(Instant, i64)>,
+    pub casts: Vec<(Instant, u32)>,
+}
+
+impl PlayerData {
+    /// Record a hit of a skill, returning the skill's usage for further bookkeeping.
+    fn add_hit(
+        &mut self,
+        timestamp: Instant,
+        skill_id: u32,
+        hit: SkillHit,
+        name: impl FnOnce() -> Option<String>,
+    ) -> &mut SkillUsage {
+        self.damage.push((timestamp, hit.damage));
+        self.dmg_dealt += hit.damage;
+        self.hits += 1;
+        self.casts.push((timestamp, skill_id));
+
+        let skill = self.skills.entry(skill_id).or_insert_with(|| SkillUsage {
+            name: name(),
+            ..Default::default()
+        });
+        skill.count += 1;
+        skill.damage += hit.damage;
+        skill.crits += hit.is_crit as usize;
+        skill.back += hit.is_back_attack as usize;
+        skill.front += hit.is_front_attack as usize;
+        add_buffed(&mut skill.buffed, &hit.buffed, |hits| *hits += 1);
+        add_buffed(&mut self.buffed, &hit.buffed, |buffed| {
+            buffed.hits += 1;
+            buffed.damage += hit.damage;
+        });
+        for &key in &hit.buffed_by {
+            let by = self.buffed_by.entry(key).or_default();
+            by.hits += 1;
+            by.damage += hit.damage;
+        }
+        skill.hits.push((timestamp, hit));
+        skill
+    }
+
+    /// Damage that other players' buffs added to this player's hits.
+    pub fn damage_from_buffs(&self) -> i64 {
+        self.buff_contributions.values().sum()
+    }
+
+    /// Share of damage dealt under a buff category, by its index.
+    pub fn buffed_rate(&self, category: usize) -> f64 {
+        match self.buffed.get(category) {
+            Some(buffed) if self.dmg_dealt > 0 => buffed.damage as f64 / self.dmg_dealt as f64,
+            _ => 0.0,
+        }
+    }
+}
+
+/// Damage a player dealt under a buff category.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BuffedDamage {
+    pub hits: u64,
+    pub damage: i64,
+}
+
+/// Totals of shields applied, as shield value.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Shielding {
+    pub given: u64,
+    /// Damage the shields prevented.
+    pub absorbed: u64,
+    /// Shield value left unused when the shields ended.
+    pub expired: u64,
+}
+
+impl std::iter::Sum for Shielding {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), |acc, s| Self {
+            given: acc.given + s.given,
+            absorbed: acc.absorbed + s.absorbed,
+            expired: acc.expired + s.expired,
+        })
+    }
+}
+
+/// A status effect applied to an entity, from application to removal.
+#[derive(Debug, Clone)]
+pub struct StatusEffectInterval {
+    pub status_effect_id: u32,
+    /// ID of this application of the status effect.
+    pub instance_id: u32,
+    /// ID of the entity that applied the status effect.
+    pub source_id: u64,
+    /// Name of the status effect, if it exists in the status effect database.
+    pub name: Option<String>,
+    pub scope: StatusEffectScope,
+    pub start: Instant,
+    /// Time the status effect was removed, if it was.
+    pub end: Option<Instant>,
+    /// Stack counts, in order, starting with the count it was applied with.
+    pub stacks: Vec<(Instant, u8)>,
+    /// Duration the status effect was applied with, in seconds.
+    pub total_time: f32,
+    /// Server tick the status effect is due to expire at.
+    pub end_tick: u64,
+    /// Changes to the expiration tick, in order.
+    pub refreshes: Vec<(Instant, u64)>,
+}
+
+impl StatusEffectInterval {
+    /// A passive or zone-wide status effect starting at `time`.
+    fn passive(id: u32, info: &BuffInfo, time: Instant) -> Self {
+        Self {
+            status_effect_id: id,
+            instance_id: info.instance_id,
+            source_id: info.applicant,
+            name: info.name.clone(),
+            scope: info.scope,
+            start: time,
+            end: None,
+            stacks: vec![(time, info.stacks)],
+            total_time: 0.0,
+            end_tick: 0,
+            refreshes: Vec::new(),
+        }
+    }
+}
+
+/// Identity gauge and stance history of a player.
+#[derive(Debug, Default, Clone)]
+pub struct Identity {
+    /// Values of the three identity gauges, in order.
+    pub gauge: Vec<(Instant, [u32; 3])>,
+    /// Stance changes, in order. Stance 0 is the normal stance.
+    pub stances: Vec<(Instant, u8)>,
+    /// Casts of support identity ultimates, in order.
+    pub ultimates: Vec<(Instant, u32)>,
+}
+
+impl Identity {
+    /// Time spent in each stance between the first stance change and `end`.
+    pub fn time_in_stances(&self, end: Instant) -> BTreeMap<u8, Duration> {
+        let mut times = BTreeMap::new();
+        let next_times = self.stances.iter().skip(1).map(|&(i, _)| i);
+        for (&(start, stance), next) in self
+            .stances
+            .iter()
+            .zip(next_times.chain(std::iter::once(end)))
+        {
+            *times.entry(stance).or_default() += next.saturating_duration_since(start);
+        }
+        times
+    }
+
+    /// Time spent in any stance other than the normal one.
+    pub fn time_in_identity(&self, end: Instant) -> Duration {
+        self.time_in_stances(end)
+            .into_iter()
+            .filter(|&(stance, _)| stance != 0)
+            .map(|(_, time)| time)
+            .sum()
+    }
+
+    /// Time the first gauge spent full, which is gauge gained going to waste. The cap isn't
+    /// sent, so the highest value seen stands in for it.
+    pub fn time_at_cap(&self, end: Instant) -> Duration {
+        let Some(cap) = self.gauge.iter().map(|(_, gauge)| gauge[0]).max() else {
+            return Duration::ZERO;
+        };
+        if cap == 0 {
+            return Duration::ZERO;
+        }
+        let next_times = self.gauge.iter().skip(1).map(|&(i, _)| i);
+        self.gauge
+            .iter()
+            .zip(next_times.chain(std::iter::once(end)))
+            .filter(|((_, gauge), _)| gauge[0] == cap)
+            .map(|(&(start, _), next)| next.saturating_duration_since(start))
+            .sum()
+    }
+}
+
+/// How soon after a player's hit a stagger increase is credited to them.
+const STAGGER_HIT_WINDOW: Duration = Duration::from_millis(500);
+
+/// Stagger history of an NPC.
+#[derive(Debug, Default, Clone)]
+pub struct Stagger {
+    /// Stagger bar updates, in order.
+    pub samples: Vec<StaggerSample>,
+    /// Stagger checks, in order.
+    pub checks: Vec<StaggerCheck>,
+    /// Maps player ID found in [`Environment`] to stagger they dealt.
+    pub contribution: BTreeMap<u64, u64>,
+    /// Last point of each gauge, which increases are measured from.
+    pub last_point: BTreeMap<StaggerGauge, u32>,
+}
+
+/// Stagger gauges of an NPC, which the server reports in separate packets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StaggerGauge {
+    /// Sent in `PktParalyzationStateNotify`.
+    Paralyzation,
+    /// Sent in `PktBlockSkillStateNotify`.
+    BlockSkill,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct StaggerSample {
+    pub time: Instant,
+    pub point: u32,
+    pub max_point: u32,
+}
+
+impl StaggerSample {
+    /// Fill of the stagger bar, from 0 to 1.
+    pub fn fill(&self) -> f32 {
+        if self.max_point == 0 {
+            0.0
+        } else {
+            self.point as f32 / self.max_point as f32
+        }
+    }
+}
+
+/// A window in which an NPC's stagger bar had to be filled.
+#[derive(Debug, Clone)]
+pub struct StaggerCheck {
     pub start: Instant,
-    /// Time recording ended.
+    /// When the bar was filled or the check ran out, if it's over.
     pub end: Option<Instant>,
-    /// Time of first damage dealt, if any.
-    pub first_damage: Option<Instant>,
-    /// Time of last damage dealt before recording ended, if any.
-    pub last_damage: Option<Instant>,
-    /// The environment that referenced entity IDs are valid in.
-    pub environment: usize,
-    /// Maps player ID found in [`Environment`] to their metrics.
-    pub players: BTreeMap<u64, PlayerData>,
-    /// Main target of the encounter, if any.
-    pub tracked: Vec<(u64, Boss)>,
-    /// Whether the encounter ended in failure.
-    pub wipe: bool,
-    /// Whether the encounter ended in success.
-    pub clear: bool,
+    pub max_point: u32,
+    pub success: bool,
 }
 
-impl Default for Encounter {
-    fn default() -> Self {
-        Self {
-            start: Instant::now(),
-            end: None,
-            first_damage: None,
-            last_damage: None,
-            environment: 0,
-            players: BTreeMap::new(),
-            tracked: Vec::new(),
-            wipe: false,
-            clear: false,
-        }
+impl StaggerCheck {
+    /// How long it took to fill the bar, if it was filled.
+    pub fn time_to_stagger(&self) -> Option<Duration> {
+        let end = self.end.filter(|_| self.success)?;
+        Some(end.saturating_duration_since(self.start))
     }
 }
 
-impl Encounter {
-    pub fn duration(&self) -> Duration {
-        self.last_damage
-            .or(self.end)
-            .unwrap_or_else(Instant::now)
-            .duration_since(self.first_damage.unwrap_or(self.start))
+/// A counterattack attempted by a player.
+#[derive(Debug, Clone)]
+pub struct Counter {
+    pub time: Instant,
+    pub target_id: u64,
+    /// Raw counter type sent by the server.
+    pub kind: u32,
+}
+
+impl Counter {
+    /// Counter type of a counterattack that interrupted its target. Other types are sent for
+    /// counter skills that hit the target outside of a window it could be countered in.
+    pub const SUCCESS: u32 = 0;
+
+    pub fn success(&self) -> bool {
+        self.kind == Self::SUCCESS
     }
 }
 
-/// Metrics for a player.
-#[derive(Debug, Default)]
-pub struct PlayerData {
-    /// Total damage dealt by player.
-    pub dmg_dealt: i64,
-    pub hits: u64,
-    pub brand_dmg: i64,
-    pub brand_hits: u64,
-    pub ap_dmg: i64,
-    pub ap_hits: u64,
-    pub ident_dmg: i64,
-    pub ident_hits: i64,
-    /// Map of skill ID to data about that skill.
-    pub skills: BTreeMap<u32, SkillUsage>,
+/// Counterattacks a player attempted, and how many of them succeeded.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CounterCount {
+    pub attempts: usize,
+    pub successes: usize,
+}
 
-    pub damage: Vec<(Instant, i64)>,
-    pub casts: Vec<(Instant, u32)>,
+/// How many incoming hits are kept for each death recap.
+pub const DEATH_RECAP_LEN: usize = 10;
+
+/// A player's death.
+#[derive(Debug, Clone)]
+pub struct Death {
+    pub player_id: u64,
+    pub time: Instant,
+    /// ID of the entity credited with the kill.
+    pub source_id: u64,
+    /// Time the player was seen alive again, if they were.
+    pub revived: Option<Instant>,
+    /// Last hits taken before dying, oldest first.
+    pub recap: Vec<RecapHit>,
+}
+
+impl Death {
+    /// Time spent dead, up to `end` if the player was never revived.
+    pub fn time_dead(&self, end: Instant) -> Duration {
+        self.revived
+            .unwrap_or(end)
+            .saturating_duration_since(self.time)
+    }
+}
+
+/// A hit taken by a player, as shown in a death recap.
+#[derive(Debug, Clone)]
+pub struct RecapHit {
+    pub time: Instant,
+    pub source_id: u64,
+    pub skill_id: u32,
+    /// Name of the skill, if the name exists in the skill database.
+    pub skill: Option<String>,
+    pub damage: i64,
+}
+
+/// Damage taken by a player.
+#[derive(Debug, Default, Clone)]
+pub struct DamageTaken {
+    /// Total damage taken.
+    pub total: i64,
+    /// Map of NPC skill ID to damage taken from that skill.
+    pub skills: BTreeMap<u32, SkillDamageTaken>,
+}
+
+/// Damage taken by a player from a single NPC skill.
+#[derive(Debug, Clone, Default)]
+pub struct SkillDamageTaken {
+    /// Name of the skill, if the name exists in the skill database.
+    pub name: Option<String>,
+    /// How many times this skill hit.
+    pub hits: usize,
+    /// Total damage taken from this skill.
+    pub damage: i64,
+    /// Damage of the largest single hit.
+    pub largest: i64,
+}
+
+impl DamageTaken {
+    fn add(&mut self, skill_id: u32, name: Option<&str>, damage: i64) {
+        self.total += damage;
+        let skill = self
+            .skills
+            .entry(skill_id)
+            .or_insert_with(|| SkillDamageTaken {
+                name: name.map(ToOwned::to_owned),
+                ..Default::default()
+            });
+        skill.hits += 1;
+        skill.damage += damage;
+        skill.largest = skill.largest.max(damage);
+    }
 }
 
 /// Information about a skill used by a player.
@@ -1189,9 +4563,8 @@ pub struct SkillUsage {
     pub back: usize,
     /// How many times this skill registered as a front attack.
     pub front: usize,
-    pub brand: usize,
-    pub ap_buff: usize,
-    pub ident_buff: usize,
+    /// How many times this skill hit under each of [`Encounter::buff_categories`], by index.
+    pub buffed: Vec<usize>,
 
     pub hits: Vec<(Instant, SkillHit)>,
 }
@@ -1203,17 +4576,33 @@ pub struct SkillHit {
     pub is_crit: bool,
     pub is_back_attack: bool,
     pub is_front_attack: bool,
+    /// Whether the hit was dealt under each of [`Encounter::buff_categories`], by index.
+    pub buffed: Vec<bool>,
+    /// Players whose buffs the hit was dealt under, by category index and applicant ID.
+    pub buffed_by: Vec<(usize, u64)>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Npc {
     pub id: u64,
     pub kind: u32,
-    pub name: String,
+    /// Name of the NPC, if the NPC exists in the NPC database.
+    pub name: Option<String>,
+    pub grade: NpcGrade,
+    /// Number of HP bars shown for the NPC in game, if any.
+    pub bars: Option<u16>,
+}
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, serde::Deserialize)]
+pub enum NpcGrade {
+    #[default]
+    Normal,
+    Elite,
+    Boss,
 }
 
 #[allow(dead_code)]
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Projectile {
     id: u64,
     owner_id: u64,
@@ -1257,6 +4646,116 @@ impl SkillData {
     }
 }
 
+#[derive(Debug, serde::Deserialize)]
+struct NpcInfo {
+    name: String,
+    grade: NpcGrade,
+    hp_bars: Option<u16>,
+}
+
+#[derive(Debug, Default, serde::Deserialize)]
+struct NpcData(std::collections::HashMap<u32, NpcInfo>);
+
+impl NpcData {
+    fn load() -> anyhow::Result<Self> {
+        Ok(serde_bare::from_reader(snappy_file_reader(
+            "resources/npcs",
+        )?)?)
+    }
+
+    fn get(&self, id: u32) -> Option<&NpcInfo> {
+        self.0.get(&id)
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Debug, serde::Deserialize)]
+struct StatusEffectInfo {
+    name: String,
+    icon: Option<String>,
+    category: StatusEffectCategory,
+    buff_category: Option<String>,
+    source_skill: Option<u32>,
+    /// Duration in seconds.
+    duration: Option<f32>,
+    /// Stats granted per stack by name, in hundredths of a percent.
+    stats: Vec<(String, i32)>,
+    /// Whether the status effect is a shield, absorbing damage up to its value.
+    shield: bool,
+}
+
+/// Version of `resources/status_effects`, which precedes its status effects. To be bumped
+/// whenever their serialized form changes. Version 1 added stats, version 2 shields.
+const STATUS_EFFECTS_VERSION: u16 = 2;
+
+#[derive(Debug, Default, serde::Deserialize)]
+struct StatusEffectData(std::collections::HashMap<u32, StatusEffectInfo>);
+
+impl StatusEffectData {
+    fn load() -> anyhow::Result<Self> {
+        let (version, effects): (u16, _) =
+            serde_bare::from_reader(snappy_file_reader("resources/status_effects")?)?;
+        if version != STATUS_EFFECTS_VERSION {
+            anyhow::bail!(
+                "resources/status_effects is format version {}, but version {} is needed; \
+                 run the updater to regenerate it",
+                version,
+                STATUS_EFFECTS_VERSION
+            );
+        }
+        Ok(Self(effects))
+    }
+
+    fn get(&self, id: u32) -> Option<&StatusEffectInfo> {
+        self.0.get(&id)
+    }
+}
+
+/// Zone names by zone ID.
+#[derive(Debug, Default, serde::Deserialize)]
+struct ZoneData(std::collections::HashMap<u32, String>);
+
+impl ZoneData {
+    fn load() -> anyhow::Result<Self> {
+        Ok(serde_bare::from_reader(snappy_file_reader(
+            "resources/zones",
+        )?)?)
+    }
+
+    fn get(&self, id: u32) -> Option<&str> {
+        self.0.get(&id).map(String::as_str)
+    }
+}
+
+/// Buff categories that damage dealt under is tracked for, and support identity skills.
+fn load_buff_definitions() -> anyhow::Result<BuffDefinitions> {
+    Ok(serde_bare::from_reader(snappy_file_reader(
+        "resources/buff_categories",
+    )?)?)
+}
+
+/// Boss definitions by NPC type ID.
+struct BossData(std::collections::HashMap<u32, Boss>);
+
+impl BossData {
+    fn load() -> anyhow::Result<Self> {
+        let encounters: Vec<BossEncounter> =
+            serde_bare::from_reader(snappy_file_reader("resources/bosses")?)?;
+        let mut bosses = std::collections::HashMap::new();
+        for encounter in encounters.into_iter().map(Arc::new) {
+            for &npc in &encounter.npcs {
+                let encounter = Arc::clone(&encounter);
+                bosses.insert(npc.id, Boss { npc, encounter });
+            }
+        }
+        Ok(Self(bosses))
+    }
+
+    fn get(&self, id: u32) -> Option<Boss> {
+        self.0.get(&id).cloned()
+    }
+}
+
 impl SkillDamageEvent {
     fn flag(&self) -> anyhow::Result<HitFlag> {
         let raw = self.modifier & 0xf;
@@ -1269,3 +4768,581 @@ impl SkillDamageEvent {
             .with_context(|| format!("damage hit option invalid, value: {}", raw))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn buff(applicant: u64, stacks: u8, bonus: (DamageStat, f64)) -> BuffInfo {
+        BuffInfo {
+            status_effect_id: 0,
+            stacks,
+            applicant,
+            instance_id: 0,
+            scope: StatusEffectScope::Target,
+            expires: None,
+            name: None,
+            categories: Vec::new(),
+            bonuses: vec![bonus],
+            shield: false,
+        }
+    }
+
+    #[test]
+    fn stacked_buffs_are_credited_the_damage_they_added() {
+        let mut live = LiveData::default();
+        live.parties = BTreeMap::from([(1, 7), (2, 7), (3, 7)]);
+        // two stacks of 5% attack power on the attacker, and 20% damage on the target
+        live.buffs
+            .entry(1)
+            .or_default()
+            .insert(100, buff(2, 2, (DamageStat::AttackPower, 0.05)));
+        live.buffs
+            .entry(10)
+            .or_default()
+            .insert(200, buff(3, 1, (DamageStat::Damage, 0.2)));
+
+        // 100 damage without the buffs, 100 * 1.1 * 1.2 with them
+        let contributions = live.buff_contributions(1, 10, Some(7), 132, |id| id < 10);
+        assert_eq!(contributions, [(2, 11), (3, 21)]);
+        let total: i64 = contributions.iter().map(|&(_, damage)| damage).sum();
+        assert_eq!(total, 32);
+    }
+
+    fn effect(
+        status_effect_id: u32,
+        instance_id: u32,
+        value: &[u8],
+    ) -> crate::packet::StatusEffectData<'_> {
+        crate::packet::StatusEffectData {
+            stack_count: 1,
+            skill_level: 1,
+            total_time: 0.0,
+            end_tick: 0,
+            value,
+            effect_instance_id: instance_id,
+            occur_time: 0,
+            source_id: 1,
+            status_effect_id,
+        }
+    }
+
+    #[test]
+    fn shields_are_credited_from_application_to_removal() {
+        let data = Data::new();
+        let mut data = data.lock();
+        let now = Instant::now();
+        let shield = |instance_id| BuffInfo {
+            status_effect_id: 300,
+            instance_id,
+            shield: true,
+            ..buff(1, 1, (DamageStat::Damage, 0.0))
+        };
+        let value = |values: [u64; 2]| -> Vec<u8> {
+            values
+                .iter()
+                .flat_map(|value| value.to_le_bytes())
+                .collect()
+        };
+
+        // the smaller of the two values is the shield's size
+        let applied = value([1000, 1200]);
+        data.add_status_effect(10, &effect(300, 5, &applied), shield(5), now);
+        data.sync_shield(10, 5, 600);
+        // reapplying replaces the shield, expiring what was left of it
+        let reapplied = value([800, 800]);
+        data.add_status_effect(10, &effect(300, 6, &reapplied), shield(6), now);
+        data.remove_status_effect(10, 6, now);
+        // status effects that aren't shields don't count
+        let other = BuffInfo {
+            status_effect_id: 301,
+            instance_id: 7,
+            ..buff(1, 1, (DamageStat::Damage, 0.0))
+        };
+        data.add_status_effect(10, &effect(301, 7, &applied), other, now);
+        data.sync_shield(10, 7, 500);
+        // a value too short to hold the size is only credited once synced
+        data.add_status_effect(10, &effect(300, 8, &[0xff; 8]), shield(8), now);
+        data.sync_shield(10, 8, 300);
+        data.remove_status_effect(10, 8, now);
+
+        let shielding = data.current_enc().shields_given_by(1);
+        assert_eq!(
+            (shielding.given, shielding.absorbed, shielding.expired),
+            (2100, 400, 1700)
+        );
+        assert!(data.live.shields.is_empty());
+    }
+
+    #[test]
+    fn the_same_buff_from_two_players_is_credited_to_each() {
+        let data = Data::new();
+        let mut data = data.lock();
+        data.live.parties = BTreeMap::from([(1, 7), (2, 7), (3, 7)]);
+        let now = Instant::now();
+        let applied = |applicant, instance_id| {
+            let raw = crate::packet::StatusEffectData {
+                source_id: applicant,
+                ..effect(100, instance_id, &[])
+            };
+            let info = BuffInfo {
+                status_effect_id: 100,
+                instance_id,
+                ..buff(applicant, 1, (DamageStat::AttackPower, 0.1))
+            };
+            (raw, info)
+        };
+
+        let (raw, info) = applied(2, 5);
+        data.add_status_effect(1, &raw, info, now);
+        let (raw, info) = applied(3, 6);
+        data.add_status_effect(1, &raw, info, now);
+        // 100 damage without the buffs, 100 * 1.2 with them
+        let contributions = data
+            .live
+            .buff_contributions(1, 10, Some(7), 120, |id| id < 10);
+        assert_eq!(contributions, [(2, 10), (3, 10)]);
+
+        // removing the first application leaves the second one up
+        data.remove_status_effect(1, 5, now);
+        let contributions = data
+            .live
+            .buff_contributions(1, 10, Some(7), 110, |id| id < 10);
+        assert_eq!(contributions, [(3, 10)]);
+
+        // a new application from the same player replaces their previous one
+        let (raw, info) = applied(3, 7);
+        data.add_status_effect(1, &raw, info, now);
+        let instances: Vec<_> = data.live.buffs[&1].keys().copied().collect();
+        assert_eq!(instances, [7]);
+    }
+    #[test]
+    fn passive_status_effects_stay_apart_from_targeted_ones() {
+        use StatusEffectScope::{Party, Passive, Target};
+
+        let data = Data::new();
+        let mut data = data.lock();
+        data.live.parties = BTreeMap::from([(1, 7), (2, 7)]);
+        let now = Instant::now();
+        let info = |scope, instance_id| BuffInfo {
+            status_effect_id: 500,
+            instance_id,
+            scope,
+            ..buff(1, 1, (DamageStat::Damage, 0.1))
+        };
+        let scopes = |data: &Data, id| -> Vec<_> {
+            data.live
+                .active_buffs(id, true)
+                .map(|info| info.scope)
+                .collect()
+        };
+
+        // one status effect ID, applied as a targeted effect, a passive and a party passive
+        data.add_status_effect(1, &effect(500, 5, &[]), info(Target, 5), now);
+        data.add_passive_status_effect(&[1], 500, info(Passive, 0), now);
+        data.add_passive_status_effect(&[1, 2], 500, info(Party, 0), now);
+        assert_eq!(scopes(&data, 1), [Target, Passive, Party]);
+        assert_eq!(scopes(&data, 2), [Party]);
+
+        data.remove_status_effect(1, 5, now);
+        assert_eq!(scopes(&data, 1), [Passive, Party]);
+        data.remove_passive_status_effect(500, 1, Passive, now);
+        assert_eq!(scopes(&data, 1), [Party]);
+        assert_eq!(scopes(&data, 2), [Party]);
+
+        let open: Vec<_> = data
+            .current_enc()
+            .status_effects_on(1)
+            .iter()
+            .filter(|effect| effect.end.is_none())
+            .map(|effect| effect.scope)
+            .collect();
+        assert_eq!(open, [Party]);
+    }
+
+    #[test]
+    fn zone_status_effects_only_apply_to_their_target() {
+        let data = Data::new();
+        let mut data = data.lock();
+        let now = Instant::now();
+        let zone = |id, target| BuffInfo {
+            status_effect_id: id,
+            scope: StatusEffectScope::Zone(target),
+            ..buff(0, 1, (DamageStat::Damage, 0.5))
+        };
+        data.add_zone_status_effect(zone(600, ZoneTarget::Npcs), now);
+        data.add_zone_status_effect(zone(601, ZoneTarget::Players), now);
+
+        let ids = |data: &Data, id, is_player| -> Vec<_> {
+            data.live
+                .active_buffs(id, is_player)
+                .map(|info| info.status_effect_id)
+                .collect()
+        };
+        assert_eq!(ids(&data, 1, true), [601]);
+        assert_eq!(ids(&data, 10, false), [600]);
+
+        let enc = data.current_enc_mut();
+        enc.first_damage = Some(now);
+        enc.last_damage = Some(now + Duration::from_secs(10));
+        enc.players.insert(1, PlayerData::default());
+        data.remove_zone_status_effect(600, now + Duration::from_secs(5));
+        assert!(data.live.zone_buffs.contains_key(&601));
+        assert!(!data.live.zone_buffs.contains_key(&600));
+
+        let enc = data.current_enc();
+        assert_eq!(enc.uptime(1, |_| true), 1.0);
+        assert_eq!(enc.status_effect_uptime(1, 600), 0.0);
+        assert_eq!(enc.status_effect_uptime(10, 600), 0.5);
+        assert_eq!(enc.status_effect_uptime(10, 601), 0.0);
+        assert_eq!(enc.zone_status_effect_uptime(600), 0.5);
+    }
+
+    #[test]
+    fn stagger_gauges_are_credited_separately() {
+        let data = Data::new();
+        let mut data = data.lock();
+        let now = Instant::now();
+        let enc = data.current_enc_mut();
+        let updates = [
+            (StaggerGauge::Paralyzation, 100, 1),
+            (StaggerGauge::BlockSkill, 30, 2),
+            (StaggerGauge::Paralyzation, 150, 1),
+            (StaggerGauge::BlockSkill, 40, 2),
+            (StaggerGauge::Paralyzation, 120, 1),
+            (StaggerGauge::BlockSkill, 45, 2),
+        ];
+        for (gauge, point, hitter) in updates {
+            let max_point = match gauge {
+                StaggerGauge::Paralyzation => 1000,
+                StaggerGauge::BlockSkill => 50,
+            };
+            let sample = StaggerSample {
+                time: now,
+                point,
+                max_point,
+            };
+            enc.record_stagger(20, gauge, sample, true, Some(hitter));
+        }
+
+        // the first sample of each gauge isn't credited to anyone
+        let stagger = &enc.stagger[&20];
+        assert_eq!(stagger.contribution, BTreeMap::from([(1, 50), (2, 15)]));
+        assert_eq!(stagger.samples.len(), updates.len());
+    }
+
+    #[test]
+    fn refreshed_buffs_are_credited_until_their_grace_runs_out() {
+        let mut live = LiveData::default();
+        live.parties = BTreeMap::from([(1, 7), (2, 7)]);
+        let now = Instant::now();
+        let secs = |secs: f32| now + Duration::from_secs_f32(secs);
+        // applied at tick 10000 for 10s, then refreshed to run 5s longer
+        let raw = crate::packet::StatusEffectData {
+            total_time: 10.0,
+            end_tick: 20_000,
+            source_id: 2,
+            ..effect(400, 5, &[])
+        };
+        let info = BuffInfo {
+            status_effect_id: 400,
+            instance_id: 5,
+            expires: Some(secs(10.0)),
+            ..buff(2, 1, (DamageStat::Damage, 0.2))
+        };
+        live.add_buff(10, &raw, info, now);
+        live.refresh_buff(10, 5, 25_000);
+        assert_eq!(live.buffs[&10][&5].expires, Some(secs(15.0)));
+
+        // past the original expiration, and within the grace after the refreshed one
+        assert!(live.take_expired_buffs(secs(10.5)).is_empty());
+        assert!(live
+            .take_expired_buffs(secs(15.0) + BUFF_EXPIRY_GRACE / 2)
+            .is_empty());
+        let credited = live.buff_contributions(1, 10, Some(7), 120, |id| id < 10);
+        assert_eq!(credited, [(2, 20)]);
+
+        let expired = live.take_expired_buffs(secs(15.0) + BUFF_EXPIRY_GRACE);
+        assert_eq!(expired, [(10, 5, secs(15.0))]);
+        assert!(live.buffs.is_empty());
+        assert!(live
+            .buff_contributions(1, 10, Some(7), 120, |id| id < 10)
+            .is_empty());
+    }
+
+    #[test]
+    fn uptime_counts_overlapping_status_effects_once() {
+        let now = Instant::now();
+        let secs = |secs| now + Duration::from_secs(secs);
+        let interval = |id, from, to: Option<u64>| StatusEffectInterval {
+            end: to.map(secs),
+            ..StatusEffectInterval::passive(id, &buff(2, 1, (DamageStat::Damage, 0.1)), secs(from))
+        };
+        let mut enc = Encounter {
+            start: now,
+            first_damage: Some(secs(2)),
+            last_damage: Some(secs(12)),
+            ..Default::default()
+        };
+        enc.status_effects.insert(
+            1,
+            vec![
+                // overlapping, and starting before the first damage
+                interval(700, 0, Some(6)),
+                interval(700, 4, Some(8)),
+                // adjacent
+                interval(701, 2, Some(5)),
+                interval(701, 5, Some(7)),
+                // still applied
+                interval(702, 10, None),
+            ],
+        );
+        assert_eq!(enc.status_effect_uptime(1, 700), 0.6);
+        assert_eq!(enc.status_effect_uptime(1, 701), 0.5);
+        assert_eq!(enc.status_effect_uptime(1, 702), 0.2);
+        assert_eq!(enc.uptime(1, |_| true), 0.8);
+
+        // carried over into the next encounter, from its start
+        let (status_effects, _) = enc.split_status_effects(secs(14));
+        let next = Encounter {
+            start: secs(14),
+            first_damage: Some(secs(15)),
+            last_damage: Some(secs(20)),
+            status_effects,
+            ..Default::default()
+        };
+        assert_eq!(enc.status_effect_uptime(1, 702), 0.2);
+        assert_eq!(next.status_effect_uptime(1, 702), 1.0);
+        assert_eq!(next.status_effect_uptime(1, 700), 0.0);
+    }
+
+    #[test]
+    fn party_coverage_counts_the_party_of_the_applicant() {
+        let player = |dmg_dealt, buffed: i64| PlayerData {
+            dmg_dealt,
+            buffed_by: BTreeMap::from([(
+                (0, 2),
+                BuffedDamage {
+                    hits: 1,
+                    damage: buffed,
+                },
+            )]),
+            ..Default::default()
+        };
+        let mut enc = Encounter::default();
+        enc.players.insert(1, player(100, 40));
+        enc.players.insert(2, player(100, 100));
+        enc.players.insert(3, player(1000, 500));
+        let parties = BTreeMap::from([(1, 7), (2, 7), (3, 8)]);
+
+        // player 3 is in another party
+        assert_eq!(enc.party_coverage(2, 0, &parties), 0.7);
+        assert_eq!(enc.party_coverage(2, 1, &parties), 0.0);
+        // without a known party, only their own damage counts
+        assert_eq!(enc.party_coverage(2, 0, &BTreeMap::new()), 1.0);
+    }
+
+    #[test]
+    fn hits_are_counted_under_the_categories_of_their_buffs() {
+        use crate::definitions::StatusEffectCategory::{Buff, Debuff};
+
+        let category = |name: &str, holder, kind, stats: &[&str]| BuffCategory {
+            name: name.into(),
+            holder,
+            ids: Vec::new(),
+            buff_categories: vec!["classskill".into()],
+            kind: Some(kind),
+            stats: stats.iter().map(|&stat| stat.into()).collect(),
+            supports_only: true,
+        };
+        let definitions = BuffDefinitions {
+            categories: vec![
+                category("AP", BuffHolder::Attacker, Buff, &["attack_power_rate"]),
+                category("Brand", BuffHolder::Target, Debuff, &[]),
+                BuffCategory {
+                    ids: vec![900],
+                    buff_categories: Vec::new(),
+                    supports_only: false,
+                    ..category("Item", BuffHolder::Attacker, Buff, &[])
+                },
+                BuffCategory {
+                    supports_only: false,
+                    ..category("Crit", BuffHolder::Attacker, Buff, &["critical_hit_rate"])
+                },
+            ],
+            support_identity_skills: Vec::new(),
+        };
+        let categories = &definitions.categories;
+        let info = |category, stat: &str| StatusEffectInfo {
+            name: String::new(),
+            icon: None,
+            category,
+            buff_category: Some("classskill".into()),
+            source_skill: Some(1),
+            duration: None,
+            stats: vec![(stat.into(), 1000)],
+            shield: false,
+        };
+        let buff_info = info(Buff, "attack_power_rate");
+        let debuff_info = info(Debuff, "physical_inc_rate");
+        let crit_info = info(Buff, "critical_hit_rate");
+        assert_eq!(categories_of(categories, 800, Some(&buff_info), true), [0]);
+        // other stats don't count towards attack power, from anyone
+        assert_eq!(categories_of(categories, 802, Some(&crit_info), true), [3]);
+        assert_eq!(categories_of(categories, 802, Some(&crit_info), false), [3]);
+        assert_eq!(
+            categories_of(categories, 801, Some(&debuff_info), true),
+            [1]
+        );
+        assert!(categories_of(categories, 800, Some(&buff_info), false).is_empty());
+        assert_eq!(categories_of(categories, 900, None, false), [2]);
+
+        let mut live = LiveData {
+            buff_categories: definitions.categories.into(),
+            ..Default::default()
+        };
+        live.parties = BTreeMap::from([(1, 7), (2, 7), (3, 7), (4, 8), (5, 7)]);
+        let mut add = |target_id, instance_id, applicant, id, info, from_support| {
+            let categories = categories_of(&live.buff_categories, id, info, from_support);
+            let info = BuffInfo {
+                status_effect_id: id,
+                instance_id,
+                categories,
+                ..buff(applicant, 1, (DamageStat::Damage, 0.0))
+            };
+            live.buffs
+                .entry(target_id)
+                .or_default()
+                .insert(instance_id, info);
+        };
+        // on the attacker, a support's buff and the same buff from a non-support
+        add(1, 1, 2, 800, Some(&buff_info), true);
+        add(1, 2, 5, 800, Some(&buff_info), false);
+        // on the target, debuffs from the attacker's party and another
+        add(10, 3, 3, 801, Some(&debuff_info), true);
+        add(10, 4, 4, 801, Some(&debuff_info), true);
+        // a debuff on the attacker doesn't count, having to be held by the target
+        add(1, 5, 3, 801, Some(&debuff_info), true);
+        add(1, 6, 5, 802, Some(&crit_info), false);
+
+        let active = live.hit_categories(1, 10, Some(7), |id| id < 10);
+        assert_eq!(active, [vec![2], vec![3], vec![], vec![5]]);
+    }
+
+    /// A meter without any game data, recording to `data`.
+    fn meter(data: &Arc<Mutex<Data>>, support_identity_skills: Vec<u32>) -> Meter {
+        use std::collections::HashMap;
+
+        Meter {
+            ui_ctx: egui::Context::default(),
+            data: Arc::clone(data),
+            skill_data: SkillData(HashMap::new()),
+            npc_data: NpcData(HashMap::new()),
+            status_effect_data: StatusEffectData(HashMap::new()),
+            boss_data: BossData(HashMap::new()),
+            zone_data: ZoneData(HashMap::new()),
+            buff_categories: Arc::new([]),
+            support_identity_skills,
+
+            #[cfg(feature = "packet_logging")]
+            log: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn identity_packets_are_recorded_for_players() {
+        let data = Data::new();
+        let mut meter = meter(&data, vec![21140]);
+        data.lock()
+            .current_env_mut()
+            .players
+            .insert(1, Player::default());
+
+        let gauge = |player_id, identity_gauge1| PktIdentityGaugeChangeNotify {
+            player_id,
+            identity_gauge1,
+            identity_gauge2: 0,
+            identity_gauge3: 0,
+        };
+        for (player_id, value) in [(1, 0), (1, 100), (1, 300), (1, 300), (1, 50), (10, 300)] {
+            meter
+                .on_identity_gauge_change_notify(gauge(player_id, value))
+                .unwrap();
+        }
+        for (object_id, stance) in [(1, 1), (1, 0), (10, 1)] {
+            let pkt = PktIdentityStanceChangeNotify { object_id, stance };
+            meter.on_identity_stance_change_notify(pkt).unwrap();
+        }
+        // only support identity ultimates count
+        for (source_id, skill_id) in [(1, 21140), (1, 21000), (10, 21140)] {
+            let pkt = PktSkillStartNotify {
+                source_id,
+                skill_id,
+            };
+            meter.on_skill_start_notify(pkt).unwrap();
+        }
+
+        let mut data = data.lock();
+        let enc = data.current_enc_mut();
+        assert_eq!(enc.identity.keys().collect::<Vec<_>>(), [&1]);
+        let identity = enc.identity.get_mut(&1).unwrap();
+        let values: Vec<_> = identity.gauge.iter().map(|(_, gauge)| gauge[0]).collect();
+        assert_eq!(values, [0, 100, 300, 300, 50]);
+        let ultimates: Vec<_> = identity.ultimates.iter().map(|&(_, id)| id).collect();
+        assert_eq!(ultimates, [21140]);
+
+        // a second apart each, for the durations to be known
+        let now = Instant::now();
+        let secs = |secs| now + Duration::from_secs(secs);
+        for (i, (time, _)) in identity.gauge.iter_mut().enumerate() {
+            *time = secs(i as u64);
+        }
+        identity.stances[0].0 = secs(1);
+        identity.stances[1].0 = secs(4);
+        assert_eq!(identity.time_at_cap(secs(5)), Duration::from_secs(2));
+        assert_eq!(identity.time_in_identity(secs(5)), Duration::from_secs(3));
+        let stances = identity.time_in_stances(secs(5));
+        assert_eq!(stances[&0], Duration::from_secs(1));
+    }
+
+    #[test]
+    fn counter_attempts_and_successes_are_counted() {
+        let data = Data::new();
+        let mut meter = meter(&data, Vec::new());
+        for id in [1, 2] {
+            data.lock()
+                .current_env_mut()
+                .players
+                .insert(id, Player::default());
+        }
+
+        for (source_id, kind) in [(1, Counter::SUCCESS), (1, 2), (1, Counter::SUCCESS), (2, 2)] {
+            let pkt = PktCounterAttackNotify {
+                target_id: 20,
+                source_id,
+                r#type: kind,
+            };
+            meter.on_counter_attack_notify(pkt).unwrap();
+        }
+        // counters of non-players are ignored
+        let pkt = PktCounterAttackNotify {
+            target_id: 1,
+            source_id: 20,
+            r#type: Counter::SUCCESS,
+        };
+        meter.on_counter_attack_notify(pkt).unwrap();
+
+        let data = data.lock();
+        let enc = data.current_enc();
+        let count = |attempts, successes| CounterCount {
+            attempts,
+            successes,
+        };
+        assert_eq!(enc.counter_count(1), count(3, 2));
+        assert_eq!(enc.counter_count(2), count(1, 0));
+        assert_eq!(enc.counter_count(20), count(0, 0));
+        assert!(enc.counters[&1]
+            .iter()
+            .all(|counter| counter.target_id == 20));
+    }
+}
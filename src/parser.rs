@@ -2,10 +2,6 @@
 
 use anyhow::Context;
 
-use crate::definitions::{
-    MoveOptionData, SkillMoveOptionData, SkillOptionData, TripodIndex, TripodLevel,
-};
-
 pub trait Packet {
     const OPCODE: crate::generated::opcode::Opcode;
 }
@@ -229,6 +225,19 @@ impl<'a> Parser<'a> {
         Ok(v)
     }
 
+    /// Read exactly `count` `T`s, with no length prefix.
+    pub fn read_repeated<'bump, T: Event<'bump>>(
+        &mut self,
+        bump: &'bump bumpalo::Bump,
+        count: usize,
+    ) -> anyhow::Result<BumpVec<'bump, T::Out>> {
+        let mut v = BumpVec::new_in(bump);
+        for _ in 0..count {
+            v.push(T::parse(self, bump)?);
+        }
+        Ok(v)
+    }
+
     /// Read `len * mult` bytes if `len <= max_len`.
     pub fn read_bytes<'bump>(
         &mut self,
@@ -246,6 +255,32 @@ impl<'a> Parser<'a> {
         Ok(())
     }
 
+    /// Read `len` of type `L` followed by `len * mult` bytes if `len <= max_len`, copying them
+    /// into the bump allocation.
+    pub fn read_kinded_bytes<'bump, L>(
+        &mut self,
+        bump: &'bump bumpalo::Bump,
+        multiplier: usize,
+        max_len: usize,
+    ) -> anyhow::Result<&'bump [u8]>
+    where
+        L: Event<'bump>,
+        L::Out: TryInto<usize>,
+    {
+        let len = L::parse(self, bump)?
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("can't convert to usize"))?;
+        if len > max_len {
+            return Ok(&[]);
+        }
+        let bytes = self
+            .0
+            .get(0..len * multiplier)
+            .context("not enough bytes to read bytes")?;
+        self.0 = &self.0[len * multiplier..];
+        Ok(bump.alloc_slice_copy(bytes))
+    }
+
     /// Parse a `bool` followed by conditional `T` into an [`Option<T>`].
     pub fn read_optional<'bump, T: Event<'bump>>(
         &mut self,
@@ -350,125 +385,6 @@ impl<'bump, T: Event<'bump, Out = T>, const N: usize> Event<'bump> for [T; N] {
     }
 }
 
-impl Event<'_> for SkillOptionData {
-    fn parse(parser: &mut Parser, bump: &bumpalo::Bump) -> anyhow::Result<Self> {
-        let mut data = Self::default();
-        let flag = parser.read_u8()?;
-        if (flag & 1) != 0 {
-            data.layer_index = Some(parser.read_u8()?);
-        }
-        if ((flag >> 1) & 1) != 0 {
-            data.start_stage_index = Some(parser.read_u8()?);
-        }
-        if ((flag >> 2) & 1) != 0 {
-            data.transit_index = Some(parser.read_u32()?);
-        }
-        if ((flag >> 3) & 1) != 0 {
-            data.stage_start_time = Some(parser.read_u32()?);
-        }
-        if ((flag >> 4) & 1) != 0 {
-            data.farmost_dist = Some(parser.read_u32()?);
-        }
-        if ((flag >> 5) & 1) != 0 {
-            data.tripod_index = Some(TripodIndex::parse(parser, bump)?);
-        }
-        if ((flag >> 6) & 1) != 0 {
-            data.tripod_level = Some(TripodLevel::parse(parser, bump)?);
-        }
-        Ok(data)
-    }
-}
-
-impl Event<'_> for SkillMoveOptionData {
-    fn parse(parser: &mut Parser, _: &bumpalo::Bump) -> anyhow::Result<Self> {
-        let mut data = Self::default();
-        let flag = parser.read_u8()?;
-        if (flag & 1) != 0 {
-            data.move_time = Some(parser.read_u32()?);
-        }
-        if ((flag >> 1) & 1) != 0 {
-            data.stand_up_time = Some(parser.read_u32()?);
-        }
-        if ((flag >> 2) & 1) != 0 {
-            data.down_time = Some(parser.read_u32()?);
-        }
-        if ((flag >> 3) & 1) != 0 {
-            data.freeze_time = Some(parser.read_u32()?);
-        }
-        if ((flag >> 4) & 1) != 0 {
-            data.move_height = Some(parser.read_u32()?);
-        }
-        if ((flag >> 5) & 1) != 0 {
-            data.farmost_dist = Some(parser.read_u32()?);
-        }
-        if ((flag >> 6) & 1) != 0 {
-            let count = parser.read_u16()?;
-            if count <= 6 {
-                parser.skip(count.into())?;
-            }
-        }
-        Ok(data)
-    }
-}
-
-impl Event<'_> for MoveOptionData {
-    fn parse(parser: &mut Parser, _: &bumpalo::Bump) -> anyhow::Result<Self> {
-        let mut data = Self::default();
-        let flag = parser.read_u8()?;
-        if (flag & 1) != 0 {
-            data.modifier = Some(parser.read_u8()?);
-        }
-        if ((flag >> 1) & 1) != 0 {
-            data.speed = Some(parser.read_u32()?);
-        }
-        if ((flag >> 2) & 1) != 0 {
-            data.next_pos = Some(parser.read_u64()?);
-        }
-        if ((flag >> 3) & 1) != 0 {
-            parser.read_u32()?;
-        }
-        if ((flag >> 4) & 1) != 0 {
-            let count = parser.read_u16()?;
-            if count <= 4 {
-                parser.skip(count.into())?;
-            }
-        }
-        if ((flag >> 5) & 1) != 0 {
-            let count = parser.read_u16()?;
-            if count <= 5 {
-                parser.skip(count.into())?;
-            }
-        }
-        if ((flag >> 6) & 1) != 0 {
-            let count = parser.read_u16()?;
-            if count <= 6 {
-                parser.skip(count.into())?;
-            }
-        }
-        Ok(data)
-    }
-}
-
-impl Event<'_> for TripodIndex {
-    fn parse(parser: &mut Parser, _: &bumpalo::Bump) -> anyhow::Result<Self> {
-        Ok(Self {
-            first: parser.read_u8()?,
-            second: parser.read_u8()?,
-            third: parser.read_u8()?,
-        })
-    }
-}
-
-impl Event<'_> for TripodLevel {
-    fn parse(parser: &mut Parser, _: &bumpalo::Bump) -> anyhow::Result<Self> {
-        Ok(Self {
-            first: parser.read_u16()?,
-            second: parser.read_u16()?,
-            third: parser.read_u16()?,
-        })
-    }
-}
-
 /// Representation of an archetype of common internal packet structures.
 ///
 /// Such structures consist of a `length` (of varying width -- usually u16,
@@ -0,0 +1,530 @@
+//! Saving of finished encounters to disk as processed [`Log`]s, and reading them back.
+
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::{BufRead as _, BufReader, BufWriter, Read as _, Write as _},
+    path::{Path, PathBuf},
+    sync::mpsc,
+    thread::{self, JoinHandle},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Context;
+
+use crate::meter::{
+    log::{self, Header, Log, Summary},
+    Encounter, Environment, Zone,
+};
+
+/// Start of a Snappy frame stream, which headerless logs begin with.
+const SNAPPY_STREAM_ID: &[u8] = b"\xff\x06\x00\x00sNaPpY";
+
+/// Which encounters get saved, and where.
+#[derive(Debug, Clone)]
+pub struct LogConfig {
+    /// Directory logs are written to, created if missing.
+    pub dir: PathBuf,
+    /// Encounters shorter than this aren't saved.
+    pub min_duration: Duration,
+    /// Only save encounters that tracked a boss.
+    pub bosses_only: bool,
+    /// Game client build recorded in log headers.
+    pub client_build: Option<String>,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            dir: PathBuf::from("proc_logs"),
+            min_duration: Duration::from_secs(5),
+            bosses_only: true,
+            client_build: None,
+        }
+    }
+}
+
+impl LogConfig {
+    /// Default configuration, overridden by the `LARPS_LOG_DIR`, `LARPS_LOG_MIN_DURATION`
+    /// (seconds), `LARPS_LOG_ALL` and `LARPS_CLIENT_BUILD` environment variables.
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+        if let Some(dir) = std::env::var_os("LARPS_LOG_DIR") {
+            config.dir = PathBuf::from(dir);
+        }
+        if let Ok(secs) = std::env::var("LARPS_LOG_MIN_DURATION") {
+            match secs.parse() {
+                Ok(secs) => config.min_duration = Duration::from_secs(secs),
+                Err(e) => println!("ignoring invalid LARPS_LOG_MIN_DURATION: {}", e),
+            }
+        }
+        if std::env::var_os("LARPS_LOG_ALL").is_some() {
+            config.bosses_only = false;
+        }
+        config.client_build = std::env::var("LARPS_CLIENT_BUILD").ok();
+        config
+    }
+
+    /// Whether an encounter passes the configured filters.
+    pub fn accepts(&self, enc: &Encounter) -> bool {
+        enc.first_damage.is_some()
+            && !enc.players.is_empty()
+            && enc.duration() >= self.min_duration
+            && (!self.bosses_only || !enc.tracked.is_empty())
+    }
+}
+
+/// Subdirectory of the log directory raid runs are written to.
+const RUNS_DIR: &str = "runs";
+
+/// Something for the writer thread to save.
+enum Job {
+    /// A copy of a finished encounter, turned into a [`Log`] on the writer thread.
+    Encounter(
+        String,
+        Summary,
+        Box<(Encounter, Environment, BTreeMap<u64, u32>)>,
+    ),
+    Run(String, log::Run),
+}
+
+/// Handle to a background thread that builds and writes logs, so that processing packets
+/// never waits on it or on disk IO.
+pub struct LogWriter {
+    config: LogConfig,
+    tx: mpsc::Sender<Job>,
+    thread: JoinHandle<()>,
+}
+
+impl LogWriter {
+    pub fn spawn(config: LogConfig) -> Self {
+        let (tx, rx) = mpsc::channel::<Job>();
+        let dir = config.dir.clone();
+        let client_build = config.client_build.clone();
+        let thread = thread::spawn(move || {
+            for job in rx {
+                let (kind, name, result) = match job {
+                    Job::Encounter(name, summary, copy) => {
+                        let (enc, env, parties) = *copy;
+                        let Some(log) = Log::from_encounter(&enc, &env, &parties) else {
+                            continue;
+                        };
+                        let header = Header::new(client_build.clone());
+                        let result = write_file(&dir, &name, &(header, summary), &log);
+                        ("log", name, result)
+                    }
+                    Job::Run(name, run) => {
+                        let header = Header::run(client_build.clone());
+                        let result = write_file(&dir.join(RUNS_DIR), &name, &header, &run);
+                        ("raid run", name, result)
+                    }
+                };
+                match result {
+                    Ok(path) => println!("wrote processed {}: {}", kind, path.display()),
+                    Err(e) => println!("error writing processed {} {}: {}", kind, name, e),
+                }
+            }
+        });
+        Self { config, tx, thread }
+    }
+
+    /// Stop accepting work and wait for everything queued to be written.
+    pub fn close(self) {
+        drop(self.tx);
+        if self.thread.join().is_err() {
+            println!("log writer thread panicked, some logs may not have been saved");
+        }
+    }
+
+    pub fn config(&self) -> &LogConfig {
+        &self.config
+    }
+
+    /// Queue a finished encounter to be saved, if it passes the configured filters. `parties`
+    /// maps player IDs to their party.
+    pub fn save(
+        &self,
+        enc: &Encounter,
+        env: &Environment,
+        parties: &BTreeMap<u64, u32>,
+        name: Option<&str>,
+    ) {
+        if !self.config.accepts(enc) {
+            return;
+        }
+        let now = SystemTime::now();
+        let summary = Summary {
+            saved_at: unix_secs(now),
+            zone: enc.zone.as_ref().map(Zone::display_name),
+            boss: enc.tracked.first().map(|(_, boss)| boss.name()),
+            difficulty: enc.difficulty(),
+            duration: enc.duration().as_millis().try_into().unwrap_or(u64::MAX),
+        };
+        let file_name = file_name(now, name, enc);
+        let copy = Box::new((enc.clone(), env.clone(), parties.clone()));
+        if self
+            .tx
+            .send(Job::Encounter(file_name, summary, copy))
+            .is_err()
+        {
+            println!("log writer thread is gone, encounter not saved");
+        }
+    }
+
+    /// Queue a raid run to be saved, named `<unix seconds>_<raid id>`.
+    pub fn save_run(&self, run: log::Run) {
+        let name = format!("{}_{}", run.started_at, run.raid_id);
+        if self.tx.send(Job::Run(name, run)).is_err() {
+            println!("log writer thread is gone, raid run not saved");
+        }
+    }
+}
+
+/// `<unix seconds>_<encounter name>[_<difficulty>]`, with characters unsuitable for file names
+/// replaced.
+fn file_name(time: SystemTime, name: Option<&str>, enc: &Encounter) -> String {
+    let mut file_name = format!("{}_{}", unix_secs(time), name.unwrap_or("unknown"));
+    if let Some(difficulty) = enc.difficulty() {
+        file_name.push('_');
+        file_name.push_str(difficulty.name());
+    }
+    file_name
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '_' | '-' | '(' | ')' => c,
+            _ => '_',
+        })
+        .collect()
+}
+
+fn unix_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Write an uncompressed preamble and compressed body to a temporary file then rename it into
+/// place, so a crash mid-write never leaves a truncated log behind.
+fn write_file(
+    dir: &Path,
+    name: &str,
+    preamble: &impl serde::Serialize,
+    body: &impl serde::Serialize,
+) -> anyhow::Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+    let mut path = dir.join(name);
+    let mut n = 1;
+    while path.exists() {
+        path = dir.join(format!("{}_{}", name, n));
+        n += 1;
+    }
+    let tmp_path = path.with_extension("tmp");
+
+    let result = (|| -> anyhow::Result<()> {
+        let mut w = BufWriter::new(File::create(&tmp_path)?);
+        serde_bare::to_writer(&mut w, preamble)?;
+        let mut w = snap::write::FrameEncoder::new(w);
+        serde_bare::to_writer(&mut w, body)?;
+        w.flush()?;
+        let f = w
+            .into_inner()
+            .map_err(|e| e.into_error())?
+            .into_inner()
+            .map_err(|e| e.into_error())?;
+        f.sync_all()?;
+        Ok(())
+    })();
+    if let Err(e) = result {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(e);
+    }
+
+    fs::rename(&tmp_path, &path)?;
+    Ok(path)
+}
+
+/// Paths of the saved logs in `dir`, most recent first.
+pub fn list_logs(dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let mut logs = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if !entry.file_type()?.is_file() || path.extension().is_some_and(|ext| ext == "tmp") {
+            continue;
+        }
+        logs.push((entry.metadata()?.modified()?, path));
+    }
+    logs.sort_by(|(a, _), (b, _)| b.cmp(a));
+    Ok(logs.into_iter().map(|(_, path)| path).collect())
+}
+
+/// Paths of the saved raid runs for logs in `dir`, most recent first.
+pub fn list_runs(dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let dir = dir.join(RUNS_DIR);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    list_logs(&dir)
+}
+
+/// Read a raid run previously written by a [`LogWriter`].
+pub fn read_run(path: &Path) -> anyhow::Result<log::Run> {
+    let mut r = BufReader::new(File::open(path)?);
+    let header: Header =
+        serde_bare::from_reader((&mut r).take(1024)).context("raid run header is corrupt")?;
+    anyhow::ensure!(header.magic == log::RUN_MAGIC, "not a raid run file");
+    anyhow::ensure!(
+        header.version == log::RUN_VERSION,
+        "raid run format version {} isn't supported by this meter",
+        header.version
+    );
+    let run = serde_bare::from_reader(snap::read::FrameDecoder::new(r))
+        .context("raid run is truncated or corrupt")?;
+    Ok(run)
+}
+
+/// Read the format version and, for logs with a [`Header`], the [`Summary`] of a log, leaving
+/// `r` at the start of the compressed body.
+fn read_preamble(r: &mut BufReader<File>) -> anyhow::Result<(u16, Option<Summary>)> {
+    let start = r.fill_buf()?;
+    if start.starts_with(SNAPPY_STREAM_ID) {
+        return Ok((0, None));
+    }
+    anyhow::ensure!(start.starts_with(&log::MAGIC), "not a log file");
+    // bound the preamble so a corrupt length can't run off into the body
+    let header: Header =
+        serde_bare::from_reader(r.by_ref().take(1024)).context("log header is corrupt")?;
+    let summary =
+        serde_bare::from_reader(r.by_ref().take(1024)).context("log summary is corrupt")?;
+    Ok((header.version, Some(summary)))
+}
+
+/// Read only the [`Summary`] of a log, which headerless logs don't have.
+pub fn read_summary(path: &Path) -> anyhow::Result<Option<Summary>> {
+    let mut r = BufReader::new(File::open(path)?);
+    Ok(read_preamble(&mut r)?.1)
+}
+
+/// Read a log previously written by a [`LogWriter`], upgrading it from older formats.
+pub fn read_log(path: &Path) -> anyhow::Result<Log> {
+    let mut r = BufReader::new(File::open(path)?);
+    let (version, _) = read_preamble(&mut r)?;
+
+    let mut body = Vec::new();
+    snap::read::FrameDecoder::new(r)
+        .read_to_end(&mut body)
+        .context("log is truncated or corrupt")?;
+    let log = Log::from_versioned(version, &body)
+        .with_context(|| format!("reading format version {} log body", version))?;
+    log.validate()?;
+    Ok(log)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A file in a directory of its own under the system temp dir.
+    fn temp_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("larps-persist-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir.join(name)
+    }
+
+    fn empty_log() -> Log {
+        let enc = Encounter {
+            end: Some(std::time::Instant::now()),
+            ..Default::default()
+        };
+        Log::from_encounter(&enc, &Environment::new(), &BTreeMap::new()).unwrap()
+    }
+
+    fn summary() -> Summary {
+        Summary {
+            saved_at: 1_700_000_000,
+            zone: Some("zone".into()),
+            boss: Some("boss".into()),
+            difficulty: None,
+            duration: 1000,
+        }
+    }
+
+    /// Write `preamble` followed by a compressed log body.
+    fn write_raw(name: &str, preamble: &[u8]) -> PathBuf {
+        write_raw_body(name, preamble, &serde_bare::to_vec(&empty_log()).unwrap())
+    }
+
+    fn write_raw_body(name: &str, preamble: &[u8], body: &[u8]) -> PathBuf {
+        let path = temp_path(name);
+        let mut bytes = preamble.to_vec();
+        let mut w = snap::write::FrameEncoder::new(&mut bytes);
+        w.write_all(body).unwrap();
+        drop(w);
+        fs::write(&path, bytes).unwrap();
+        path
+    }
+
+    #[test]
+    fn written_logs_are_read_back() {
+        let dir = temp_path("written");
+        let preamble = (Header::new(Some("build".into())), summary());
+        let path = write_file(&dir, "log", &preamble, &empty_log()).unwrap();
+        read_log(&path).unwrap();
+        let summary = read_summary(&path).unwrap().unwrap();
+        assert_eq!(summary.boss.as_deref(), Some("boss"));
+        assert_eq!(list_logs(&dir).unwrap(), [path]);
+    }
+
+    /// Log structures as they were written before logs had a header. Newtypes are written as
+    /// the values they wrap, so these stand in for them.
+    mod v0 {
+        use std::collections::BTreeMap;
+
+        #[derive(serde::Serialize)]
+        pub struct Log {
+            pub end: u64,
+            pub entities: Vec<Entity>,
+            pub pov: Option<usize>,
+            pub targets: Vec<usize>,
+            pub status: Option<Status>,
+        }
+
+        #[derive(serde::Serialize)]
+        pub struct Entity {
+            pub name: Option<String>,
+            pub damage: Vec<(u64, i64)>,
+            pub casts: Vec<(u64, u32)>,
+            pub skills: BTreeMap<u32, Skill>,
+            pub kind: EntityKind,
+        }
+
+        #[derive(serde::Serialize)]
+        pub enum EntityKind {
+            Player,
+            Npc(u32),
+        }
+
+        #[derive(serde::Serialize)]
+        pub struct Skill {
+            pub name: Option<String>,
+            pub hits: Vec<(u64, SkillHit)>,
+        }
+
+        #[derive(serde::Serialize)]
+        pub struct SkillHit {
+            pub damage: i64,
+            pub target: usize,
+            pub is_crit: bool,
+            pub is_back_attack: bool,
+            pub is_front_attack: bool,
+        }
+
+        #[derive(serde::Serialize)]
+        pub enum Status {
+            Wipe,
+            Clear,
+        }
+    }
+
+    #[test]
+    fn headerless_logs_are_version_0() {
+        let hit = v0::SkillHit {
+            damage: 300,
+            target: 1,
+            is_crit: true,
+            is_back_attack: false,
+            is_front_attack: true,
+        };
+        let skill = v0::Skill {
+            name: Some("skill".into()),
+            hits: vec![(1000, hit)],
+        };
+        let log = v0::Log {
+            end: 10_000,
+            entities: vec![
+                v0::Entity {
+                    name: Some("player".into()),
+                    damage: vec![(1000, 300)],
+                    casts: vec![(900, 7)],
+                    skills: BTreeMap::from([(7, skill)]),
+                    kind: v0::EntityKind::Player,
+                },
+                v0::Entity {
+                    name: Some("boss".into()),
+                    damage: Vec::new(),
+                    casts: Vec::new(),
+                    skills: BTreeMap::new(),
+                    kind: v0::EntityKind::Npc(42),
+                },
+            ],
+            pov: Some(0),
+            targets: vec![1],
+            status: Some(v0::Status::Wipe),
+        };
+        let path = write_raw_body("headerless", &[], &serde_bare::to_vec(&log).unwrap());
+        assert!(read_summary(&path).unwrap().is_none());
+
+        // entities are identified by their index, and players' classes aren't known
+        let (env, enc) = read_log(&path).unwrap().into_encounter();
+        assert_eq!(env.pov, Some(0));
+        let player = &env.players[&0];
+        assert_eq!(player.name.as_deref(), Some("player"));
+        assert!(matches!(player.class, crate::definitions::Class::Unknown));
+        assert_eq!(env.npcs[&1].kind, 42);
+        assert_eq!(env.npcs[&1].name.as_deref(), Some("boss"));
+
+        let data = &enc.players[&0];
+        assert_eq!(data.dmg_dealt, 300);
+        assert_eq!(data.casts.len(), 1);
+        let skill = &data.skills[&7];
+        assert_eq!(skill.name.as_deref(), Some("skill"));
+        let (_, hit) = &skill.hits[0];
+        assert_eq!(hit.target_id, 1);
+        assert!(hit.is_crit && hit.is_front_attack && !hit.is_back_attack);
+        assert!(hit.buffed.is_empty());
+        assert!(enc.wipe && !enc.clear);
+        assert!(enc.deaths.is_empty() && enc.status_effects.is_empty());
+    }
+
+    #[test]
+    fn bad_headers_are_rejected() {
+        let path = write_raw("not_a_log", b"NOPE");
+        assert!(read_log(&path).is_err());
+
+        let mut header = Header::new(None);
+        header.version = log::VERSION + 1;
+        let mut preamble = serde_bare::to_vec(&(header, summary())).unwrap();
+        let path = write_raw("newer", &preamble);
+        let Err(e) = read_log(&path) else {
+            panic!("log of a newer version was read");
+        };
+        assert!(format!("{:#}", e).contains("newer"), "{:#}", e);
+
+        // the header is cut off in the middle of the meter version
+        preamble.truncate(log::MAGIC.len() + 3);
+        let path = temp_path("truncated");
+        fs::write(&path, &preamble).unwrap();
+        assert!(read_log(&path).is_err());
+    }
+
+    #[test]
+    fn runs_are_not_logs() {
+        let dir = temp_path("runs");
+        let run = log::Run {
+            raid_id: 1,
+            started_at: 0,
+            clear_time: None,
+            total_time: 0,
+            result: None,
+            brave_hearts: 0,
+            init_brave_hearts: 0,
+            bosses: Vec::new(),
+            kills: Vec::new(),
+            battle_items: Vec::new(),
+            encounters: Vec::new(),
+        };
+        let path = write_file(&dir, "run", &Header::run(None), &run).unwrap();
+        assert_eq!(read_run(&path).unwrap().raid_id, 1);
+        assert!(read_log(&path).is_err());
+    }
+}
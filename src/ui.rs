@@ -3,13 +3,18 @@
 use std::{
     borrow::Cow,
     fs,
+    path::PathBuf,
     sync::{mpsc::Sender, Arc},
+    time::{Duration, Instant},
 };
 
 use egui::PointerButton;
 use parking_lot::Mutex;
 
-use crate::meter::{Data, Player};
+use crate::{
+    meter::{Data, Encounter, Environment, Player, SavedEncounter, Stagger, Zone},
+    persist::{self, LogConfig},
+};
 
 const CLASS_ICON_PATH: &str = "resources/class.png";
 const FONT_PATH: &str = "resources/font.ttf";
@@ -44,13 +49,14 @@ pub fn run(
             icons,
             dragging: false,
             count: bar_count,
+            saved_logs: Vec::new(),
         }
     })
 }
 
 enum State {
     Dps(EncounterChoice),
-    Breakdown(usize, u64, EncounterChoice),
+    Breakdown(EncounterChoice, u64, EncounterChoice),
     EncounterList,
 }
 
@@ -58,6 +64,25 @@ enum State {
 enum EncounterChoice {
     Current,
     Previous(usize),
+    /// Index into [`Data::saved`].
+    Saved(usize),
+}
+
+impl EncounterChoice {
+    /// Look up the encounter and its environment, pinning [`EncounterChoice::Current`] to the
+    /// encounter it currently refers to.
+    fn resolve(self, data: &Data) -> Option<(Self, &Encounter, &Environment)> {
+        let (idx, encounter) = match self {
+            Self::Current => data.recent_encounters().next()?,
+            Self::Previous(idx) => data.recent_encounters().find(|&(i, _)| i == idx)?,
+            Self::Saved(idx) => {
+                let saved = data.saved.get(idx)?;
+                return Some((self, &saved.encounter, &saved.environment));
+            }
+        };
+        let env = &data.environments[encounter.environment];
+        Some((Self::Previous(idx), encounter, env))
+    }
 }
 
 struct Ui {
@@ -66,6 +91,41 @@ struct Ui {
     data: Arc<Mutex<Data>>,
     dragging: bool,
     count: usize,
+    /// Most recent saved logs on disk, refreshed whenever the encounter list is opened.
+    saved_logs: Vec<SavedLog>,
+}
+
+/// A saved log on disk, and how it's labelled in the encounter list.
+struct SavedLog {
+    path: PathBuf,
+    label: String,
+}
+
+impl SavedLog {
+    /// Label the log with the zone, boss and time in its summary, falling back to the file name
+    /// for logs too old to have one.
+    fn read(path: PathBuf) -> Self {
+        let summary = persist::read_summary(&path).ok().flatten();
+        let label = match summary {
+            Some(summary) => {
+                let mut label = format_unix_time(summary.saved_at);
+                for name in [&summary.zone, &summary.boss].into_iter().flatten() {
+                    label.push(' ');
+                    label.push_str(name);
+                }
+                if let Some(difficulty) = summary.difficulty {
+                    label.push_str(&format!(" [{}]", difficulty.name()));
+                }
+                label
+            }
+            None => path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .into_owned(),
+        };
+        Self { path, label }
+    }
 }
 
 impl win32_overlay::App for Ui {
@@ -111,6 +171,7 @@ impl Ui {
 
     fn dps_view(&mut self, ctx: &egui::Context, ui: &mut egui::Ui, choice: EncounterChoice) {
         if ctx.input(|i| i.pointer.button_released(PointerButton::Secondary)) {
+            self.refresh_saved_logs();
             self.state = State::EncounterList;
             ctx.request_repaint();
         }
@@ -119,13 +180,7 @@ impl Ui {
         let secondary_text_color = egui::Color32::from_gray(0xcc);
 
         let data = self.data.lock();
-        let mut encounters = data.recent_encounters();
-        let encounter = match choice {
-            EncounterChoice::Current => encounters.next(),
-            EncounterChoice::Previous(idx) => encounters.find(|&(i, _)| i == idx),
-        };
-
-        let Some((idx, encounter)) = encounter else {
+        let Some((resolved, encounter, env)) = choice.resolve(&data) else {
             ui.set_min_width(ui.available_width());
             ui.label("No data.");
             return;
@@ -133,14 +188,17 @@ impl Ui {
 
         let duration = encounter.duration().as_secs_f64();
 
-        if let Some(boss_info) = &data
-            .live
-            .recently_tracked
-            .and_then(|id| data.live.tracked.get(&id))
+        // boss hp is only known live
+        let live_boss = match resolved {
+            EncounterChoice::Saved(_) => None,
+            _ => data.live.recently_tracked,
+        };
+        if let Some((boss_id, boss_info)) =
+            live_boss.and_then(|id| data.live.tracked.get(&id).map(|info| (id, info)))
         {
             let cur_hp = 0.max(boss_info.cur_hp);
             let percentage = cur_hp as f32 / boss_info.max_hp as f32;
-            let (bar, _) = Bar::new(ui, percentage, egui::Sense::hover(), (145, 18, 1));
+            let (bar, resp) = Bar::new(ui, percentage, egui::Sense::hover(), (145, 18, 1));
             let text_color = egui::Color32::WHITE;
             if let Some(max_bars) = boss_info.bar_count {
                 let bar_count = percentage * max_bars as f32;
@@ -157,13 +215,53 @@ impl Ui {
 
             let percent_text = format!("{:.1}%", percentage * 100.0);
             bar.paint_text_at(&percent_text, BarTextPosition::Right, text_color);
+
+            // status effects currently on the boss
+            let effects: Vec<_> = data
+                .live
+                .buffs
+                .get(&boss_id)
+                .into_iter()
+                .flat_map(|buffs| buffs.values())
+                .filter_map(|info| info.name.as_deref().map(|name| (name, info.stacks)))
+                .collect();
+            let stagger = encounter.stagger.get(&boss_id);
+            let has_history = !encounter.status_effects_on(boss_id).is_empty();
+            if !effects.is_empty() || stagger.is_some() || has_history {
+                resp.on_hover_ui_at_pointer(|ui| {
+                    for (name, stacks) in effects {
+                        match stacks {
+                            0 | 1 => ui.label(name),
+                            _ => ui.label(format!("{} x{}", name, stacks)),
+                        };
+                    }
+                    if let Some(stagger) = stagger {
+                        stagger_ui(ui, stagger, encounter, env);
+                    }
+                    if has_history {
+                        uptime_ui(ui, encounter, boss_id);
+                    }
+                });
+            }
         }
 
+        // party membership is tracked live, and recorded in saved logs
+        let parties = match resolved {
+            EncounterChoice::Saved(idx) => &data.saved[idx].parties,
+            _ => &data.live.parties,
+        };
+
         let mut sorted: Vec<_> = encounter.players.iter().collect();
         sorted.sort_by_key(|(_, p)| -p.dmg_dealt);
-        let highest_dmg = sorted.first().unwrap().1.dmg_dealt;
-
-        let env = &data.environments[encounter.environment];
+        // saved logs can have players who only took damage, or none at all
+        let Some(highest_dmg) = sorted
+            .first()
+            .map(|(_, p)| p.dmg_dealt)
+            .filter(|&dmg| dmg > 0)
+        else {
+            ui.label("No data.");
+            return;
+        };
 
         for (id, player, player_info) in sorted
             .iter()
@@ -178,47 +276,134 @@ impl Ui {
                 bar.paint_icon(icon);
             }
 
-            let name_text = make_player_name(&player_info, text_color, secondary_text_color);
+            let deaths: Vec<_> = encounter.deaths_of(id).collect();
+            let name_text =
+                make_player_name(&player_info, deaths.len(), text_color, secondary_text_color);
             bar.paint_text_job_at(name_text, BarTextPosition::Left(1.3), text_color);
 
             let dps_text = to_human_readable(player.dmg_dealt as f64 / duration);
 
-            let brand_text = if player.brand_dmg > 0 {
-                format!(
-                    "{}%",
-                    (player.brand_dmg as f64 / player.dmg_dealt as f64 * 100.0).round()
-                )
-            } else {
-                "".to_string()
-            };
-
-            let ap_text = if player.ap_dmg > 0 {
-                format!(
-                    "{}%",
-                    (player.ap_dmg as f64 / player.dmg_dealt as f64 * 100.0).round()
-                )
-            } else {
-                "".to_string()
-            };
+            let mut text = String::new();
+            for idx in 0..encounter.buff_categories.len() {
+                let rate = player.buffed_rate(idx);
+                let rate_text = if rate > 0.0 {
+                    format!("{}%", (rate * 100.0).round())
+                } else {
+                    "".to_string()
+                };
+                text.push_str(&format!("{:>4} ", rate_text));
+            }
+            text.push_str(&format!(" {:>5}", dps_text));
+            bar.paint_text_at(&text, BarTextPosition::Right, text_color);
 
-            let ident_text = if player.ident_dmg > 0 {
-                format!(
-                    "{}%",
-                    (player.ident_dmg as f64 / player.dmg_dealt as f64 * 100.0).round()
-                )
+            let counters = encounter.counter_count(id);
+            let identity = encounter.identity.get(&id);
+            let from_buffs = player.damage_from_buffs();
+            let enabled = encounter.damage_enabled_by(id);
+            let coverage: Vec<_> = encounter
+                .buff_categories
+                .iter()
+                .enumerate()
+                .map(|(idx, category)| (&category.name, encounter.party_coverage(id, idx, parties)))
+                .filter(|&(_, coverage)| coverage > 0.0)
+                .collect();
+            let shields_given = encounter.shields_given_by(id);
+            let shields_received = encounter.shields_received_by(id);
+            let resp = if deaths.is_empty()
+                && counters.attempts == 0
+                && identity.is_none()
+                && from_buffs == 0
+                && enabled == 0
+                && coverage.is_empty()
+                && shields_given.given == 0
+                && shields_received.given == 0
+            {
+                resp
             } else {
-                "".to_string()
+                let end = encounter.end.unwrap_or_else(Instant::now);
+                resp.on_hover_ui_at_pointer(|ui| {
+                    if from_buffs > 0 {
+                        ui.label(format!(
+                            "from buffs {} ({}%)",
+                            to_human_readable(from_buffs as f64),
+                            HumanReadable(from_buffs as f64 / player.dmg_dealt as f64 * 100.0)
+                        ));
+                    }
+                    if enabled > 0 {
+                        ui.label(format!(
+                            "enabled {} ({}/s)",
+                            to_human_readable(enabled as f64),
+                            to_human_readable(enabled as f64 / duration)
+                        ));
+                    }
+                    for (name, coverage) in &coverage {
+                        ui.label(format!(
+                            "party {} {}%",
+                            name.to_lowercase(),
+                            HumanReadable(coverage * 100.0)
+                        ));
+                    }
+                    if shields_given.given > 0 {
+                        ui.label(format!(
+                            "shields given {} (absorbed {}, expired {})",
+                            to_human_readable(shields_given.given as f64),
+                            to_human_readable(shields_given.absorbed as f64),
+                            to_human_readable(shields_given.expired as f64)
+                        ));
+                    }
+                    if shields_received.given > 0 {
+                        ui.label(format!(
+                            "shields received {} (absorbed {})",
+                            to_human_readable(shields_received.given as f64),
+                            to_human_readable(shields_received.absorbed as f64)
+                        ));
+                    }
+                    if let Some(identity) = identity {
+                        let in_identity = identity.time_in_identity(end);
+                        if !in_identity.is_zero() {
+                            ui.label(format!("identity {}", format_time(in_identity)));
+                        }
+                        let at_cap = identity.time_at_cap(end);
+                        if !at_cap.is_zero() {
+                            ui.label(format!("gauge capped {}", format_time(at_cap)));
+                        }
+                        if !identity.ultimates.is_empty() {
+                            ui.label(format!("ultimates {}", identity.ultimates.len()));
+                        }
+                    }
+                    if counters.attempts > 0 {
+                        ui.label(format!(
+                            "counters {}/{}",
+                            counters.successes, counters.attempts
+                        ));
+                    }
+                    for death in &deaths {
+                        ui.label(format!(
+                            "died at {} to {}, dead for {}",
+                            format_time(death.time.saturating_duration_since(encounter.start)),
+                            entity_name(env, death.source_id),
+                            format_time(death.time_dead(end)),
+                        ));
+                        for hit in &death.recap {
+                            let skill = match hit.skill.as_ref() {
+                                Some(name) => Cow::Borrowed(name),
+                                None => Cow::Owned(hit.skill_id.to_string()),
+                            };
+                            ui.monospace(format!(
+                                "  -{:.1}s {:>6} {} ({})",
+                                death.time.saturating_duration_since(hit.time).as_secs_f64(),
+                                to_human_readable(hit.damage as f64),
+                                skill,
+                                entity_name(env, hit.source_id),
+                            ));
+                        }
+                    }
+                })
             };
 
-            let text = format!(
-                "{:>4} {:>4} {:>4}  {:>5}",
-                ident_text, ap_text, brand_text, dps_text
-            );
-            bar.paint_text_at(&text, BarTextPosition::Right, text_color);
-
             if resp.clicked() {
                 println!("clicked {}", player_info.class);
-                self.state = State::Breakdown(idx, id, choice);
+                self.state = State::Breakdown(resolved, id, choice);
                 ctx.request_repaint();
             }
         }
@@ -228,7 +413,7 @@ impl Ui {
         &mut self,
         ctx: &egui::Context,
         ui: &mut egui::Ui,
-        idx: usize,
+        choice: EncounterChoice,
         id: u64,
         prev_state: EncounterChoice,
     ) {
@@ -238,12 +423,13 @@ impl Ui {
         }
 
         let data = self.data.lock();
-        let encounter = &data.encounters[idx];
+        let Some((_, encounter, env)) = choice.resolve(&data) else {
+            ui.set_min_width(ui.available_width());
+            ui.label("No data.");
+            return;
+        };
         let player = encounter.players.get(&id).unwrap();
-        let player_info = data.environments[encounter.environment]
-            .players
-            .get(&id)
-            .unwrap();
+        let player_info = env.players.get(&id).unwrap();
 
         if player.skills.is_empty() {
             ui.set_min_width(ui.available_width());
@@ -289,26 +475,15 @@ impl Ui {
                         HumanReadable(skill.front as f64 / skill.count as f64 * 100.0)
                     ));
                 }
-                if skill.brand > 0 {
-                    ui.monospace(format!(
-                        "brand {} ({}%)",
-                        skill.brand,
-                        HumanReadable(skill.brand as f64 / skill.count as f64 * 100.0)
-                    ));
-                }
-                if skill.ap_buff > 0 {
-                    ui.monospace(format!(
-                        "ap    {} ({}%)",
-                        skill.ap_buff,
-                        HumanReadable(skill.ap_buff as f64 / skill.count as f64 * 100.0)
-                    ));
-                }
-                if skill.ident_buff > 0 {
-                    ui.monospace(format!(
-                        "ident    {} ({}%)",
-                        skill.ident_buff,
-                        HumanReadable(skill.ident_buff as f64 / skill.count as f64 * 100.0)
-                    ));
+                for (category, &hits) in encounter.buff_categories.iter().zip(&skill.buffed) {
+                    if hits > 0 {
+                        ui.monospace(format!(
+                            "{:<5} {} ({}%)",
+                            category.name.to_lowercase(),
+                            hits,
+                            HumanReadable(hits as f64 / skill.count as f64 * 100.0)
+                        ));
+                    }
                 }
             });
 
@@ -329,23 +504,115 @@ impl Ui {
         }
         let data = self.data.lock();
         let encounters = data.recent_encounters();
-        for (i, _) in encounters.take(7) {
-            if ui.button(i.to_string()).clicked() {
+        for (i, enc) in encounters.take(7) {
+            let mut label = match data.encounter_name(enc) {
+                Some(name) => format!("{} {}", i, name),
+                None => i.to_string(),
+            };
+            if let Some(difficulty) = enc.difficulty() {
+                label.push_str(&format!(" [{}]", difficulty.name()));
+            }
+            let attempt = data.attempt(i);
+            if attempt > 1 {
+                label.push_str(&format!(" #{}", attempt));
+            }
+            if ui.button(label).clicked() {
                 self.state = State::Dps(EncounterChoice::Previous(i));
                 ctx.request_repaint();
             }
         }
+
+        if !data.raids.is_empty() {
+            ui.separator();
+        }
+        for run in data.raids.iter().rev().take(3) {
+            let name = run
+                .encounters
+                .first()
+                .and_then(|&idx| data.encounters[idx].zone.as_ref().map(Zone::display_name))
+                .unwrap_or_else(|| format!("raid {}", run.raid_id));
+            let mut label = match run.clear_time() {
+                Some(time) => {
+                    let secs = time.as_secs();
+                    format!("{} cleared in {}:{:02}", name, secs / 60, secs % 60)
+                }
+                None if run.end.is_some() => format!("{} failed", name),
+                None => format!("{} in progress", name),
+            };
+            label.push_str(&format!(
+                ", {} revives, {} battle items",
+                run.revives_used(),
+                run.battle_items_used()
+            ));
+            ui.label(label);
+        }
+
+        drop(data);
+
+        if self.saved_logs.is_empty() {
+            return;
+        }
+        ui.separator();
+        let mut clicked = None;
+        for saved in &self.saved_logs {
+            if ui.button(&saved.label).clicked() {
+                clicked = Some(&saved.path);
+            }
+        }
+        if let Some(path) = clicked {
+            let loaded = self.data.lock().saved_index(path);
+            // decode outside the lock, so that packets keep being processed meanwhile
+            let idx = match loaded {
+                Some(idx) => Ok(idx),
+                None => SavedEncounter::read(path).map(|saved| self.data.lock().add_saved(saved)),
+            };
+            match idx {
+                Ok(idx) => self.state = State::Dps(EncounterChoice::Saved(idx)),
+                Err(e) => println!("failed to open saved log: {:#}", e),
+            }
+            ctx.request_repaint();
+        }
+    }
+
+    fn refresh_saved_logs(&mut self) {
+        let dir = match &self.data.lock().log_writer {
+            Some(writer) => writer.config().dir.clone(),
+            None => LogConfig::default().dir,
+        };
+        self.saved_logs = persist::list_logs(&dir)
+            .unwrap_or_default()
+            .into_iter()
+            .take(7)
+            .map(SavedLog::read)
+            .collect();
     }
 
     fn render(&mut self, ctx: &egui::Context, ui: &mut egui::Ui) {
         match self.state {
             State::Dps(choice) => self.dps_view(ctx, ui, choice),
-            State::Breakdown(idx, id, prev) => self.breakdown_view(ctx, ui, idx, id, prev),
+            State::Breakdown(choice, id, prev) => self.breakdown_view(ctx, ui, choice, id, prev),
             State::EncounterList => self.encounter_view(ctx, ui),
         }
     }
 }
 
+/// `YYYY-MM-DD HH:MM` in UTC.
+fn format_unix_time(secs: u64) -> String {
+    let days = (secs / 86400) as i64;
+    let (hour, minute) = (secs % 86400 / 3600, secs % 3600 / 60);
+    // civil from days since the epoch, by Howard Hinnant
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{}-{:02}-{:02} {:02}:{:02}", year, month, day, hour, minute)
+}
+
 fn slice_at_nth_char(s: &str, idx: usize) -> &str {
     let idx = s
         .char_indices()
@@ -358,6 +625,7 @@ fn slice_at_nth_char(s: &str, idx: usize) -> &str {
 
 fn make_player_name(
     player: &Player,
+    deaths: usize,
     color: egui::Color32,
     offcolor: egui::Color32,
 ) -> egui::text::LayoutJob {
@@ -382,9 +650,87 @@ fn make_player_name(
         job.append(&ilvl_text, 0.0, format);
     }
 
+    if deaths > 0 {
+        let format = egui::TextFormat {
+            color: egui::Color32::from_rgb(0xe0, 0x40, 0x40),
+            font_id: egui::FontId::monospace(FONT_SIZE),
+            ..Default::default()
+        };
+        job.append(&format!(" †{}", deaths), 0.0, format);
+    }
+
     job
 }
 
+fn stagger_ui(ui: &mut egui::Ui, stagger: &Stagger, encounter: &Encounter, env: &Environment) {
+    if let Some(sample) = stagger.samples.last() {
+        ui.monospace(format!("stagger {:.0}%", sample.fill() * 100.0));
+    }
+    for check in &stagger.checks {
+        let start = format_time(check.start.saturating_duration_since(encounter.start));
+        match check.time_to_stagger() {
+            Some(time) => ui.monospace(format!(
+                "  {} staggered in {:.1}s",
+                start,
+                time.as_secs_f64()
+            )),
+            None if check.end.is_some() => ui.monospace(format!("  {} failed", start)),
+            None => ui.monospace(format!("  {} in progress", start)),
+        };
+    }
+    let mut contribution: Vec<_> = stagger.contribution.iter().collect();
+    contribution.sort_by_key(|(_, &points)| std::cmp::Reverse(points));
+    let total: u64 = contribution.iter().map(|(_, &points)| points).sum();
+    for (&id, &points) in contribution {
+        ui.monospace(format!(
+            "{:<8} {:>3.0}%",
+            slice_at_nth_char(entity_name(env, id), 8),
+            points as f64 / total as f64 * 100.0
+        ));
+    }
+}
+
+/// Uptime of the named status effects applied to an entity, highest first.
+fn uptime_ui(ui: &mut egui::Ui, encounter: &Encounter, target_id: u64) {
+    let mut names: Vec<&str> = encounter
+        .status_effects_on(target_id)
+        .iter()
+        .filter_map(|effect| effect.name.as_deref())
+        .collect();
+    names.sort_unstable();
+    names.dedup();
+
+    let mut uptimes: Vec<_> = names
+        .into_iter()
+        .map(|name| {
+            let uptime = encounter.uptime(target_id, |effect| effect.name.as_deref() == Some(name));
+            (name, uptime)
+        })
+        .collect();
+    uptimes.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+    for (name, uptime) in uptimes.into_iter().take(8) {
+        ui.monospace(format!(
+            "{:<16} {:>3.0}%",
+            slice_at_nth_char(name, 16),
+            uptime * 100.0
+        ));
+    }
+}
+
+fn entity_name(env: &Environment, id: u64) -> &str {
+    let name = match env.players.get(&id) {
+        Some(player) => player.name.as_deref(),
+        None => env.npcs.get(&id).and_then(|npc| npc.name.as_deref()),
+    };
+    name.unwrap_or("?")
+}
+
+/// `m:ss` of a duration.
+fn format_time(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
 enum BarTextPosition {
     Left(f32),
     Center,
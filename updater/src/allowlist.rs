@@ -0,0 +1,160 @@
+//! Selection of the packets, and fields within them, that code is generated for.
+//!
+//! The allowlist is a JSON object mapping structure names as they are emitted (`PktNewNpc`,
+//! `NpcData`) to either `null`, keeping every field, or a list of field names to keep.
+//! Packets with an opcode that aren't listed are dropped entirely. Subpackets are kept as long
+//! as a kept packet still needs to parse over them.
+//!
+//! Fields that aren't kept are still parsed, as later fields depend on their length, but they
+//! are never materialized -- fixed size fields become [`Kind::Skip`]s and dynamically sized
+//! ones become [`Kind::KindedBytes`] where possible.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+};
+
+use crate::parse::{find_used_idents, Field, Kind, LiteralOrIdent, Packet};
+
+#[derive(Debug, serde::Deserialize)]
+pub struct Allowlist(BTreeMap<String, Option<Vec<String>>>);
+
+impl Allowlist {
+    pub fn load(path: &str) -> anyhow::Result<Self> {
+        Ok(serde_json::from_slice(&fs::read(path)?)?)
+    }
+
+    /// Drop packets and strip fields not present in the allowlist.
+    pub fn apply(&self, packets: Vec<Packet>) -> Vec<Packet> {
+        let mut packets: Vec<Packet> = packets
+            .into_iter()
+            .filter(|p| p.opcode.is_none() || self.0.contains_key(&p.name))
+            .collect();
+
+        for packet in packets.iter_mut() {
+            if let Some(Some(keep)) = self.0.get(&packet.name) {
+                discard_fields(&mut packet.fields, |name| keep.iter().any(|k| k == name));
+            }
+        }
+
+        // Structures referenced by kept fields have their contents captured, whereas
+        // structures only referenced by discarded fields merely need to be parsed over.
+        let captured = reachable(&packets, true);
+        let parsed = reachable(&packets, false);
+        packets.retain(|p| p.opcode.is_some() || parsed.contains(&p.name));
+
+        for packet in packets.iter_mut() {
+            if packet.opcode.is_none() && !captured.contains(&packet.name) {
+                discard_fields(&mut packet.fields, |_| false);
+            }
+            skip_discarded(&mut packet.fields);
+        }
+
+        packets
+    }
+}
+
+/// Remove the name of each field not satisfying `keep`, unless another field depends on it.
+fn discard_fields(fields: &mut [Field], keep: impl Fn(&str) -> bool) {
+    let mut required = vec![];
+    find_used_idents(fields, &mut required);
+
+    for field in fields.iter_mut() {
+        let Some(name) = &field.name else { continue };
+        if required.contains(name) {
+            continue;
+        }
+        if !keep(name.strip_prefix("r#").unwrap_or(name)) {
+            field.name = None;
+        }
+    }
+}
+
+/// Names of all structures reachable from packets with an opcode.
+fn reachable(packets: &[Packet], named_only: bool) -> BTreeSet<String> {
+    let mut seen = BTreeSet::new();
+    let mut stack: Vec<&Packet> = packets.iter().filter(|p| p.opcode.is_some()).collect();
+    while let Some(packet) = stack.pop() {
+        for field in &packet.fields {
+            if named_only && field.name.is_none() {
+                continue;
+            }
+            let Some(name) = referenced_struct(&field.kind) else {
+                continue;
+            };
+            if seen.insert(name.to_owned()) {
+                if let Some(p) = packets.iter().find(|p| p.name == name) {
+                    stack.push(p);
+                }
+            }
+        }
+    }
+    seen
+}
+
+fn referenced_struct(kind: &Kind) -> Option<&str> {
+    match kind {
+        Kind::Struct(name) => Some(name),
+        Kind::Optional(_, kind) | Kind::Array { kind, .. } | Kind::Repeat { kind, .. } => {
+            referenced_struct(kind)
+        }
+        _ => None,
+    }
+}
+
+/// Size in bytes of a kind, if it's the same for every occurrence.
+fn fixed_size(kind: &Kind) -> Option<usize> {
+    Some(match kind {
+        Kind::U8 | Kind::I8 | Kind::Bool => 1,
+        Kind::U16 | Kind::I16 | Kind::Angle => 2,
+        Kind::U32 | Kind::I32 | Kind::F32 => 4,
+        Kind::U64 | Kind::I64 | Kind::Vector => 8,
+        Kind::Bytes(len) | Kind::Skip(len) => *len,
+        _ => return None,
+    })
+}
+
+/// Convert unnamed fields into the cheapest kind that still advances past them.
+fn skip_discarded(fields: &mut Vec<Field>) {
+    for field in fields.iter_mut().filter(|f| f.name.is_none()) {
+        let kind = match &field.kind {
+            kind if fixed_size(kind).is_some() => Kind::Skip(fixed_size(kind).unwrap()),
+            // strings are a u16 count of UTF-16 code units
+            Kind::String(max_len) => Kind::KindedBytes(Box::new(Kind::U16), *max_len, Some(2)),
+            Kind::Array {
+                kind,
+                len_kind,
+                len: LiteralOrIdent::Literal(max_len),
+            } if fixed_size(kind).is_some() => {
+                Kind::KindedBytes(len_kind.clone(), *max_len as usize, fixed_size(kind))
+            }
+            Kind::Repeat {
+                kind,
+                count: LiteralOrIdent::Literal(count),
+            } if fixed_size(kind).is_some() => {
+                Kind::Skip(*count as usize * fixed_size(kind).unwrap())
+            }
+            _ => continue,
+        };
+        field.kind = kind;
+    }
+
+    // merge runs of skips into one
+    let mut merged: Vec<Field> = Vec::with_capacity(fields.len());
+    for field in fields.drain(..) {
+        if let (
+            None,
+            Kind::Skip(count),
+            Some(Field {
+                name: None,
+                kind: Kind::Skip(prev),
+            }),
+        ) = (&field.name, &field.kind, merged.last_mut())
+        {
+            *prev += count;
+            continue;
+        }
+        merged.push(field);
+    }
+    *fields = merged;
+}
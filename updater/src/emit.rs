@@ -4,12 +4,13 @@ use std::{fmt::Write, fs};
 
 use crate::parse::Packet;
 
+mod handler;
 mod opcodes;
 mod packets;
 
 pub fn write_packets(packets: &[Packet], dst: &str) -> anyhow::Result<()> {
     let mut output = String::new();
-    packets::emit(&mut output, &packets)?;
+    packets::emit(&mut output, packets)?;
     let output = rustfmt(&output)?;
     fs::write(dst, output)?;
 
@@ -18,13 +19,22 @@ pub fn write_packets(packets: &[Packet], dst: &str) -> anyhow::Result<()> {
 
 pub fn write_opcodes(packets: &[Packet], dst: &str) -> anyhow::Result<()> {
     let mut opcodes = String::new();
-    opcodes::emit(&mut opcodes, &packets)?;
+    opcodes::emit(&mut opcodes, packets)?;
     let opcodes = rustfmt(&opcodes)?;
     fs::write(dst, opcodes)?;
 
     Ok(())
 }
 
+pub fn write_handler(packets: &[Packet], dst: &str) -> anyhow::Result<()> {
+    let mut handler = String::new();
+    handler::emit(&mut handler, packets)?;
+    let handler = rustfmt(&handler)?;
+    fs::write(dst, handler)?;
+
+    Ok(())
+}
+
 fn rustfmt(code: &str) -> anyhow::Result<String> {
     use std::io::Write as _;
     use std::process::{Command, Stdio};
@@ -0,0 +1,60 @@
+//! Generation of the `PacketHandler` trait and the opcode dispatch feeding it.
+
+use std::fmt::Write;
+
+use heck::ToSnekCase;
+
+use crate::parse::Packet;
+
+pub fn emit(w: &mut impl Write, packets: &[Packet]) -> anyhow::Result<()> {
+    w.write_str("//! Dispatch of parsed packets to a [`PacketHandler`].\n\n")?;
+    super::emit_notice(w)?;
+    writeln!(w, "use crate::{{capture::parse_with_context, definitions::Opcode, packet, parser::{{Packet, Parser}}}};")?;
+    writeln!(w)?;
+
+    let names = || {
+        packets
+            .iter()
+            .filter(|p| p.opcode.is_some())
+            .map(|p| (&p.name[..], p.name[3..].to_snek_case()))
+    };
+
+    w.write_str(
+        "/// Parse the payload of a packet with the given opcode and pass it to `handler`.\n",
+    )?;
+    w.write_str("pub fn dispatch<P: PacketHandler>(handler: &mut P, opcode: Opcode, parser: &mut Parser, bump: &mut bumpalo::Bump) -> anyhow::Result<()> {\n")?;
+    w.write_str("match opcode {\n")?;
+    for (name, method) in names() {
+        writeln!(w, "Opcode::{} => {{", &name[3..])?;
+        writeln!(
+            w,
+            "let pkt = parse_with_context::<packet::{}>(parser, bump)?;",
+            name
+        )?;
+        w.write_str("handler.on_packet(&pkt);\n")?;
+        writeln!(w, "handler.on_{}(pkt)?;", method)?;
+        w.write_str("}\n")?;
+    }
+    w.write_str("}\n")?;
+    w.write_str("Ok(())\n")?;
+    w.write_str("}\n\n")?;
+
+    w.write_str("#[rustfmt::skip]\n")?;
+    w.write_str("pub trait PacketHandler {\n")?;
+    for (name, method) in names() {
+        writeln!(
+            w,
+            "    fn on_{}(&mut self, _: packet::{}) -> anyhow::Result<()> {{ Ok(()) }}",
+            method, name
+        )?;
+    }
+    w.write_char('\n')?;
+    w.write_str("    fn on_packet<P>(&mut self, _: &P) where P: Packet + serde::Serialize {}\n\n")?;
+    w.write_str("    /// Used to filter out unnecessary opcodes before parsing.\n")?;
+    w.write_str("    fn filter(_: &Opcode) -> bool {\n")?;
+    w.write_str("        true\n")?;
+    w.write_str("    }\n")?;
+    w.write_str("}\n")?;
+
+    Ok(())
+}
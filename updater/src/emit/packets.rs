@@ -2,7 +2,7 @@
 
 use std::{borrow::Cow, fmt::Write};
 
-use crate::parse::{Condition, Field, Kind, Packet};
+use crate::parse::{Condition, Field, Kind, LiteralOrIdent, Packet};
 
 pub fn emit(w: &mut impl Write, packets: &[Packet]) -> anyhow::Result<()> {
     w.write_str("//! LoA packet structures.\n\n")?;
@@ -12,10 +12,6 @@ pub fn emit(w: &mut impl Write, packets: &[Packet]) -> anyhow::Result<()> {
         "use crate::parser::{{BumpVec, Event, Packet, Parser, KindedBytes, serialize_bumpvec}};"
     )?;
     writeln!(w, "use super::opcode::Opcode;")?;
-    writeln!(
-        w,
-        "use crate::definitions::{{TripodIndex, TripodLevel, SkillOptionData, SkillMoveOptionData}};"
-    )?;
     writeln!(w)?;
     for packet in packets {
         // println!("{:#?}", packet);
@@ -38,8 +34,9 @@ fn has_captured_fields(fields: &[Field]) -> bool {
 // NOTE: Fields contain references to other packets (`Kind::Struct`), which mandates
 //       carrying around the list of packets to determine lifetime requirements.
 
-// Lifetime annotations are required on structs recursively containing a `Kind::Array`
-// or a `Kind::String` -- both depending on a bump allocation for dynamic memory.
+// Lifetime annotations are required on structs recursively containing a `Kind::Array`,
+// `Kind::String` or captured `Kind::KindedBytes` -- all depending on a bump allocation for
+// dynamic memory.
 
 fn any_fields_need_lifetime(packets: &[Packet], fields: &[Field]) -> bool {
     captured(&fields).any(|(_, k)| kind_needs_lifetime(packets, k))
@@ -55,11 +52,11 @@ fn packet_needs_lifetime(packets: &[Packet], name: &str) -> bool {
 
 fn kind_needs_lifetime(packets: &[Packet], kind: &Kind) -> bool {
     match kind {
-        Kind::String(_) => true,
+        Kind::String(_) | Kind::KindedBytes(..) => true,
         Kind::Optional(_, kind) => kind_needs_lifetime(packets, kind),
         Kind::Struct(name) => packet_needs_lifetime(packets, name),
         Kind::Tuple(fs) => any_fields_need_lifetime(packets, fs),
-        Kind::Array { .. } => true,
+        Kind::Array { .. } | Kind::Repeat { .. } => true,
         _ => false,
     }
 }
@@ -74,7 +71,7 @@ fn emit_struct(w: &mut impl Write, packets: &[Packet], packet: &Packet) -> anyho
     if has_captured_fields(&packet.fields) {
         w.write_str(" {\n")?;
         for (name, kind) in captured(&packet.fields) {
-            if matches!(kind, Kind::Array { .. }) {
+            if matches!(kind, Kind::Array { .. } | Kind::Repeat { .. }) {
                 writeln!(w, "    #[serde(serialize_with = \"serialize_bumpvec\")]")?;
             }
             writeln!(w, "    pub {}: {},", name, kind.rust_type(packets))?;
@@ -137,7 +134,7 @@ fn emit_field(w: &mut impl Write, packets: &[Packet], field: &Field) -> anyhow::
     if let Some(name) = &field.name {
         write!(w, "let {} = ", name)?;
     }
-    emit_kind(w, packets, &field.kind)?;
+    emit_kind(w, packets, &field.kind, field.name.is_some())?;
     w.write_str(";\n")?;
     Ok(())
 }
@@ -152,11 +149,18 @@ fn uses_bump(packet: &Packet) -> bool {
                 | Kind::Bytes(_)
                 | Kind::KindedBytes(..)
                 | Kind::Array { .. }
+                | Kind::Repeat { .. }
         )
     })
 }
 
-fn emit_kind(w: &mut impl Write, packets: &[Packet], kind: &Kind) -> anyhow::Result<()> {
+/// Emit the parsing of `kind`, materializing its value only if it's `captured`.
+fn emit_kind(
+    w: &mut impl Write,
+    packets: &[Packet],
+    kind: &Kind,
+    captured: bool,
+) -> anyhow::Result<()> {
     match kind {
         Kind::U8 => w.write_str("parser.read_u8()?")?,
         Kind::U16 => w.write_str("parser.read_u16()?")?,
@@ -174,32 +178,24 @@ fn emit_kind(w: &mut impl Write, packets: &[Packet], kind: &Kind) -> anyhow::Res
         Kind::Angle => w.write_str("parser.read_u16()?")?,
         Kind::Vector => w.write_str("parser.read_u64()?")?,
         Kind::Optional(cond, kind) => {
-            match cond {
-                Condition::Bool => write!(
-                    w,
-                    "(parser.read_bool()?).then(|| <{}>::parse(parser, bump)).transpose()?",
-                    kind.rust_type_nl()
-                )?,
-                Condition::Equality(name, lit) => write!(
-                    w,
-                    "({} == {}).then(|| <{}>::parse(parser, bump)).transpose()?",
-                    name,
-                    lit,
-                    kind.rust_type_nl()
-                )?,
-                Condition::Greater(name, lit) => write!(
-                    w,
-                    "({} > {}).then(|| <{}>::parse(parser, bump)).transpose()?",
-                    name,
-                    lit,
-                    kind.rust_type_nl()
-                )?,
-            }
-            // write!(w, "parser.read_optional::<{}>(bump)?", kind.rust_type_nl())?
+            w.write_str("if ")?;
+            emit_condition(w, cond)?;
+            w.write_str(" { Some(")?;
+            emit_kind(w, packets, kind, captured)?;
+            w.write_str(") } else { None }")?;
         }
         Kind::If(..) => unreachable!(),
         Kind::Struct(name) => write!(w, "<{}>::parse(parser, bump)?", name)?,
         Kind::Bytes(len) => write!(w, "<[u8; {}]>::parse(parser, bump)?", len)?,
+        Kind::KindedBytes(len_kind, max_len, mult) if captured => {
+            write!(
+                w,
+                "parser.read_kinded_bytes::<{}>(bump, {}, {})?",
+                len_kind.rust_type_nl(),
+                mult.unwrap_or(1),
+                max_len
+            )?;
+        }
         Kind::KindedBytes(len_kind, max_len, mult) => {
             write!(
                 w,
@@ -209,6 +205,19 @@ fn emit_kind(w: &mut impl Write, packets: &[Packet], kind: &Kind) -> anyhow::Res
                 max_len
             )?;
         }
+        // length was read beforehand
+        Kind::Array {
+            kind,
+            len: LiteralOrIdent::Ident(len),
+            ..
+        } => {
+            write!(
+                w,
+                "parser.read_repeated::<{}>(bump, {} as usize)?",
+                kind.rust_type_nl(),
+                len,
+            )?;
+        }
         Kind::Array {
             kind,
             len_kind,
@@ -222,12 +231,45 @@ fn emit_kind(w: &mut impl Write, packets: &[Packet], kind: &Kind) -> anyhow::Res
                 len,
             )?;
         }
+        Kind::Repeat { kind, count } => {
+            write!(w, "parser.read_repeated::<{}>(bump, ", kind.rust_type_nl())?;
+            match count {
+                LiteralOrIdent::Literal(count) => write!(w, "{})?", count)?,
+                LiteralOrIdent::Ident(count) => write!(w, "{} as usize)?", count)?,
+            }
+        }
         Kind::Skip(count) => write!(w, "parser.skip({})?", count)?,
         Kind::Tuple(_) => unreachable!(),
     }
     Ok(())
 }
 
+fn emit_condition(w: &mut impl Write, cond: &Condition) -> anyhow::Result<()> {
+    match cond {
+        Condition::Bool => w.write_str("parser.read_bool()?")?,
+        Condition::Equality(name, lit) => write!(w, "{} == {}", name, lit)?,
+        Condition::Greater(name, lit) => write!(w, "{} > {}", name, lit)?,
+        Condition::Less(name, lit) => write!(w, "{} < {}", name, lit)?,
+        Condition::Flag(name, mask) => write!(w, "{} & {:#x} != 0", name, mask)?,
+        Condition::Not(cond) => {
+            w.write_str("!(")?;
+            emit_condition(w, cond)?;
+            w.write_char(')')?;
+        }
+        // never true, as opposed to an empty `!()`
+        Condition::Any(conds) if conds.is_empty() => w.write_str("false")?,
+        Condition::Any(conds) => {
+            for (i, cond) in conds.iter().enumerate() {
+                if i != 0 {
+                    w.write_str(" || ")?;
+                }
+                emit_condition(w, cond)?;
+            }
+        }
+    }
+    Ok(())
+}
+
 impl Kind {
     // no lifetime
     fn rust_type_nl(&self) -> Cow<str> {
@@ -235,7 +277,9 @@ impl Kind {
             Kind::String(_) => "&str".into(),
             Kind::Optional(_, kind) => format!("Option<{}>", kind.rust_type_nl()).into(),
             Kind::Struct(name) => name.into(),
-            Kind::Array { kind, .. } => format!("BumpVec<{}>", kind.rust_type_nl()).into(),
+            Kind::Array { kind, .. } | Kind::Repeat { kind, .. } => {
+                format!("BumpVec<{}>", kind.rust_type_nl()).into()
+            }
             Kind::Tuple(_) => unreachable!(),
             Kind::KindedBytes(len_kind, max_len, mult) => format!(
                 "KindedBytes<{}, {}, {}>",
@@ -276,8 +320,8 @@ impl Kind {
                 }
             }
             Kind::Bytes(len) => format!("[u8; {}]", len).into(),
-            Kind::KindedBytes(..) => "()".into(),
-            Kind::Array { kind, .. } => {
+            Kind::KindedBytes(..) => "&'bump [u8]".into(),
+            Kind::Array { kind, .. } | Kind::Repeat { kind, .. } => {
                 format!("BumpVec<'bump, {}>", kind.rust_type(packets)).into()
             }
             Kind::Skip(_) => "()".into(),
@@ -1,3 +1,4 @@
+pub mod allowlist;
 pub mod emit;
 pub mod parse;
 pub mod resources;
@@ -1,16 +1,22 @@
 //! Responsible for updating data that routinely changes in LoA client updates.
 //! This includes packet formats, the XOR cipher key, the Oodle decompression
-//! state, and the database of datamined skills.
+//! state, and the databases of datamined skills, NPCs, status effects and zones, as well as
+//! converting the curated boss definitions and buff categories.
 
 use updater::{
+    allowlist::Allowlist,
     emit, parse,
-    resources::{OodleState, Resource, Skills, Xor},
+    resources::{
+        Bosses, BuffCategories, Npcs, OodleState, Resource, Skills, StatusEffects, Xor, Zones,
+    },
 };
 
 const TARGET: &str = "updater/meter-core/src/packets/generated";
 const SUBDIRS: &[&str] = &["definitions", "structures"];
 const PACKET_DST: &str = "src/generated/packet.rs";
 const OPCODE_DST: &str = "src/generated/opcode.rs";
+const HANDLER_DST: &str = "src/generated/handler.rs";
+const ALLOWLIST: &str = "updater/allowlist.json";
 
 const XOR: &str = "updater/meter-data/xor.bin";
 const XOR_DST: &str = "src/generated/xor";
@@ -21,19 +27,44 @@ const OODLE_STATE_DST: &str = "resources/oodle_state";
 const SKILL: &str = "updater/meter-data/databases/Skill.json";
 const SKILL_DST: &str = "resources/skills";
 
+const NPC: &str = "updater/meter-data/databases/Npc.json";
+const NPC_DST: &str = "resources/npcs";
+
+const STATUS_EFFECT: &str = "updater/meter-data/databases/SkillBuff.json";
+const STATUS_EFFECT_DST: &str = "resources/status_effects";
+
+const ZONE: &str = "updater/meter-data/databases/Zone.json";
+const ZONE_DST: &str = "resources/zones";
+
+const BOSSES: &str = "updater/bosses.json";
+const BOSSES_DST: &str = "resources/bosses";
+
+const BUFF_CATEGORIES: &str = "updater/buff_categories.json";
+const BUFF_CATEGORIES_DST: &str = "resources/buff_categories";
+
 fn main() -> anyhow::Result<()> {
     let target = std::env::current_dir()?.join(TARGET);
-    let packet_files = SUBDIRS
-        .into_iter()
+    let mut packet_files: Vec<_> = SUBDIRS
+        .iter()
         .flat_map(|sd| target.join(sd).read_dir())
         .flatten()
         .flatten()
-        .map(|e| e.path());
-    let packets = parse::parse_packets(packet_files);
+        .map(|e| e.path())
+        .collect();
+    // subpackets are numbered in the order they're parsed, so keep it the same across runs
+    packet_files.sort();
+    let packets = parse::parse_packets(packet_files.iter());
+    let packets = Allowlist::load(ALLOWLIST)?.apply(packets);
     emit::write_packets(&packets, PACKET_DST)?;
     emit::write_opcodes(&packets, OPCODE_DST)?;
+    emit::write_handler(&packets, HANDLER_DST)?;
 
     Skills::convert_and_write(SKILL, SKILL_DST)?;
+    Npcs::convert_and_write(NPC, NPC_DST)?;
+    StatusEffects::convert_and_write(STATUS_EFFECT, STATUS_EFFECT_DST)?;
+    Zones::convert_and_write(ZONE, ZONE_DST)?;
+    Bosses::convert_and_write(BOSSES, BOSSES_DST)?;
+    BuffCategories::convert_and_write(BUFF_CATEGORIES, BUFF_CATEGORIES_DST)?;
     OodleState::convert_and_write(OODLE_STATE, OODLE_STATE_DST)?;
     Xor::convert_and_write(XOR, XOR_DST)?;
 
@@ -21,14 +21,14 @@ pub struct Packet {
 pub fn parse_packets<'a>(file: impl Iterator<Item = impl AsRef<std::path::Path>>) -> Vec<Packet> {
     let packets: Vec<Packet> = file
         .map(|p| fs::read_to_string(p.as_ref()).expect("failed to read path"))
-        .map(|v| parse_packet(&v))
+        .flat_map(|v| parse_packet(&v))
         .collect();
     postprocess(packets)
 }
 
-fn parse_packet(src: &str) -> Packet {
+fn parse_packet(src: &str) -> Vec<Packet> {
     println!("{}", src);
-    let packet = match parser().parse(&*src) {
+    let packets = match parser().parse(&*src) {
         Ok(out) => out,
         Err(errs) => {
             for e in errs.into_iter() {
@@ -40,7 +40,7 @@ fn parse_packet(src: &str) -> Packet {
         }
     };
 
-    packet
+    packets
 }
 
 fn postprocess(packets: Vec<Packet>) -> Vec<Packet> {
@@ -113,6 +113,7 @@ fn lift_tuples_and_convert_builtins(packets: Vec<Packet>) -> Vec<Packet> {
             Kind::Optional(_, kind) => recurse_kinds(kind, out_packets),
             Kind::KindedBytes(kind, _, _) => recurse_kinds(kind, out_packets),
             Kind::Array { kind, .. } => recurse_kinds(kind, out_packets),
+            Kind::Repeat { kind, .. } => recurse_kinds(kind, out_packets),
             _ => {}
         }
     }
@@ -128,13 +129,14 @@ fn lift_tuples_and_convert_builtins(packets: Vec<Packet>) -> Vec<Packet> {
     out_packets
 }
 
-fn find_used_idents(fields: &[Field], out: &mut Vec<String>) {
+pub(crate) fn find_used_idents(fields: &[Field], out: &mut Vec<String>) {
     fn recurse_fields(kind: &Kind, out: &mut Vec<String>) {
         match kind {
             Kind::Optional(_, kind) => recurse_fields(kind, out),
             Kind::KindedBytes(kind, _, _) => recurse_fields(kind, out),
             Kind::Tuple(fields) => find_used_idents(fields, out),
             Kind::Array { kind, .. } => recurse_fields(kind, out),
+            Kind::Repeat { kind, .. } => recurse_fields(kind, out),
             Kind::If(_, fields) => find_used_idents(fields, out),
             _ => {}
         }
@@ -142,15 +144,14 @@ fn find_used_idents(fields: &[Field], out: &mut Vec<String>) {
 
     for field in fields {
         match &field.kind {
-            Kind::If(cond, _) | Kind::Optional(cond, _) => match cond {
-                Condition::Equality(name, _) | Condition::Greater(name, _) => {
-                    out.push(name.clone());
-                }
-                _ => {}
-            },
+            Kind::If(cond, _) | Kind::Optional(cond, _) => cond.idents(out),
             Kind::Array {
                 len: LiteralOrIdent::Ident(name),
                 ..
+            }
+            | Kind::Repeat {
+                count: LiteralOrIdent::Ident(name),
+                ..
             } => {
                 out.push(name.clone());
             }
@@ -169,6 +170,7 @@ fn strip_generated_names(fields: &mut [Field], required: &[String]) {
             Kind::KindedBytes(kind, _, _) => recurse_fields(kind, required),
             Kind::Tuple(fields) => strip_generated_names(fields, required),
             Kind::Array { kind, .. } => recurse_fields(kind, required),
+            Kind::Repeat { kind, .. } => recurse_fields(kind, required),
             Kind::If(_, fields) => strip_generated_names(fields, required),
             _ => {}
         }
@@ -218,6 +220,26 @@ pub enum Condition {
     Bool,
     Equality(String, usize),
     Greater(String, usize),
+    Less(String, usize),
+    /// Any of the bits in the mask are set.
+    Flag(String, usize),
+    Not(Box<Condition>),
+    Any(Vec<Condition>),
+}
+
+impl Condition {
+    /// Idents the condition is evaluated on.
+    fn idents(&self, out: &mut Vec<String>) {
+        match self {
+            Condition::Bool => {}
+            Condition::Equality(name, _)
+            | Condition::Greater(name, _)
+            | Condition::Less(name, _)
+            | Condition::Flag(name, _) => out.push(name.clone()),
+            Condition::Not(cond) => cond.idents(out),
+            Condition::Any(conds) => conds.iter().for_each(|c| c.idents(out)),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -264,6 +286,11 @@ pub enum Kind {
         len_kind: Box<Kind>,
         len: LiteralOrIdent,
     },
+    /// A fixed number of repetitions, without a length prefix.
+    Repeat {
+        kind: Box<Kind>,
+        count: LiteralOrIdent,
+    },
     Skip(usize),
 }
 
@@ -287,9 +314,11 @@ impl Kind {
 }
 
 /// A `chumsky` parser for `lost-ark-dev/meter-core`'s parsing format.
+/// Yields the packet itself followed by any helper functions in the same file, lifted into
+/// subpackets.
 // Won't hold up to changes in their format, but should have enough
 // constructs to be able to adapt to minor changes fairly easily.
-fn parser() -> impl Parser<char, Packet, Error = Simple<char>> + Clone {
+fn parser() -> impl Parser<char, Vec<Packet>, Error = Simple<char>> + Clone {
     let semi = just(';');
     let comma = just(',');
     let arrow = just("=>");
@@ -306,6 +335,25 @@ fn parser() -> impl Parser<char, Packet, Error = Simple<char>> + Clone {
     });
     let string = text::ident::<_, Simple<char>>().padded_by(just('"'));
     let ident = text::ident::<_, Simple<char>>().or(just('$').map(|c| c.to_string()));
+    let int = just("0x")
+        .ignore_then(text::int::<_, Simple<char>>(16))
+        .try_map(|s, span| {
+            usize::from_str_radix(&s, 16).map_err(|e| Simple::custom(span, format!("{}", e)))
+        })
+        .or(text::int::<_, Simple<char>>(10).try_map(|s, span| {
+            s.parse::<usize>()
+                .map_err(|e| Simple::custom(span, format!("{}", e)))
+        }));
+    // A local or a field of the structure being read, i.e. `flag` or `data.flag`.
+    let operand = ident
+        .then_ignore(just('.'))
+        .or_not()
+        .ignore_then(ident)
+        .map(|s| s.to_snek_case());
+    // Function parameters and return type annotation.
+    let signature = take_until(just(')'))
+        .then(just(':').padded().then(ident).or_not())
+        .padded();
 
     let comment = just("//").then(take_until(just('\n'))).padded();
     let block_comment = just("/*").then(take_until(just("*/"))).padded();
@@ -355,7 +403,7 @@ fn parser() -> impl Parser<char, Packet, Error = Simple<char>> + Clone {
                     text::int::<_, Simple<char>>(10)
                         .map(|s| s.parse::<usize>().unwrap())
                         .map(Kind::Bytes)
-                        .or(ident.map(|ident| Kind::Array {
+                        .or(operand.map(|ident| Kind::Array {
                             kind: Box::new(Kind::U8),
                             len_kind: Box::new(Kind::I64),
                             len: LiteralOrIdent::Ident(ident),
@@ -389,14 +437,21 @@ fn parser() -> impl Parser<char, Packet, Error = Simple<char>> + Clone {
                     kinded_bytes,
                     string,
                     skip,
-                    // TODO: bytes and string
                     text::ident::<char, _>()
-                        .map(|s| Kind::from_primitive_name(&s).unwrap())
+                        .try_map(|s, span| {
+                            Kind::from_primitive_name(&s).ok_or_else(|| {
+                                Simple::custom(span, format!("unknown reader call `{}`", s))
+                            })
+                        })
                         .then_ignore(just("()")),
                 ))),
                 text::ident()
                     .map(|s: String| Kind::Struct(s.to_pascal_case()))
                     .then_ignore(just(".read(reader)")),
+                // call to a helper function defined in the same file
+                text::ident()
+                    .map(|s: String| Kind::Struct(s.to_pascal_case()))
+                    .then_ignore(just("(reader)")),
             ))
         });
 
@@ -410,27 +465,37 @@ fn parser() -> impl Parser<char, Packet, Error = Simple<char>> + Clone {
             .then_ignore(just('=').padded())
             .map(|s| s.to_snek_case());
 
+        // `(flag >> 3) & 1` or `flag & 8`.
+        let flag = operand
+            .then_ignore(just(">>").padded())
+            .then(int)
+            .delimited_by(just('(').padded(), just(')').padded())
+            .or(operand.map(|ident| (ident, 0)))
+            .then_ignore(just('&').padded())
+            .then(int)
+            .map(|((ident, shift), mask)| Condition::Flag(ident, mask << shift));
+        let comparison = operand
+            .then(choice((just("==="), just("!=="), just(">"), just("<"))).padded())
+            .then(int)
+            .map(|((ident, op), val)| match op {
+                "===" => Condition::Equality(ident, val),
+                "!==" => Condition::Not(Box::new(Condition::Equality(ident, val))),
+                ">" => Condition::Greater(ident, val),
+                "<" => Condition::Less(ident, val),
+                _ => unreachable!(),
+            });
+        let condition = just("reader.bool()")
+            .map(|_| Condition::Bool)
+            .then_ignore(block_comment.or_not())
+            .or(flag)
+            .or(comparison);
+
         let if_stmt = just("if")
             .padded()
             .ignore_then(
                 just('(')
                     .padded()
-                    .ignore_then(
-                        just("reader.bool()")
-                            .map(|_| Condition::Bool)
-                            .then_ignore(block_comment.or_not())
-                            .or(ident
-                                .then(just("===").or(just(">")).padded())
-                                .then(
-                                    text::int::<_, Simple<char>>(10)
-                                        .map(|s| s.parse::<usize>().unwrap()),
-                                )
-                                .map(|((ident, op), val)| match op {
-                                    "===" => Condition::Equality(ident, val),
-                                    ">" => Condition::Greater(ident, val),
-                                    _ => unreachable!(),
-                                })),
-                    )
+                    .ignore_then(condition)
                     .then_ignore(just(")")),
             )
             .padded();
@@ -438,40 +503,190 @@ fn parser() -> impl Parser<char, Packet, Error = Simple<char>> + Clone {
         // Yields a Field.
         let statement = assignment
             .or_not()
-            .then(reader_call)
+            .then(reader_call.clone())
             .then_ignore(semi.or_not())
             .map(|(name, kind)| Field { kind, name });
 
+        // Array initialization ahead of a loop filling it in, i.e. `data.list = [];`.
+        let empty_array = assignment.then(just("[]")).then(semi.or_not()).ignored();
+
+        // A block executes only if the condition holds, whereas a single statement is kept as
+        // is with an optional value.
         let conditional_block = if_stmt
-            .then(pblock.or(statement.clone().map(|f| vec![f])))
+            .clone()
+            .then(pblock.clone())
             .map(|(cond, fields)| Field {
                 kind: Kind::If(cond, fields),
                 name: None,
+            })
+            .or(if_stmt.then(statement.clone()).map(|(cond, field)| Field {
+                kind: Kind::Optional(cond, Box::new(field.kind)),
+                name: field.name,
+            }));
+
+        // `for (let i = 0; i < count; i++)` with either a block, or a single push into an
+        // array captured as the loop's field. An inclusive `i <= 3` bound loops once more.
+        let push = ident
+            .then_ignore(just('.'))
+            .ignore_then(ident)
+            .then_ignore(just(".push("))
+            .then(reader_call)
+            .then_ignore(just(')').then(semi.or_not()).padded())
+            .map(|(name, kind)| (Some(name.to_snek_case()), kind));
+        let loop_stmt = just("for")
+            .padded()
+            .ignore_then(just('('))
+            .ignore_then(take_until(just('<')))
+            .ignore_then(just('=').or_not())
+            .then(
+                int.map(|n| LiteralOrIdent::Literal(n as u64))
+                    .or(operand.map(LiteralOrIdent::Ident))
+                    .padded(),
+            )
+            .try_map(|(inclusive, count), span| match (inclusive, count) {
+                (None, count) => Ok(count),
+                (Some(_), LiteralOrIdent::Literal(n)) => Ok(LiteralOrIdent::Literal(n + 1)),
+                (Some(_), LiteralOrIdent::Ident(ident)) => Err(Simple::custom(
+                    span,
+                    format!("inclusive loop bound on `{}`", ident),
+                )),
+            })
+            .then_ignore(take_until(just(')')).padded())
+            .then(choice((
+                push.clone()
+                    .delimited_by(open_block.padded(), close_block.padded()),
+                push,
+                pblock.clone().map(|fields| (None, Kind::Tuple(fields))),
+            )))
+            .map(|(count, (name, kind))| Field {
+                kind: Kind::Repeat {
+                    kind: Box::new(kind),
+                    count,
+                },
+                name,
+            });
+
+        // Yields a `Vec<Field>`.
+        let simple_statement = choice((
+            loop_stmt.map(|f| vec![f]),
+            conditional_block.map(|f| vec![f]),
+            empty_array.map(|_| vec![]),
+            statement.map(|f| vec![f]),
+        ));
+        let stmts = simple_statement
+            .clone()
+            .padded()
+            .repeated()
+            .map(|fs| fs.into_iter().flatten().collect::<Vec<_>>());
+
+        // Each case becomes a block conditional on the switched value, a `default` one is
+        // conditional on it matching none of the cases.
+        let brk = just("break").padded().then(semi).or_not();
+        let case_body = stmts
+            .clone()
+            .then_ignore(brk)
+            .delimited_by(open_block.padded(), close_block.padded())
+            .or(stmts.clone())
+            .then_ignore(brk);
+        let cases = just("case")
+            .padded()
+            .ignore_then(int)
+            .then_ignore(just(':').padded())
+            .repeated()
+            .at_least(1)
+            .then(case_body.clone())
+            .repeated();
+        let default = just("default")
+            .padded()
+            .ignore_then(just(':').padded())
+            .ignore_then(case_body);
+        let switch = just("switch")
+            .padded()
+            .ignore_then(operand.delimited_by(just('(').padded(), just(')').padded()))
+            .then(
+                cases
+                    .then(default.or_not())
+                    .delimited_by(open_block.padded(), close_block.padded()),
+            )
+            .map(|(ident, (cases, default))| {
+                let mut seen = vec![];
+                let mut fields = vec![];
+                for (vals, body) in cases {
+                    let mut conds: Vec<Condition> = vals
+                        .iter()
+                        .map(|&val| Condition::Equality(ident.clone(), val))
+                        .collect();
+                    seen.extend(vals);
+                    if body.is_empty() {
+                        continue;
+                    }
+                    let cond = if conds.len() == 1 {
+                        conds.pop().unwrap()
+                    } else {
+                        Condition::Any(conds)
+                    };
+                    fields.push(Field {
+                        kind: Kind::If(cond, body),
+                        name: None,
+                    });
+                }
+                if let Some(body) = default.filter(|body| !body.is_empty()) {
+                    // without any cases, the default one is always executed
+                    if seen.is_empty() {
+                        fields.extend(body);
+                        return fields;
+                    }
+                    let cases = seen
+                        .into_iter()
+                        .map(|val| Condition::Equality(ident.clone(), val))
+                        .collect();
+                    fields.push(Field {
+                        kind: Kind::If(Condition::Not(Box::new(Condition::Any(cases))), body),
+                        name: None,
+                    });
+                }
+                fields
             });
 
         let const_rdr_stmt = just("const reader").then(take_until(just(';'))).padded();
+        // Initialization of the structure being read, i.e. `const data = {} as Foo;` or
+        // `const data: Foo = new Foo();`.
         let const_stmt = just("const")
             .padded()
-            .then_ignore(ident.padded())
-            // .then_ignore(just('=').padded())
-            // .then_ignore(just("new").or(just("{}")).padded())
-            // HACK: let's hope this is always one line
-            .then(take_until(just('\n')))
+            .then(ident.padded())
+            .then(just(':').padded().then(ident.padded()).or_not())
+            .then(just('=').padded())
+            .then(just("{}").or(just("new")))
+            .then(take_until(semi.ignored().or(text::newline())))
             .padded();
         let return_stmt = just("return").padded().then(ident.padded()).then(semi);
 
         open_block
             .ignore_then(const_rdr_stmt.or_not())
             .ignore_then(const_stmt.or_not())
-            .ignore_then(choice((conditional_block, statement)).padded().repeated())
+            .ignore_then(
+                switch
+                    .or(simple_statement)
+                    .padded()
+                    .repeated()
+                    .map(|fs| fs.into_iter().flatten().collect::<Vec<_>>()),
+            )
             .then_ignore(return_stmt.or_not())
             .then_ignore(close_block.padded())
     });
 
     let read = just("export function read(")
-        .ignore_then(take_until(just(')')))
         .padded()
-        .ignore_then(parse_block);
+        .ignore_then(signature)
+        .ignore_then(parse_block.clone());
+
+    let helper = just("function")
+        .padded()
+        .ignore_then(ident)
+        .then_ignore(just('('))
+        .then_ignore(signature)
+        .then(parse_block)
+        .padded();
 
     let name = just("export const name =")
         .padded()
@@ -494,11 +709,52 @@ fn parser() -> impl Parser<char, Packet, Error = Simple<char>> + Clone {
     comment
         .ignore_then(import.repeated())
         .ignore_then(export)
+        .then(helper.clone().repeated())
         .then(read)
+        .then(helper.repeated())
         .then(metadata.or_not())
-        .map(|((name, fields), opcode)| Packet {
-            name,
-            fields,
-            opcode,
+        .map(|((((name, before), fields), after), opcode)| {
+            // Helper names are only unique within a file, so qualify them by the packet's name.
+            let helpers: Vec<(String, Vec<Field>)> = before
+                .into_iter()
+                .chain(after)
+                .map(|(helper, fields)| (helper.to_pascal_case(), fields))
+                .collect();
+            let local: Vec<String> = helpers.iter().map(|(h, _)| h.clone()).collect();
+
+            let mut packets = vec![Packet {
+                name: name.clone(),
+                fields,
+                opcode,
+            }];
+            packets.extend(helpers.into_iter().map(|(helper, fields)| Packet {
+                name: format!("{}{}", name, helper),
+                fields,
+                opcode: None,
+            }));
+            for packet in packets.iter_mut() {
+                qualify_helpers(&mut packet.fields, &name, &local);
+            }
+            packets
         })
 }
+
+/// Rename references to helper functions in `helpers` to their subpacket names.
+fn qualify_helpers(fields: &mut [Field], packet: &str, helpers: &[String]) {
+    fn recurse_kinds(kind: &mut Kind, packet: &str, helpers: &[String]) {
+        match kind {
+            Kind::Struct(name) if helpers.contains(name) => *name = format!("{}{}", packet, name),
+            Kind::Optional(_, kind) => recurse_kinds(kind, packet, helpers),
+            Kind::KindedBytes(kind, _, _) => recurse_kinds(kind, packet, helpers),
+            Kind::Array { kind, .. } | Kind::Repeat { kind, .. } => {
+                recurse_kinds(kind, packet, helpers)
+            }
+            Kind::Tuple(fields) | Kind::If(_, fields) => qualify_helpers(fields, packet, helpers),
+            _ => {}
+        }
+    }
+
+    for field in fields {
+        recurse_kinds(&mut field.kind, packet, helpers);
+    }
+}
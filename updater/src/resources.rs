@@ -68,6 +68,260 @@ impl Resource for Skills {
     }
 }
 
+pub struct Npcs;
+impl Resource for Npcs {
+    fn convert(src: Vec<u8>) -> anyhow::Result<Vec<u8>> {
+        #[derive(Debug, serde::Deserialize)]
+        struct MdNpc {
+            name: String,
+            #[serde(default)]
+            grade: String,
+            #[serde(rename = "hpBars", default)]
+            hp_bars: u16,
+        }
+
+        // order must match `NpcGrade` in the meter
+        #[derive(Debug, serde::Serialize, serde::Deserialize)]
+        enum Grade {
+            Normal,
+            Elite,
+            Boss,
+        }
+
+        #[derive(Debug, serde::Serialize, serde::Deserialize)]
+        struct Npc {
+            name: String,
+            grade: Grade,
+            hp_bars: Option<u16>,
+        }
+
+        impl From<MdNpc> for Npc {
+            fn from(n: MdNpc) -> Self {
+                let grade = match n.grade.as_str() {
+                    "boss" | "raid" | "commander" => Grade::Boss,
+                    "elite" | "named" | "seed" | "lieutenant" => Grade::Elite,
+                    _ => Grade::Normal,
+                };
+                Npc {
+                    name: n.name,
+                    grade,
+                    hp_bars: (n.hp_bars != 0).then_some(n.hp_bars),
+                }
+            }
+        }
+
+        let md_npcs: HashMap<u32, MdNpc> = serde_json::from_slice(&src)?;
+        let npcs: HashMap<u32, Npc> = md_npcs
+            .into_iter()
+            .filter(|(_, n)| !n.name.is_empty())
+            .map(|(id, n)| (id, n.into()))
+            .collect();
+
+        Ok(snappy_compress(&serde_bare::to_vec(&npcs)?)?)
+    }
+}
+
+/// Version of the status effect format, written ahead of the status effects.
+// must match `STATUS_EFFECTS_VERSION` in the meter
+const STATUS_EFFECTS_VERSION: u16 = 2;
+
+pub struct StatusEffects;
+impl Resource for StatusEffects {
+    fn convert(src: Vec<u8>) -> anyhow::Result<Vec<u8>> {
+        #[derive(Debug, serde::Deserialize)]
+        struct MdStatusEffect {
+            name: String,
+            #[serde(default)]
+            icon: String,
+            category: String,
+            #[serde(rename = "type", default)]
+            kind: String,
+            #[serde(default)]
+            buffcategory: String,
+            #[serde(default)]
+            duration: f32,
+            sourceskill: Option<u32>,
+            sourceskills: Option<Vec<u32>>,
+            #[serde(default)]
+            passiveoption: Vec<MdPassiveOption>,
+        }
+
+        #[derive(Debug, serde::Deserialize)]
+        struct MdPassiveOption {
+            #[serde(rename = "type")]
+            kind: String,
+            #[serde(default)]
+            keystat: String,
+            #[serde(default)]
+            value: i32,
+        }
+
+        // order must match `StatusEffectCategory` in the meter
+        #[derive(Debug, serde::Serialize, serde::Deserialize)]
+        enum Category {
+            Buff,
+            Debuff,
+        }
+
+        #[derive(Debug, serde::Serialize, serde::Deserialize)]
+        struct StatusEffect {
+            name: String,
+            icon: Option<String>,
+            category: Category,
+            buff_category: Option<String>,
+            source_skill: Option<u32>,
+            duration: Option<f32>,
+            /// Stats granted per stack, in hundredths of a percent.
+            stats: Vec<(String, i32)>,
+            shield: bool,
+        }
+
+        impl From<MdStatusEffect> for StatusEffect {
+            fn from(e: MdStatusEffect) -> Self {
+                let source_skill = e
+                    .sourceskill
+                    .or_else(|| e.sourceskills.and_then(|s| s.first().copied()));
+                StatusEffect {
+                    name: e.name,
+                    icon: (!e.icon.is_empty()).then_some(e.icon),
+                    category: match e.category.as_str() {
+                        "debuff" => Category::Debuff,
+                        _ => Category::Buff,
+                    },
+                    buff_category: (!e.buffcategory.is_empty()).then_some(e.buffcategory),
+                    source_skill: source_skill.filter(|&id| id != 0),
+                    duration: (e.duration > 0.0).then_some(e.duration),
+                    stats: e
+                        .passiveoption
+                        .into_iter()
+                        .filter(|o| o.kind == "stat" && !o.keystat.is_empty())
+                        .map(|o| (o.keystat, o.value))
+                        .collect(),
+                    shield: e.kind == "shield",
+                }
+            }
+        }
+
+        let md_effects: HashMap<u32, MdStatusEffect> = serde_json::from_slice(&src)?;
+        let effects: HashMap<u32, StatusEffect> = md_effects
+            .into_iter()
+            .map(|(id, e)| (id, e.into()))
+            .collect();
+
+        Ok(snappy_compress(&serde_bare::to_vec(&(
+            STATUS_EFFECTS_VERSION,
+            effects,
+        ))?)?)
+    }
+}
+
+pub struct Zones;
+impl Resource for Zones {
+    fn convert(src: Vec<u8>) -> anyhow::Result<Vec<u8>> {
+        #[derive(Debug, serde::Deserialize)]
+        struct MdZone {
+            #[serde(default)]
+            name: String,
+        }
+
+        let md_zones: HashMap<u32, MdZone> = serde_json::from_slice(&src)?;
+        let zones: HashMap<u32, String> = md_zones
+            .into_iter()
+            .filter(|(_, z)| !z.name.is_empty())
+            .map(|(id, z)| (id, z.name))
+            .collect();
+
+        Ok(snappy_compress(&serde_bare::to_vec(&zones)?)?)
+    }
+}
+
+/// Curated boss encounter definitions.
+pub struct Bosses;
+impl Resource for Bosses {
+    fn convert(src: Vec<u8>) -> anyhow::Result<Vec<u8>> {
+        // order must match `BossRole` in the meter
+        #[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+        #[serde(rename_all = "lowercase")]
+        enum Role {
+            #[default]
+            Start,
+            Extend,
+        }
+
+        #[derive(Debug, serde::Serialize, serde::Deserialize)]
+        struct Npc {
+            id: u32,
+            #[serde(default)]
+            bars: Option<u16>,
+            #[serde(default)]
+            role: Role,
+        }
+
+        #[derive(Debug, serde::Serialize, serde::Deserialize)]
+        struct Encounter {
+            raid: String,
+            #[serde(default)]
+            gate: Option<u8>,
+            #[serde(default)]
+            difficulty: Option<String>,
+            npcs: Vec<Npc>,
+        }
+
+        let encounters: Vec<Encounter> = serde_json::from_slice(&src)?;
+        Ok(snappy_compress(&serde_bare::to_vec(&encounters)?)?)
+    }
+}
+
+/// Curated categories of buffs that the meter tracks damage dealt under, and the support
+/// identity skills that apply them.
+pub struct BuffCategories;
+impl Resource for BuffCategories {
+    fn convert(src: Vec<u8>) -> anyhow::Result<Vec<u8>> {
+        // order must match `BuffHolder` in the meter
+        #[derive(Debug, serde::Serialize, serde::Deserialize)]
+        #[serde(rename_all = "lowercase")]
+        enum Holder {
+            Attacker,
+            Target,
+        }
+
+        // order must match `StatusEffectCategory` in the meter
+        #[derive(Debug, serde::Serialize, serde::Deserialize)]
+        #[serde(rename_all = "lowercase")]
+        enum Kind {
+            Buff,
+            Debuff,
+        }
+
+        #[derive(Debug, serde::Serialize, serde::Deserialize)]
+        struct Category {
+            name: String,
+            holder: Holder,
+            #[serde(default)]
+            ids: Vec<u32>,
+            #[serde(default)]
+            buff_categories: Vec<String>,
+            #[serde(default)]
+            kind: Option<Kind>,
+            #[serde(default)]
+            stats: Vec<String>,
+            #[serde(default)]
+            supports_only: bool,
+        }
+
+        // must match `BuffDefinitions` in the meter
+        #[derive(Debug, serde::Serialize, serde::Deserialize)]
+        struct Definitions {
+            categories: Vec<Category>,
+            #[serde(default)]
+            support_identity_skills: Vec<u32>,
+        }
+
+        let definitions: Definitions = serde_json::from_slice(&src)?;
+        Ok(snappy_compress(&serde_bare::to_vec(&definitions)?)?)
+    }
+}
+
 fn snappy_compress(bytes: &[u8]) -> anyhow::Result<Vec<u8>> {
     use std::io::Write as _;
     let mut buf = Vec::new();
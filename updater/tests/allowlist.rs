@@ -0,0 +1,146 @@
+//! Packets and fields outside the allowlist must not be emitted, while still being parsed over.
+
+use std::{fs, path::Path};
+
+use updater::{
+    allowlist::Allowlist,
+    emit,
+    parse::{Field, Kind, LiteralOrIdent, Packet},
+};
+
+fn field(name: Option<&str>, kind: Kind) -> Field {
+    Field {
+        name: name.map(Into::into),
+        kind,
+    }
+}
+
+fn packets() -> Vec<Packet> {
+    vec![
+        Packet {
+            name: "PktKept".into(),
+            opcode: Some(1),
+            fields: vec![
+                field(Some("count"), Kind::U16),
+                field(Some("kept"), Kind::U32),
+                field(Some("dropped"), Kind::U64),
+                field(Some("dropped_name"), Kind::String(20)),
+                field(Some("sub"), Kind::Struct("SubKept".into())),
+                field(Some("parsed_over"), Kind::Struct("SubParsed".into())),
+                field(
+                    Some("list"),
+                    Kind::Array {
+                        kind: Box::new(Kind::U32),
+                        len_kind: Box::new(Kind::U16),
+                        len: LiteralOrIdent::Ident("count".into()),
+                    },
+                ),
+            ],
+        },
+        Packet {
+            name: "PktDropped".into(),
+            opcode: Some(2),
+            fields: vec![field(Some("value"), Kind::Struct("SubDropped".into()))],
+        },
+        Packet {
+            name: "SubKept".into(),
+            opcode: None,
+            fields: vec![field(Some("inner"), Kind::U32)],
+        },
+        Packet {
+            name: "SubParsed".into(),
+            opcode: None,
+            fields: vec![field(Some("inner_parsed"), Kind::U8)],
+        },
+        Packet {
+            name: "SubDropped".into(),
+            opcode: None,
+            fields: vec![field(Some("inner_dropped"), Kind::U8)],
+        },
+    ]
+}
+
+fn load(name: &str, json: &str) -> Allowlist {
+    let path = Path::new(env!("CARGO_TARGET_TMPDIR")).join(name);
+    fs::write(&path, json).unwrap();
+    Allowlist::load(path.to_str().unwrap()).unwrap()
+}
+
+fn emitted(name: &str, emit: impl FnOnce(&str) -> anyhow::Result<()>) -> String {
+    let path = Path::new(env!("CARGO_TARGET_TMPDIR")).join(name);
+    emit(path.to_str().unwrap()).unwrap();
+    fs::read_to_string(path).unwrap()
+}
+
+#[test]
+fn unlisted_packets_and_fields_are_not_emitted() {
+    let packets = load("allowlist_fields.json", r#"{"PktKept": ["kept", "sub"]}"#).apply(packets());
+
+    let names: Vec<&str> = packets.iter().map(|p| &p.name[..]).collect();
+    assert_eq!(names, ["PktKept", "SubKept", "SubParsed"]);
+
+    let kept = &packets[0];
+    let captured: Vec<&str> = kept
+        .fields
+        .iter()
+        .filter_map(|f| f.name.as_deref())
+        .collect();
+    // `count` is the length of `list`, which must still be parsed over
+    assert_eq!(captured, ["count", "kept", "sub"]);
+    assert!(packets[2].fields.iter().all(|f| f.name.is_none()));
+
+    let packets_rs = emitted("allowlist_packets.rs", |dst| {
+        emit::write_packets(&packets, dst)
+    });
+    assert!(packets_rs.contains("pub kept: u32"));
+    assert!(packets_rs.contains("pub inner: u32"));
+    for absent in [
+        "PktDropped",
+        "SubDropped",
+        "dropped",
+        "list",
+        "inner_parsed",
+    ] {
+        assert!(
+            !packets_rs.contains(absent),
+            "`{}` was emitted:\n{}",
+            absent,
+            packets_rs
+        );
+    }
+
+    let opcodes_rs = emitted("allowlist_opcodes.rs", |dst| {
+        emit::write_opcodes(&packets, dst)
+    });
+    assert!(opcodes_rs.contains("Kept"));
+    assert!(!opcodes_rs.contains("Dropped"));
+
+    let handler_rs = emitted("allowlist_handler.rs", |dst| {
+        emit::write_handler(&packets, dst)
+    });
+    assert!(handler_rs.contains("on_kept"));
+    assert!(!handler_rs.contains("on_dropped"));
+}
+
+#[test]
+fn null_keeps_every_field() {
+    let packets = load("allowlist_null.json", r#"{"PktKept": null}"#).apply(packets());
+
+    let captured: Vec<&str> = packets[0]
+        .fields
+        .iter()
+        .filter_map(|f| f.name.as_deref())
+        .collect();
+    assert_eq!(
+        captured,
+        [
+            "count",
+            "kept",
+            "dropped",
+            "dropped_name",
+            "sub",
+            "parsed_over",
+            "list"
+        ]
+    );
+}
@@ -0,0 +1,21 @@
+//! The meter's packet parsing primitives, with the generated code snapshots in place of
+//! `src/generated`.
+
+#![feature(allocator_api)]
+#![feature(vec_into_raw_parts)]
+#![feature(associated_type_defaults)]
+#![allow(dead_code)]
+
+#[path = "../../../../src/definitions.rs"]
+pub mod definitions;
+#[path = "../../../../src/parser.rs"]
+pub mod parser;
+
+#[path = "../../snapshots/opcodes.rs"]
+pub mod opcode;
+#[path = "../../snapshots/packets.rs"]
+pub mod packet;
+
+pub mod generated {
+    pub use super::{opcode, packet};
+}
@@ -0,0 +1,17 @@
+// Auto Generated, do not edit.
+import { Read } from "../../stream";
+import * as FixtureSub from "./FixtureSub";
+export type FixtureOptions = {
+  layerIndex?: number;
+  transitIndex?: number;
+  sub?: FixtureSub.FixtureSub;
+};
+export function read(reader: Read) {
+  const data = {} as FixtureOptions;
+  const flag = reader.u8();
+  if (flag & 1) data.layerIndex = reader.u8();
+  if ((flag >> 1) & 1) data.transitIndex = reader.u32();
+  if ((flag >> 2) & 1) data.sub = FixtureSub.read(reader);
+  if ((flag >> 3) & 1) data.unk3 = reader.bytes(reader.u16(), 6);
+  return data;
+}
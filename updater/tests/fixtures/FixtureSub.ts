@@ -0,0 +1,12 @@
+// Auto Generated, do not edit.
+import { Read } from "../../stream";
+export type FixtureSub = {
+  id: number;
+  value: number;
+};
+export function read(reader: Read) {
+  const data = {} as FixtureSub;
+  data.id = reader.u32();
+  data.value = reader.i16();
+  return data;
+}
@@ -0,0 +1,56 @@
+// Auto Generated, do not edit.
+import { Read } from "../../stream";
+import * as ReadNBytesInt64 from "../../common/ReadNBytesInt64";
+import * as LostArkDateTime from "../../common/LostArkDateTime";
+import * as Vector3F from "../../common/Vector3F";
+import * as Angle from "../../common/Angle";
+import * as FixtureSub from "../structures/FixtureSub";
+export type PKTFixtureBasic = {
+  objectId: bigint;
+  level: number;
+  hp: bigint;
+  name: string;
+  position: Vector3F.Vector3F;
+  direction: Angle.Angle;
+  createdAt: Date;
+  speed: number;
+  isDead: boolean;
+  tint: Buffer;
+  fixed: Buffer;
+  ids: number[];
+  subs: FixtureSub.FixtureSub[];
+  pairs: { a: number; b: number }[];
+};
+export function read(buf: Buffer) {
+  const reader = new Read(buf);
+  const data = {} as PKTFixtureBasic;
+  data.objectId = reader.u64();
+  data.level = reader.u16();
+  data.hp = ReadNBytesInt64.read(reader);
+  data.name = reader.string(20);
+  reader.skip(3);
+  data.unk0 = reader.u32();
+  data.position = Vector3F.read(reader);
+  data.direction = Angle.read(reader);
+  data.createdAt = LostArkDateTime.read(reader);
+  data.speed = reader.f32();
+  data.isDead = reader.bool();
+  data.tint = reader.bytes(reader.u16(), 14, 5);
+  data.unk1 = reader.bytes(reader.u32(), 688);
+  data.fixed = reader.bytes(16);
+  data.ids = reader.array(reader.u16(), () => reader.u32(), 10);
+  data.subs = reader.array(reader.u8(), () => FixtureSub.read(reader), 3);
+  data.pairs = reader.array(
+    reader.u16(),
+    () => {
+      const s: any = {};
+      s.a = reader.u32();
+      s.b = reader.i8();
+      return s;
+    },
+    5
+  );
+  return data;
+}
+export const name = "PKTFixtureBasic";
+export const opcode = 1001;
@@ -0,0 +1,53 @@
+// Auto Generated, do not edit.
+import { Read } from "../../stream";
+import * as FixtureSub from "../structures/FixtureSub";
+export type PKTFixtureConditional = {
+  sub?: FixtureSub.FixtureSub;
+  targetId?: bigint;
+  flag: number;
+  first?: number;
+  third?: number;
+  kind: number;
+  big?: number;
+  small?: number;
+  notThree?: number;
+  always: number;
+};
+export function read(buf: Buffer) {
+  const reader = new Read(buf);
+  const data = {} as PKTFixtureConditional;
+  if (reader.bool()) data.sub = FixtureSub.read(reader);
+  if (reader.bool() /* hasTarget */) data.targetId = reader.u64();
+  data.flag = reader.u8();
+  if (data.flag & 1) data.first = reader.u8();
+  if ((data.flag >> 2) & 1) data.third = reader.u32();
+  data.kind = reader.u8();
+  if (kind === 1) {
+    data.unk2 = reader.u32();
+    reader.skip(2);
+  }
+  if (kind > 2) data.big = reader.u16();
+  if (kind < 2) data.small = reader.u16();
+  if (kind !== 3) data.notThree = reader.u8();
+  switch (data.kind) {
+    case 1:
+      data.one = reader.u32();
+      break;
+    case 2:
+    case 3: {
+      data.twoOrThree = FixtureSub.read(reader);
+      break;
+    }
+    case 4:
+      break;
+    default:
+      reader.skip(8);
+  }
+  switch (data.kind) {
+    default:
+      data.always = reader.u16();
+  }
+  return data;
+}
+export const name = "PKTFixtureConditional";
+export const opcode = 1002;
@@ -0,0 +1,41 @@
+// Auto Generated, do not edit.
+import { Read } from "../../stream";
+import * as FixtureSub from "../structures/FixtureSub";
+export type PKTFixtureLoop = {
+  fixedList: number[];
+  inclusiveList: number[];
+  count: number;
+  subs: FixtureSub.FixtureSub[];
+  len: number;
+  payload: Buffer;
+  extra: any;
+};
+function readExtra(reader: Read): any {
+  const data = {} as any;
+  data.extraId = reader.u32();
+  data.extraName = reader.string(7);
+  return data;
+}
+export function read(buf: Buffer) {
+  const reader = new Read(buf);
+  const data = {} as PKTFixtureLoop;
+  data.fixedList = [];
+  for (let i = 0; i < 3; i++) data.fixedList.push(reader.u32());
+  data.inclusiveList = [];
+  for (let i = 0; i <= 2; i++) data.inclusiveList.push(reader.u8());
+  data.count = reader.u8();
+  data.subs = [];
+  for (let i = 0; i < data.count; i++) {
+    data.subs.push(FixtureSub.read(reader));
+  }
+  for (let i = 0; i < 2; i++) {
+    reader.skip(1);
+    data.unk3 = reader.u16();
+  }
+  data.len = reader.u16();
+  data.payload = reader.bytes(len);
+  data.extra = readExtra(reader);
+  return data;
+}
+export const name = "PKTFixtureLoop";
+export const opcode = 1003;
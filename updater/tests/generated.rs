@@ -0,0 +1,48 @@
+//! The checked-in packet code must be exactly what the updater generates from meter-core's
+//! structures with the allowlist applied, rather than edited by hand.
+//!
+//! Only runs with meter-core checked out at `updater/meter-core`.
+
+use std::{fs, path::Path};
+
+use updater::{allowlist::Allowlist, emit, parse};
+
+const ROOT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/..");
+const SUBDIRS: &[&str] = &["definitions", "structures"];
+
+#[test]
+fn generated_code_is_up_to_date() {
+    let root = Path::new(ROOT);
+    let target = root.join("updater/meter-core/src/packets/generated");
+    if !target.exists() {
+        println!("meter-core isn't checked out, skipping");
+        return;
+    }
+
+    let mut packet_files: Vec<_> = SUBDIRS
+        .iter()
+        .flat_map(|sd| fs::read_dir(target.join(sd)).unwrap())
+        .map(|e| e.unwrap().path())
+        .collect();
+    packet_files.sort();
+    let packets = parse::parse_packets(packet_files.iter());
+    let allowlist = root.join("updater/allowlist.json");
+    let packets = Allowlist::load(allowlist.to_str().unwrap())
+        .unwrap()
+        .apply(packets);
+
+    let tmp = Path::new(env!("CARGO_TARGET_TMPDIR"));
+    let dst = |name: &str| tmp.join(name).to_str().unwrap().to_owned();
+    emit::write_packets(&packets, &dst("packet.rs")).unwrap();
+    emit::write_opcodes(&packets, &dst("opcode.rs")).unwrap();
+    emit::write_handler(&packets, &dst("handler.rs")).unwrap();
+    for name in ["packet.rs", "opcode.rs", "handler.rs"] {
+        let expected = fs::read_to_string(dst(name)).unwrap();
+        let actual = fs::read_to_string(root.join("src/generated").join(name)).unwrap();
+        assert!(
+            expected == actual,
+            "src/generated/{} differs from the updater's output, run the updater to regenerate it",
+            name
+        );
+    }
+}
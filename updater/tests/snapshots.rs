@@ -0,0 +1,81 @@
+//! Snapshot tests of the packet structures parsed from `tests/fixtures` and the code generated
+//! from them.
+//!
+//! Run with `UPDATE_SNAPSHOTS=1` to overwrite the snapshots after an intended change, and review
+//! the diff.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use updater::{emit, parse};
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+const SNAPSHOTS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/snapshots");
+const CODEGEN: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/codegen/Cargo.toml");
+
+fn assert_snapshot(name: &str, actual: &str) {
+    let path = Path::new(SNAPSHOTS).join(name);
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&path)
+        .unwrap_or_else(|_| panic!("missing snapshot `{}`, run with UPDATE_SNAPSHOTS=1", name));
+    assert!(
+        expected == actual,
+        "snapshot `{}` differs, run with UPDATE_SNAPSHOTS=1 to update it\n\n{}",
+        name,
+        actual
+    );
+}
+
+/// Run an emitter writing to a file and return what it wrote.
+fn emitted(name: &str, emit: impl FnOnce(&str) -> anyhow::Result<()>) -> String {
+    let path = Path::new(env!("CARGO_TARGET_TMPDIR")).join(name);
+    emit(path.to_str().unwrap()).unwrap();
+    fs::read_to_string(path).unwrap()
+}
+
+// Subpacket names are numbered by a global counter, so all fixtures are parsed exactly once in
+// a single test.
+#[test]
+fn parse_and_emit() {
+    let mut fixtures: Vec<PathBuf> = fs::read_dir(FIXTURES)
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .filter(|p| p.extension().is_some_and(|e| e == "ts"))
+        .collect();
+    fixtures.sort();
+
+    let packets = parse::parse_packets(fixtures.iter());
+    assert_snapshot("ir.txt", &format!("{:#?}\n", packets));
+
+    let packets_rs = emitted("packets.rs", |dst| emit::write_packets(&packets, dst));
+    assert_snapshot("packets.rs", &packets_rs);
+
+    let opcodes_rs = emitted("opcodes.rs", |dst| emit::write_opcodes(&packets, dst));
+    assert_snapshot("opcodes.rs", &opcodes_rs);
+}
+
+/// The snapshotted code is built against the meter's actual parser in `tests/codegen`.
+#[test]
+fn generated_code_compiles() {
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let output = Command::new(cargo)
+        .args(["check", "--quiet", "--manifest-path", CODEGEN])
+        .env(
+            "CARGO_TARGET_DIR",
+            Path::new(env!("CARGO_TARGET_TMPDIR")).join("codegen"),
+        )
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "generated code failed to compile:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
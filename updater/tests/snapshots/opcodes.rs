@@ -0,0 +1,26 @@
+// NOTE: This file is automatically generated.
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Opcode {
+    FixtureBasic,
+    FixtureConditional,
+    FixtureLoop,
+}
+
+impl Opcode {
+    pub const fn from_u16(raw: u16) -> Option<Self> {
+        Some(match raw {
+            1001 => Opcode::FixtureBasic,
+            1002 => Opcode::FixtureConditional,
+            1003 => Opcode::FixtureLoop,
+            _ => return None,
+        })
+    }
+    pub const fn to_u16(self: Self) -> u16 {
+        match self {
+            Opcode::FixtureBasic => 1001,
+            Opcode::FixtureConditional => 1002,
+            Opcode::FixtureLoop => 1003,
+        }
+    }
+}
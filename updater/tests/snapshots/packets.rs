@@ -0,0 +1,344 @@
+//! LoA packet structures.
+
+// NOTE: This file is automatically generated.
+
+use super::opcode::Opcode;
+use crate::parser::{serialize_bumpvec, BumpVec, Event, KindedBytes, Packet, Parser};
+
+#[derive(serde::Serialize)]
+pub struct FixtureOptions {
+    pub flag: u8,
+    pub layer_index: Option<u8>,
+    pub transit_index: Option<u32>,
+    pub sub: Option<FixtureSub>,
+}
+
+impl<'bump> Event<'bump> for FixtureOptions {
+    fn parse(parser: &mut Parser, bump: &'bump bumpalo::Bump) -> anyhow::Result<Self> {
+        let flag = parser.read_u8()?;
+        let layer_index = if flag & 0x1 != 0 {
+            Some(parser.read_u8()?)
+        } else {
+            None
+        };
+        let transit_index = if flag & 0x2 != 0 {
+            Some(parser.read_u32()?)
+        } else {
+            None
+        };
+        let sub = if flag & 0x4 != 0 {
+            Some(<FixtureSub>::parse(parser, bump)?)
+        } else {
+            None
+        };
+        if flag & 0x8 != 0 {
+            Some(KindedBytes::<u16, 1, 6>::parse(parser, bump)?)
+        } else {
+            None
+        };
+        Ok(Self {
+            flag,
+            layer_index,
+            transit_index,
+            sub,
+        })
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct FixtureSub {
+    pub id: u32,
+    pub value: i16,
+}
+
+impl<'bump> Event<'bump> for FixtureSub {
+    fn parse(parser: &mut Parser, _: &'bump bumpalo::Bump) -> anyhow::Result<Self> {
+        let id = parser.read_u32()?;
+        let value = parser.read_i16()?;
+        Ok(Self { id, value })
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct Sub0 {
+    pub a: u32,
+    pub b: i8,
+}
+
+impl<'bump> Event<'bump> for Sub0 {
+    fn parse(parser: &mut Parser, _: &'bump bumpalo::Bump) -> anyhow::Result<Self> {
+        let a = parser.read_u32()?;
+        let b = parser.read_i8()?;
+        Ok(Self { a, b })
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct PktFixtureBasic<'bump> {
+    pub object_id: u64,
+    pub level: u16,
+    pub hp: i64,
+    pub name: &'bump str,
+    pub position: u64,
+    pub direction: u16,
+    pub created_at: u64,
+    pub speed: f32,
+    pub is_dead: bool,
+    pub tint: &'bump [u8],
+    pub fixed: [u8; 16],
+    #[serde(serialize_with = "serialize_bumpvec")]
+    pub ids: BumpVec<'bump, u32>,
+    #[serde(serialize_with = "serialize_bumpvec")]
+    pub subs: BumpVec<'bump, FixtureSub>,
+    #[serde(serialize_with = "serialize_bumpvec")]
+    pub pairs: BumpVec<'bump, Sub0>,
+}
+
+impl Packet for PktFixtureBasic<'_> {
+    const OPCODE: Opcode = Opcode::FixtureBasic;
+}
+
+impl<'bump> Event<'bump> for PktFixtureBasic<'bump> {
+    fn parse(parser: &mut Parser, bump: &'bump bumpalo::Bump) -> anyhow::Result<Self> {
+        let object_id = parser.read_u64()?;
+        let level = parser.read_u16()?;
+        let hp = parser.read_packed_i64()?;
+        let name = parser.read_str(bump)?;
+        parser.skip(3)?;
+        parser.read_u32()?;
+        let position = parser.read_u64()?;
+        let direction = parser.read_u16()?;
+        let created_at = parser.read_simple_u64()?;
+        let speed = parser.read_f32()?;
+        let is_dead = parser.read_bool()?;
+        let tint = parser.read_kinded_bytes::<u16>(bump, 5, 14)?;
+        KindedBytes::<u32, 1, 688>::parse(parser, bump)?;
+        let fixed = <[u8; 16]>::parse(parser, bump)?;
+        let ids = parser.read_counted::<u32, u16>(bump, 10)?;
+        let subs = parser.read_counted::<FixtureSub, u8>(bump, 3)?;
+        let pairs = parser.read_counted::<Sub0, u16>(bump, 5)?;
+        Ok(Self {
+            object_id,
+            level,
+            hp,
+            name,
+            position,
+            direction,
+            created_at,
+            speed,
+            is_dead,
+            tint,
+            fixed,
+            ids,
+            subs,
+            pairs,
+        })
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct Sub1;
+
+impl<'bump> Event<'bump> for Sub1 {
+    fn parse(parser: &mut Parser, _: &'bump bumpalo::Bump) -> anyhow::Result<Self> {
+        parser.read_u32()?;
+        parser.skip(2)?;
+        Ok(Self)
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct Sub2 {
+    pub one: u32,
+}
+
+impl<'bump> Event<'bump> for Sub2 {
+    fn parse(parser: &mut Parser, _: &'bump bumpalo::Bump) -> anyhow::Result<Self> {
+        let one = parser.read_u32()?;
+        Ok(Self { one })
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct Sub3 {
+    pub two_or_three: FixtureSub,
+}
+
+impl<'bump> Event<'bump> for Sub3 {
+    fn parse(parser: &mut Parser, bump: &'bump bumpalo::Bump) -> anyhow::Result<Self> {
+        let two_or_three = <FixtureSub>::parse(parser, bump)?;
+        Ok(Self { two_or_three })
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct Sub4;
+
+impl<'bump> Event<'bump> for Sub4 {
+    fn parse(parser: &mut Parser, _: &'bump bumpalo::Bump) -> anyhow::Result<Self> {
+        parser.skip(8)?;
+        Ok(Self)
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct PktFixtureConditional {
+    pub sub: Option<FixtureSub>,
+    pub target_id: Option<u64>,
+    pub flag: u8,
+    pub first: Option<u8>,
+    pub third: Option<u32>,
+    pub kind: u8,
+    pub big: Option<u16>,
+    pub small: Option<u16>,
+    pub not_three: Option<u8>,
+    pub always: u16,
+}
+
+impl Packet for PktFixtureConditional {
+    const OPCODE: Opcode = Opcode::FixtureConditional;
+}
+
+impl<'bump> Event<'bump> for PktFixtureConditional {
+    fn parse(parser: &mut Parser, bump: &'bump bumpalo::Bump) -> anyhow::Result<Self> {
+        let sub = if parser.read_bool()? {
+            Some(<FixtureSub>::parse(parser, bump)?)
+        } else {
+            None
+        };
+        let target_id = if parser.read_bool()? {
+            Some(parser.read_u64()?)
+        } else {
+            None
+        };
+        let flag = parser.read_u8()?;
+        let first = if flag & 0x1 != 0 {
+            Some(parser.read_u8()?)
+        } else {
+            None
+        };
+        let third = if flag & 0x4 != 0 {
+            Some(parser.read_u32()?)
+        } else {
+            None
+        };
+        let kind = parser.read_u8()?;
+        if kind == 1 {
+            Some(<Sub1>::parse(parser, bump)?)
+        } else {
+            None
+        };
+        let big = if kind > 2 {
+            Some(parser.read_u16()?)
+        } else {
+            None
+        };
+        let small = if kind < 2 {
+            Some(parser.read_u16()?)
+        } else {
+            None
+        };
+        let not_three = if !(kind == 3) {
+            Some(parser.read_u8()?)
+        } else {
+            None
+        };
+        if kind == 1 {
+            Some(<Sub2>::parse(parser, bump)?)
+        } else {
+            None
+        };
+        if kind == 2 || kind == 3 {
+            Some(<Sub3>::parse(parser, bump)?)
+        } else {
+            None
+        };
+        if !(kind == 1 || kind == 2 || kind == 3 || kind == 4) {
+            Some(<Sub4>::parse(parser, bump)?)
+        } else {
+            None
+        };
+        let always = parser.read_u16()?;
+        Ok(Self {
+            sub,
+            target_id,
+            flag,
+            first,
+            third,
+            kind,
+            big,
+            small,
+            not_three,
+            always,
+        })
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct Sub5;
+
+impl<'bump> Event<'bump> for Sub5 {
+    fn parse(parser: &mut Parser, _: &'bump bumpalo::Bump) -> anyhow::Result<Self> {
+        parser.skip(1)?;
+        parser.read_u16()?;
+        Ok(Self)
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct PktFixtureLoop<'bump> {
+    #[serde(serialize_with = "serialize_bumpvec")]
+    pub fixed_list: BumpVec<'bump, u32>,
+    #[serde(serialize_with = "serialize_bumpvec")]
+    pub inclusive_list: BumpVec<'bump, u8>,
+    pub count: u8,
+    #[serde(serialize_with = "serialize_bumpvec")]
+    pub subs: BumpVec<'bump, FixtureSub>,
+    pub len: u16,
+    #[serde(serialize_with = "serialize_bumpvec")]
+    pub payload: BumpVec<'bump, u8>,
+    pub extra: PktFixtureLoopReadExtra<'bump>,
+}
+
+impl Packet for PktFixtureLoop<'_> {
+    const OPCODE: Opcode = Opcode::FixtureLoop;
+}
+
+impl<'bump> Event<'bump> for PktFixtureLoop<'bump> {
+    fn parse(parser: &mut Parser, bump: &'bump bumpalo::Bump) -> anyhow::Result<Self> {
+        let fixed_list = parser.read_repeated::<u32>(bump, 3)?;
+        let inclusive_list = parser.read_repeated::<u8>(bump, 3)?;
+        let count = parser.read_u8()?;
+        let subs = parser.read_repeated::<FixtureSub>(bump, count as usize)?;
+        parser.read_repeated::<Sub5>(bump, 2)?;
+        let len = parser.read_u16()?;
+        let payload = parser.read_repeated::<u8>(bump, len as usize)?;
+        let extra = <PktFixtureLoopReadExtra>::parse(parser, bump)?;
+        Ok(Self {
+            fixed_list,
+            inclusive_list,
+            count,
+            subs,
+            len,
+            payload,
+            extra,
+        })
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct PktFixtureLoopReadExtra<'bump> {
+    pub extra_id: u32,
+    pub extra_name: &'bump str,
+}
+
+impl<'bump> Event<'bump> for PktFixtureLoopReadExtra<'bump> {
+    fn parse(parser: &mut Parser, bump: &'bump bumpalo::Bump) -> anyhow::Result<Self> {
+        let extra_id = parser.read_u32()?;
+        let extra_name = parser.read_str(bump)?;
+        Ok(Self {
+            extra_id,
+            extra_name,
+        })
+    }
+}